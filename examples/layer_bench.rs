@@ -0,0 +1,133 @@
+//! Compares two ways of caching render layers.
+//!
+//! - **bitmap readback**: what `dom::layer` used to do. Every layer is drawn on
+//!   the shared window canvas and read back into a window-sized bitmap, which
+//!   is written back onto the canvas on composite.
+//! - **picture**: every layer is recorded as a skia `Picture` and replayed on
+//!   composite.
+//!
+//! Run with `cargo run --release --example layer_bench`.
+
+use std::time::{Duration, Instant};
+
+use irisia::skia_safe::{
+    colors::TRANSPARENT, Bitmap, Canvas, Color, Paint, Picture, PictureRecorder, Rect, Surface,
+};
+
+const WIDTH: i32 = 1920;
+const HEIGHT: i32 = 1080;
+const LAYERS: usize = 8;
+const FRAMES: u32 = 60;
+
+fn main() {
+    let readback = bitmap_readback();
+    let picture = picture_replay();
+
+    println!("{LAYERS} layers, {WIDTH}x{HEIGHT}, {FRAMES} frames");
+    readback.print("bitmap readback");
+    picture.print("picture");
+    println!(
+        "picture layers use {:.2}% of the memory and {:.2}% of the time",
+        picture.layer_bytes as f64 / readback.layer_bytes as f64 * 100.0,
+        picture.elapsed.as_secs_f64() / readback.elapsed.as_secs_f64() * 100.0,
+    );
+}
+
+struct Report {
+    elapsed: Duration,
+    layer_bytes: usize,
+}
+
+impl Report {
+    fn print(&self, name: &str) {
+        println!(
+            "{name:>16}: {:>10.3}ms per frame, {:>10}KiB held by layers",
+            self.elapsed.as_secs_f64() * 1000.0 / FRAMES as f64,
+            self.layer_bytes / 1024
+        );
+    }
+}
+
+fn draw_layer_content(canvas: &mut Canvas, layer: usize) {
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true);
+
+    for index in 0..50 {
+        let offset = (layer * 50 + index) as f32;
+        paint.set_color(Color::from_rgb(
+            (offset * 7.0) as u8,
+            (offset * 13.0) as u8,
+            (offset * 3.0) as u8,
+        ));
+        canvas.draw_rect(
+            Rect::from_xywh(offset * 3.0, offset * 2.0, 120.0, 40.0),
+            &paint,
+        );
+        canvas.draw_circle((offset * 4.0, offset * 1.5), 20.0, &paint);
+    }
+}
+
+fn bitmap_readback() -> Report {
+    let mut surface =
+        Surface::new_raster_n32_premul((WIDTH, HEIGHT)).expect("cannot create surface");
+    let image_info = surface.image_info();
+    let mut bitmaps: Vec<Bitmap> = (0..LAYERS)
+        .map(|_| {
+            let mut bitmap = Bitmap::new();
+            bitmap.alloc_pixels_info(&image_info, None);
+            bitmap
+        })
+        .collect();
+
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        let canvas = surface.canvas();
+        for (layer, bitmap) in bitmaps.iter_mut().enumerate() {
+            canvas.clear(TRANSPARENT);
+            draw_layer_content(canvas, layer);
+            assert!(canvas.read_pixels_to_bitmap(bitmap, (0, 0)));
+        }
+
+        canvas.clear(Color::WHITE);
+        for bitmap in bitmaps.iter() {
+            assert!(canvas.write_pixels_from_bitmap(bitmap, (0, 0)));
+        }
+    }
+
+    Report {
+        elapsed: start.elapsed(),
+        layer_bytes: bitmaps.iter().map(Bitmap::compute_byte_size).sum(),
+    }
+}
+
+fn picture_replay() -> Report {
+    let mut surface =
+        Surface::new_raster_n32_premul((WIDTH, HEIGHT)).expect("cannot create surface");
+    let bounds = Rect::from_wh(WIDTH as _, HEIGHT as _);
+    let mut recorder = PictureRecorder::new();
+    let mut pictures: Vec<Picture> = Vec::with_capacity(LAYERS);
+
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        pictures.clear();
+        for layer in 0..LAYERS {
+            draw_layer_content(recorder.begin_recording(bounds, None), layer);
+            pictures.push(
+                recorder
+                    .finish_recording_as_picture(None)
+                    .expect("cannot finish recording"),
+            );
+        }
+
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        for picture in pictures.iter() {
+            canvas.draw_picture(picture, None, None);
+        }
+    }
+
+    Report {
+        elapsed: start.elapsed(),
+        layer_bytes: pictures.iter().map(Picture::approximate_bytes_used).sum(),
+    }
+}
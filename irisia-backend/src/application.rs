@@ -1,9 +1,74 @@
-use std::time::Duration;
+use std::{fmt::Display, time::Duration};
 
 use anyhow::Result;
 use skia_safe::Canvas;
 
+use crate::log;
+
 pub trait AppWindow: 'static {
     fn on_redraw(&mut self, canvas: &mut Canvas, delta: Duration) -> Result<()>;
     fn on_window_event(&mut self, event: crate::StaticWindowEvent);
+
+    /// Called after a frame drawn by `on_redraw` has been presented.
+    fn on_frame_presented(&mut self, timing: FrameTiming) {
+        let _ = timing;
+    }
+
+    /// Called when the backend failed to render this window, returns how to recover.
+    fn on_backend_error(&mut self, error: BackendError) -> Recovery {
+        tracing::error!(target: log::BACKEND, "{error}");
+        Recovery::Ignore
+    }
+}
+
+#[derive(Debug)]
+pub enum BackendError {
+    /// The renderer of the window cannot be created.
+    RendererInit(anyhow::Error),
+
+    /// The renderer cannot be resized to the window size.
+    Resize(anyhow::Error),
+
+    /// The frame cannot be drawn or presented.
+    Render(anyhow::Error),
+}
+
+impl Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RendererInit(err) => write!(f, "renderer creation error: {err}"),
+            Self::Resize(err) => write!(f, "cannot resize window: {err}"),
+            Self::Render(err) => write!(f, "render error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recovery {
+    Ignore,
+
+    /// Drop the renderer and create a new one.
+    RetryRenderer,
+
+    CloseWindow,
+}
+
+/// Timings of a presented frame, measured by the backend.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameTiming {
+    /// Time elapsed since the previous frame.
+    pub delta: Duration,
+
+    /// Time spent in `AppWindow::on_redraw`.
+    pub draw: Duration,
+
+    /// Time spent copying the frame to the window and presenting it.
+    pub upload: Duration,
+
+    /// Expected duration of a frame, derived from the frame rate cap
+    /// or the refresh rate of the monitor. `None` if frames are drawn
+    /// on demand, where there is no frame to miss.
+    pub budget: Option<Duration>,
 }
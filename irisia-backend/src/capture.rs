@@ -0,0 +1,78 @@
+use anyhow::{anyhow, Result};
+use skia_safe::{
+    AlphaType, Canvas, ColorSpace, ColorType, EncodedImageFormat, IRect, Image, ImageInfo,
+};
+
+/// A captured frame in RGBA8888 with unpremultiplied alpha.
+#[derive(Clone)]
+pub struct CapturedFrame {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl CapturedFrame {
+    /// Read pixels of `region` from the canvas. The region will be clipped
+    /// to the canvas.
+    pub fn read_from(canvas: &mut Canvas, region: IRect) -> Result<Self> {
+        let size = canvas.base_layer_size();
+        let region = IRect::intersect(&region, &IRect::from_wh(size.width, size.height))
+            .ok_or_else(|| anyhow!("capture region is out of the canvas"))?;
+
+        let info = image_info(region.width() as _, region.height() as _);
+        let row_bytes = info.min_row_bytes();
+        let mut pixels = vec![0u8; row_bytes * region.height() as usize];
+
+        if !canvas.read_pixels(&info, &mut pixels, row_bytes, (region.left, region.top)) {
+            return Err(anyhow!("cannot read pixels from canvas"));
+        }
+
+        Ok(CapturedFrame {
+            width: region.width() as _,
+            height: region.height() as _,
+            pixels,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Pixels in RGBA8888, row by row without padding.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn into_pixels(self) -> Vec<u8> {
+        self.pixels
+    }
+
+    pub fn encode_png(&self) -> Result<Vec<u8>> {
+        let info = image_info(self.width, self.height);
+        let image = Image::from_raster_data(
+            &info,
+            skia_safe::Data::new_copy(&self.pixels),
+            info.min_row_bytes(),
+        )
+        .ok_or_else(|| anyhow!("cannot create image from captured pixels"))?;
+
+        let data = image
+            .encode_to_data(EncodedImageFormat::PNG)
+            .ok_or_else(|| anyhow!("cannot encode captured frame to png"))?;
+
+        Ok(data.as_bytes().to_vec())
+    }
+}
+
+fn image_info(width: u32, height: u32) -> ImageInfo {
+    ImageInfo::new(
+        (width as _, height as _),
+        ColorType::RGBA8888,
+        AlphaType::Unpremul,
+        Some(ColorSpace::new_srgb()),
+    )
+}
@@ -1,9 +1,11 @@
 pub mod application;
+pub mod capture;
 mod render_window;
 pub mod runtime;
 pub mod window_handle;
 
-pub use application::AppWindow;
+pub use application::{AppWindow, BackendError, FrameTiming, Recovery};
+pub use capture::CapturedFrame;
 pub use runtime::start_runtime;
 
 pub use skia_safe;
@@ -11,5 +13,11 @@ pub use winit;
 
 pub type WinitWindow = winit::window::Window;
 
+/// Logging target of the backend, re-exported by `irisia::log`.
+pub mod log {
+    /// Window creation, renderer and frame presentation.
+    pub const BACKEND: &str = "irisia::backend";
+}
+
 // only for export, shouldn't use in crate, which may cause confusion
 pub type StaticWindowEvent = winit::event::WindowEvent<'static>;
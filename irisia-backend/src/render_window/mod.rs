@@ -6,19 +6,29 @@ use std::{
     time::{Duration, Instant},
 };
 
-use anyhow::Result;
-use tokio::sync::Mutex;
+use anyhow::{anyhow, Result};
+use skia_safe::IRect;
+use tokio::sync::{Mutex, MutexGuard};
 use winit::event::Event;
 
-use crate::{AppWindow, WinitWindow};
+use crate::{
+    log,
+    runtime::pacing::{ControlFlowMode, FramePacing},
+    window_handle::CloseHandle,
+    AppWindow, BackendError, CapturedFrame, FrameTiming, Recovery, WinitWindow,
+};
 
 use self::renderer::Renderer;
 
 mod renderer;
 
+/// Interval to poll the renderer being created.
+const RENDERER_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 enum RendererGetter {
     Pending(Pin<Box<dyn Future<Output = Result<Renderer>>>>),
-    Error,
+    // the error will be taken once reported
+    Error(Option<anyhow::Error>),
     Done(Renderer),
 }
 
@@ -27,25 +37,96 @@ pub struct RenderWindow {
     window: Arc<WinitWindow>,
     renderer: RendererGetter,
     last_frame_instant: Option<Instant>,
+    pacing: FramePacing,
+    pacing_overridden: bool,
+    redraw_pending: bool,
 }
 
 impl RenderWindow {
-    pub fn new(app: Arc<Mutex<dyn AppWindow>>, window: Arc<WinitWindow>) -> Result<Self> {
-        let window_cloned = window.clone();
-        let mut renderer_creation =
-            RendererGetter::Pending(Box::pin(async move { Renderer::new(&window_cloned).await }));
-
-        // start poll
-        Self::renderer(&mut renderer_creation);
-
+    pub fn new(
+        app: Arc<Mutex<dyn AppWindow>>,
+        window: Arc<WinitWindow>,
+        pacing: FramePacing,
+    ) -> Result<Self> {
         Ok(RenderWindow {
             app: app as _,
-            renderer: renderer_creation,
+            renderer: Self::create_renderer(&window, pacing.vsync),
             window,
             last_frame_instant: None,
+            pacing,
+            pacing_overridden: false,
+            redraw_pending: false,
         })
     }
 
+    fn create_renderer(window: &Arc<WinitWindow>, vsync: bool) -> RendererGetter {
+        let window_cloned = window.clone();
+        let mut renderer_creation = RendererGetter::Pending(Box::pin(async move {
+            Renderer::new(&window_cloned, vsync).await
+        }));
+
+        // start poll
+        Self::renderer(&mut renderer_creation);
+        renderer_creation
+    }
+
+    /// Set pacing of this window, `None` to follow the default pacing of the app.
+    pub fn set_pacing(&mut self, pacing: Option<FramePacing>, default: FramePacing) {
+        self.pacing_overridden = pacing.is_some();
+        self.apply_pacing(pacing.unwrap_or(default));
+    }
+
+    pub fn set_default_pacing(&mut self, default: FramePacing) {
+        if !self.pacing_overridden {
+            self.apply_pacing(default);
+        }
+    }
+
+    fn apply_pacing(&mut self, pacing: FramePacing) {
+        let vsync_changed = pacing.vsync != self.pacing.vsync;
+        self.pacing = pacing;
+
+        if vsync_changed {
+            self.recreate_renderer();
+        }
+
+        self.window.request_redraw();
+    }
+
+    fn recreate_renderer(&mut self) {
+        tracing::debug!(target: log::BACKEND, window = ?self.window.id(), "recreating renderer");
+        // the old surface must be released before a new one is created on the same window
+        self.renderer = RendererGetter::Error(None);
+        self.renderer = Self::create_renderer(&self.window, self.pacing.vsync);
+    }
+
+    /// The instant this window wants to draw its next frame, `None` if it
+    /// has nothing to draw and can wait for events.
+    pub fn next_frame(&self) -> Option<Instant> {
+        // the renderer is polled with a noop waker, so check it again a
+        // bit later rather than spinning the event loop
+        if let RendererGetter::Pending(_) = self.renderer {
+            return Some(Instant::now() + RENDERER_POLL_INTERVAL);
+        }
+
+        if !self.redraw_pending && self.pacing.mode != ControlFlowMode::Poll {
+            return None;
+        }
+
+        Some(
+            self.pacing
+                .next_frame_after(self.last_frame_instant)
+                .unwrap_or_else(Instant::now),
+        )
+    }
+
+    fn frame_due(&self, now: Instant) -> bool {
+        match self.pacing.next_frame_after(self.last_frame_instant) {
+            Some(next) => next <= now,
+            None => true,
+        }
+    }
+
     fn renderer(renderer_getter: &mut RendererGetter) -> Option<&mut Renderer> {
         if let RendererGetter::Pending(future) = renderer_getter {
             let mut cx = Context::from_waker(futures::task::noop_waker_ref());
@@ -54,8 +135,7 @@ impl RenderWindow {
                     *renderer_getter = RendererGetter::Done(r);
                 }
                 Poll::Ready(Err(e)) => {
-                    eprintln!("renderer creation error: {e}");
-                    *renderer_getter = RendererGetter::Error;
+                    *renderer_getter = RendererGetter::Error(Some(e));
                     return None;
                 }
                 Poll::Pending => return None,
@@ -64,12 +144,32 @@ impl RenderWindow {
 
         match renderer_getter {
             RendererGetter::Done(r) => Some(r),
-            RendererGetter::Error => None,
+            RendererGetter::Error(_) => None,
             RendererGetter::Pending(_) => unreachable!(),
         }
     }
 
     pub fn redraw(&mut self) {
+        let _span = tracing::trace_span!(target: log::BACKEND, "frame", window = ?self.window.id())
+            .entered();
+
+        let renderer = match Self::renderer(&mut self.renderer) {
+            Some(r) => r,
+            None => {
+                match &mut self.renderer {
+                    // renderer is not ready yet, keep the frame until it is
+                    RendererGetter::Pending(_) => self.redraw_pending = true,
+                    RendererGetter::Error(err) => {
+                        if let Some(err) = err.take() {
+                            self.handle_error(BackendError::RendererInit(err));
+                        }
+                    }
+                    RendererGetter::Done(_) => unreachable!(),
+                }
+                return;
+            }
+        };
+
         let delta = {
             let now = Instant::now();
             match self.last_frame_instant.replace(now) {
@@ -78,42 +178,114 @@ impl RenderWindow {
             }
         };
 
-        let mut app = match self.app.try_lock() {
-            Ok(app) => app,
-            Err(_) => self.app.blocking_lock(),
-        };
+        let mut app = lock_app(&self.app);
+        let mut errors = Vec::new();
 
-        if let Some(renderer) = Self::renderer(&mut self.renderer) {
-            if let Err(err) = renderer.resize(self.window.inner_size()) {
-                eprintln!("cannot resize window: {err}");
-            }
+        if let Err(err) = renderer.resize(self.window.inner_size()) {
+            errors.push(BackendError::Resize(err));
+        }
 
-            if let Err(err) = renderer.render(|canvas| app.on_redraw(canvas, delta)) {
-                eprintln!("render error: {err}");
+        let render_start = Instant::now();
+        let mut draw = Duration::ZERO;
+        let result = renderer.render(|canvas| {
+            let result = app.on_redraw(canvas, delta);
+            draw = render_start.elapsed();
+            result
+        });
+
+        match result {
+            Ok(()) => app.on_frame_presented(FrameTiming {
+                delta,
+                draw,
+                upload: render_start.elapsed().saturating_sub(draw),
+                budget: self.frame_budget(),
+            }),
+            Err(err) => errors.push(BackendError::Render(err)),
+        }
+
+        drop(app);
+        for err in errors {
+            self.handle_error(err);
+        }
+    }
+
+    fn handle_error(&mut self, error: BackendError) {
+        let recovery = lock_app(&self.app).on_backend_error(error);
+        tracing::debug!(
+            target: log::BACKEND,
+            window = ?self.window.id(),
+            ?recovery,
+            "recovering from backend error"
+        );
+
+        match recovery {
+            Recovery::Ignore => {}
+            Recovery::RetryRenderer => {
+                self.recreate_renderer();
+                self.window.request_redraw();
             }
+            Recovery::CloseWindow => CloseHandle(self.window.id()).close(),
+        }
+    }
+
+    /// Capture the last presented frame. Fails if the window was resized
+    /// after it, a redraw is requested then to capture again.
+    pub fn capture(&mut self, region: Option<IRect>) -> Result<CapturedFrame> {
+        let Some(renderer) = Self::renderer(&mut self.renderer) else {
+            return Err(anyhow!("renderer of the window is not available"));
+        };
+
+        if renderer.size() != self.window.inner_size() {
+            self.window.request_redraw();
+            return Err(anyhow!("window resized and not redrawn yet"));
         }
+        renderer.capture(region)
+    }
+
+    fn frame_budget(&self) -> Option<Duration> {
+        if self.pacing.mode != ControlFlowMode::Poll {
+            return None;
+        }
+
+        let min_interval = self.pacing.min_interval();
+        if !min_interval.is_zero() {
+            return Some(min_interval);
+        }
+
+        let millihertz = self
+            .window
+            .current_monitor()
+            .and_then(|monitor| monitor.refresh_rate_millihertz())
+            .filter(|&millihertz| millihertz != 0)
+            .unwrap_or(60_000);
+
+        Some(Duration::from_secs(1000) / millihertz)
     }
 
     pub fn handle_event(&mut self, event: Event<()>) {
         match event {
             Event::MainEventsCleared => {
-                // restrict maximum drawing rate to 120fps
-                const MIN_INTERVAL: Duration = Duration::from_millis(1000 / 120);
-
-                if let Some(last) = self.last_frame_instant {
-                    if last.elapsed() < MIN_INTERVAL {
-                        return;
-                    }
+                if self
+                    .next_frame()
+                    .map_or(false, |next| next <= Instant::now())
+                {
+                    self.window.request_redraw();
                 }
             }
 
             Event::RedrawRequested(_) => {
-                self.redraw();
+                if self.frame_due(Instant::now()) {
+                    self.redraw_pending = false;
+                    self.redraw();
+                } else {
+                    // too early for the frame rate cap, draw it later
+                    self.redraw_pending = true;
+                }
             }
 
             Event::WindowEvent { event, .. } => {
                 if let Some(static_event) = event.to_static() {
-                    self.app.blocking_lock().on_window_event(static_event);
+                    lock_app(&self.app).on_window_event(static_event);
                 }
             }
 
@@ -121,3 +293,10 @@ impl RenderWindow {
         }
     }
 }
+
+fn lock_app(app: &Mutex<dyn AppWindow>) -> MutexGuard<dyn AppWindow> {
+    match app.try_lock() {
+        Ok(app) => app,
+        Err(_) => app.blocking_lock(),
+    }
+}
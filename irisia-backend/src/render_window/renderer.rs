@@ -1,18 +1,14 @@
-#[cfg(feature = "fps_recorder")]
-use std::{
-    sync::{atomic::AtomicU16, Arc},
-    time::Duration,
-};
+use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
 use pixels::{
     wgpu::{BlendState, DeviceDescriptor, Features, Limits},
     Pixels, PixelsBuilder, SurfaceTexture,
 };
-use skia_safe::{Canvas, Color, ColorSpace, ColorType, ImageInfo, Surface};
+use skia_safe::{Canvas, Color, ColorSpace, ColorType, IRect, ImageInfo, Surface};
 use winit::dpi::PhysicalSize;
 
-use crate::WinitWindow;
+use crate::{CapturedFrame, WinitWindow};
 
 pub struct Renderer {
     window_pixels: Pixels,
@@ -20,27 +16,13 @@ pub struct Renderer {
     output_image_info: ImageInfo,
     size: PhysicalSize<u32>,
     size2x: (u32, u32),
-    #[cfg(feature = "fps_recorder")]
-    counter: Arc<AtomicU16>,
-}
 
-#[cfg(feature = "fps_recorder")]
-fn fps_recorder() -> Arc<AtomicU16> {
-    let counter = Arc::new(AtomicU16::new(0));
-    let counter_cloned = counter.clone();
-
-    std::thread::spawn(move || loop {
-        std::thread::sleep(Duration::from_secs(2));
-        println!(
-            "{}fps",
-            counter.swap(0, std::sync::atomic::Ordering::Relaxed) / 2
-        );
-    });
-    counter_cloned
+    /// Whether the surface holds a frame rendered at the current size.
+    presented: bool,
 }
 
 impl Renderer {
-    pub async fn new(window: &Arc<WinitWindow>) -> Result<Self> {
+    pub async fn new(window: &Arc<WinitWindow>, vsync: bool) -> Result<Self> {
         let PhysicalSize { width, height } = window.inner_size();
 
         let (w2x, h2x) = to_size2x(window.inner_size());
@@ -50,7 +32,7 @@ impl Renderer {
             tokio::task::spawn_blocking(move || {
                 PixelsBuilder::new(width, height, SurfaceTexture::new(width, height, &*window))
                     .blend_state(BlendState::REPLACE)
-                    .enable_vsync(false)
+                    .enable_vsync(vsync)
                     .device_descriptor(DeviceDescriptor {
                         label: Default::default(),
                         features: Features::empty(),
@@ -78,9 +60,7 @@ impl Renderer {
             surface,
             size: window.inner_size(),
             size2x: (w2x, h2x),
-
-            #[cfg(feature = "fps_recorder")]
-            counter: fps_recorder(),
+            presented: false,
         })
     }
 
@@ -102,13 +82,31 @@ impl Renderer {
         }
 
         self.window_pixels.render()?;
-
-        #[cfg(feature = "fps_recorder")]
-        self.counter
-            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.presented = true;
         Ok(())
     }
 
+    pub fn size(&self) -> PhysicalSize<u32> {
+        self.size
+    }
+
+    /// Capture the last rendered frame. `region` is in physical pixels,
+    /// `None` for the whole window.
+    pub fn capture(&mut self, region: Option<IRect>) -> Result<CapturedFrame> {
+        if !self.presented {
+            return Err(anyhow!("no frame rendered at the current size yet"));
+        }
+
+        let window_rect = IRect::from_wh(self.size.width as _, self.size.height as _);
+        let region = match region {
+            Some(region) => IRect::intersect(&region, &window_rect)
+                .ok_or_else(|| anyhow!("capture region is out of the window"))?,
+            None => window_rect,
+        };
+
+        CapturedFrame::read_from(self.surface.canvas(), region)
+    }
+
     pub fn resize(&mut self, size: PhysicalSize<u32>) -> Result<()> {
         if self.size == size || size.width == 0 || size.height == 0 {
             return Ok(());
@@ -117,6 +115,7 @@ impl Renderer {
         let PhysicalSize { width, height } = size;
 
         let size2x = to_size2x(size);
+        self.presented = false;
 
         self.window_pixels.resize_buffer(width, height)?;
         self.window_pixels.resize_surface(width, height)?;
@@ -1,22 +1,59 @@
-use std::{collections::HashMap, future::Future};
+use std::{collections::HashMap, future::Future, time::Instant};
+
+use anyhow::{anyhow, Result};
+use skia_safe::IRect;
+use tokio::sync::oneshot;
 
 use winit::{
     event::{Event, StartCause},
-    event_loop::{EventLoop, EventLoopBuilder},
+    event_loop::{ControlFlow, EventLoop, EventLoopBuilder},
     window::{WindowBuilder, WindowId},
 };
 
-use crate::render_window::RenderWindow;
+use crate::{log, render_window::RenderWindow, CapturedFrame};
 
 use self::{global::WindowRegiterMutex, rt_event::WindowReg};
 
+pub use pacing::{ControlFlowMode, FramePacing};
+
 pub(crate) mod global;
+pub mod pacing;
 pub(crate) mod rt_event;
 
 pub async fn exit_app(code: i32) {
     WindowRegiterMutex::lock().await.send(WindowReg::Exit(code));
 }
 
+/// Capture the last frame presented by the window. `region` is in
+/// physical pixels, `None` for the whole window.
+pub async fn capture_frame(window_id: WindowId, region: Option<IRect>) -> Result<CapturedFrame> {
+    let (giver, receiver) = oneshot::channel();
+
+    WindowRegiterMutex::lock()
+        .await
+        .send(WindowReg::CaptureFrame {
+            window_id,
+            region,
+            giver,
+        });
+
+    receiver.await?
+}
+
+/// Set the frame pacing used by windows which have not specified their own.
+pub async fn set_default_pacing(pacing: FramePacing) {
+    WindowRegiterMutex::lock()
+        .await
+        .send(WindowReg::SetDefaultPacing(pacing));
+}
+
+/// Set the frame pacing of a window, `None` to follow the default pacing.
+pub async fn set_window_pacing(window_id: WindowId, pacing: Option<FramePacing>) {
+    WindowRegiterMutex::lock()
+        .await
+        .send(WindowReg::SetWindowPacing { window_id, pacing });
+}
+
 pub fn start_runtime<F>(f: F) -> !
 where
     F: Future<Output = ()> + Send + 'static,
@@ -34,67 +71,111 @@ where
 
     let event_loop: EventLoop<WindowReg> = EventLoopBuilder::with_user_event().build();
     let mut window_map: HashMap<WindowId, RenderWindow> = HashMap::new();
+    let mut default_pacing = FramePacing::default();
     WindowRegiterMutex::init(event_loop.create_proxy());
 
-    event_loop.run(move |event, event_loop, flow| {
-        flow.set_poll();
-        match event {
-            Event::NewEvents(StartCause::Init) => {
-                let future = future_option
-                    .take()
-                    .expect("unexpected take function twice");
-                tokio_runtime.spawn(future);
+    event_loop.run(move |event, event_loop, flow| match event {
+        Event::NewEvents(StartCause::Init) => {
+            flow.set_wait();
+            let future = future_option
+                .take()
+                .expect("unexpected take function twice");
+            tokio_runtime.spawn(future);
+        }
+
+        Event::RedrawEventsCleared => {
+            if let ControlFlow::ExitWithCode(_) = flow {
+                return;
             }
 
-            Event::WindowEvent { window_id, .. } | Event::RedrawRequested(window_id) => {
-                if let Some(w) = window_map.get_mut(&window_id) {
-                    match event.map_nonuser_event() {
-                        Ok(event) => w.handle_event(event),
-                        _ => unreachable!(),
-                    }
-                }
+            let next_frame = window_map
+                .values()
+                .filter_map(RenderWindow::next_frame)
+                .min();
+
+            match next_frame {
+                Some(instant) if instant <= Instant::now() => flow.set_poll(),
+                Some(instant) => flow.set_wait_until(instant),
+                None => flow.set_wait(),
             }
+        }
 
-            Event::UserEvent(window_reg) => match window_reg {
-                WindowReg::RawWindowRequest {
-                    builder,
-                    window_giver,
-                } => {
-                    let window = builder(WindowBuilder::new()).build(event_loop);
-                    let _ = window_giver.send(window);
+        Event::WindowEvent { window_id, .. } | Event::RedrawRequested(window_id) => {
+            if let Some(w) = window_map.get_mut(&window_id) {
+                match event.map_nonuser_event() {
+                    Ok(event) => w.handle_event(event),
+                    _ => unreachable!(),
                 }
+            }
+        }
 
-                WindowReg::WindowRegister { app, raw_window } => {
-                    let window_id = raw_window.id();
+        Event::UserEvent(window_reg) => match window_reg {
+            WindowReg::RawWindowRequest {
+                builder,
+                window_giver,
+            } => {
+                let window = builder(WindowBuilder::new()).build(event_loop);
+                let _ = window_giver.send(window);
+            }
 
-                    let render_window =
-                        RenderWindow::new(app(), raw_window).expect("cannot load renderer");
+            WindowReg::WindowRegister { app, raw_window } => {
+                let window_id = raw_window.id();
+                tracing::debug!(target: log::BACKEND, window = ?window_id, "window registered");
 
-                    window_map
-                        .entry(window_id)
-                        .or_insert(render_window)
-                        .redraw();
-                }
+                let render_window = RenderWindow::new(app(), raw_window, default_pacing)
+                    .expect("cannot load renderer");
+
+                window_map
+                    .entry(window_id)
+                    .or_insert(render_window)
+                    .redraw();
+            }
+
+            WindowReg::WindowDestroyed(wid) => {
+                tracing::debug!(target: log::BACKEND, window = ?wid, "window destroyed");
+                window_map.remove(&wid);
+            }
+
+            WindowReg::CaptureFrame {
+                window_id,
+                region,
+                giver,
+            } => {
+                let result = match window_map.get_mut(&window_id) {
+                    Some(w) => w.capture(region),
+                    None => Err(anyhow!("window not found")),
+                };
+                let _ = giver.send(result);
+            }
 
-                WindowReg::WindowDestroyed(wid) => {
-                    window_map.remove(&wid);
+            WindowReg::SetDefaultPacing(pacing) => {
+                default_pacing = pacing;
+                for window in window_map.values_mut() {
+                    window.set_default_pacing(pacing);
                 }
+            }
 
-                WindowReg::Exit(code) => {
-                    flow.set_exit_with_code(code);
+            WindowReg::SetWindowPacing { window_id, pacing } => {
+                if let Some(w) = window_map.get_mut(&window_id) {
+                    w.set_pacing(pacing, default_pacing);
                 }
-            },
-
-            _ => match event.map_nonuser_event() {
-                Ok(e) => {
-                    if let Some(e) = e.to_static() {
-                        for window in window_map.values_mut() {
-                            window.handle_event(e.clone());
-                        }
+            }
+
+            WindowReg::Exit(code) => {
+                tracing::debug!(target: log::BACKEND, code, "exiting event loop");
+                flow.set_exit_with_code(code);
+            }
+        },
+
+        _ => match event.map_nonuser_event() {
+            Ok(e) => {
+                if let Some(e) = e.to_static() {
+                    for window in window_map.values_mut() {
+                        window.handle_event(e.clone());
                     }
                 }
-                _ => unreachable!(),
-            },
-        }
+            }
+            _ => unreachable!(),
+        },
     });
 }
@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant};
+
+/// How the event loop waits between frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlFlowMode {
+    /// Keep redrawing continuously, as fast as `max_fps` and vsync allow.
+    Poll,
+
+    /// Sleep until a window event arrives or a redraw is requested.
+    Wait,
+}
+
+/// Frame pacing of a window.
+///
+/// The default is idle-friendly: vsync on, no extra frame cap, and the
+/// event loop only wakes for events and redraw requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FramePacing {
+    /// Maximum frames per second. `None` means uncapped, which is still
+    /// bounded by the display refresh rate if `vsync` is enabled.
+    pub max_fps: Option<u32>,
+    pub vsync: bool,
+    pub mode: ControlFlowMode,
+}
+
+impl FramePacing {
+    pub const fn on_demand() -> Self {
+        FramePacing {
+            max_fps: None,
+            vsync: true,
+            mode: ControlFlowMode::Wait,
+        }
+    }
+
+    pub const fn continuous(max_fps: Option<u32>) -> Self {
+        FramePacing {
+            max_fps,
+            vsync: true,
+            mode: ControlFlowMode::Poll,
+        }
+    }
+
+    pub fn with_max_fps(mut self, max_fps: Option<u32>) -> Self {
+        self.max_fps = max_fps;
+        self
+    }
+
+    pub fn with_vsync(mut self, vsync: bool) -> Self {
+        self.vsync = vsync;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: ControlFlowMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub(crate) fn min_interval(&self) -> Duration {
+        match self.max_fps {
+            Some(fps) if fps > 0 => Duration::from_secs(1) / fps,
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// The earliest instant the next frame is allowed to be drawn.
+    pub(crate) fn next_frame_after(&self, last_frame: Option<Instant>) -> Option<Instant> {
+        last_frame.map(|last| last + self.min_interval())
+    }
+}
+
+impl Default for FramePacing {
+    fn default() -> Self {
+        Self::on_demand()
+    }
+}
@@ -1,12 +1,17 @@
 use std::sync::Arc;
 
+use anyhow::Result;
+use skia_safe::IRect;
+
 use tokio::sync::{oneshot, Mutex};
 use winit::{
     error::OsError,
     window::{WindowBuilder, WindowId},
 };
 
-use crate::{AppWindow, WinitWindow};
+use crate::{AppWindow, CapturedFrame, WinitWindow};
+
+use super::pacing::FramePacing;
 
 pub(crate) enum WindowReg {
     RawWindowRequest {
@@ -21,5 +26,18 @@ pub(crate) enum WindowReg {
 
     WindowDestroyed(WindowId),
 
+    CaptureFrame {
+        window_id: WindowId,
+        region: Option<IRect>,
+        giver: oneshot::Sender<Result<CapturedFrame>>,
+    },
+
+    SetDefaultPacing(FramePacing),
+
+    SetWindowPacing {
+        window_id: WindowId,
+        pacing: Option<FramePacing>,
+    },
+
     Exit(i32),
 }
@@ -3,7 +3,7 @@ use winit::window::WindowId;
 use crate::runtime::{global::WindowRegiterMutex, rt_event::WindowReg};
 
 #[derive(Clone, Copy)]
-pub struct CloseHandle(pub(super) WindowId);
+pub struct CloseHandle(pub(crate) WindowId);
 
 impl CloseHandle {
     pub fn close(&self) {
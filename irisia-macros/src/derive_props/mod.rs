@@ -122,7 +122,5 @@ pub fn props(_attr: TokenStream, item: ItemStruct) -> Result<TokenStream> {
     output.extend(impl_default(&helper));
     output.extend(set_props(&helper));
 
-    println!("{}", output.to_string());
-
     Ok(output)
 }
@@ -0,0 +1,511 @@
+use std::sync::{Arc, Mutex};
+
+use irisia::{
+    element::ElementHandle,
+    primitive::Pixel,
+    skia_safe::{
+        Canvas, Color, FilterMode, Matrix, MipmapMode, Paint, Point as SkiaPoint, RRect, Rect,
+        SamplingOptions, Shader, TileMode,
+    },
+    Style,
+};
+use smallvec::SmallVec;
+
+use crate::image::{DecodedImage, ImageCache, ImageSource};
+
+/// Background layers of a box, clipped to the border radius.
+/// Like CSS, the first layer is drawn on the top.
+#[derive(Style, Clone)]
+#[style(from = "", impl_default)]
+pub struct StyleBackground(#[style(default)] pub SmallVec<[BackgroundLayer; 2]>);
+
+impl StyleBackground {
+    pub fn color(&mut self, color: Color) {
+        self.0.push(BackgroundLayer::Color(color));
+    }
+
+    pub fn linear(&mut self, gradient: LinearGradient) {
+        self.0.push(BackgroundLayer::Linear(gradient));
+    }
+
+    pub fn radial(&mut self, gradient: RadialGradient) {
+        self.0.push(BackgroundLayer::Radial(gradient));
+    }
+
+    pub fn conic(&mut self, gradient: ConicGradient) {
+        self.0.push(BackgroundLayer::Conic(gradient));
+    }
+
+    pub fn image(&mut self, image: BackgroundImage) {
+        self.0.push(BackgroundLayer::Image(image));
+    }
+
+    /// Decode images in background and keep them in the layers, then
+    /// redraw the element of `handle` once each of them is ready. Layers
+    /// whose source is in `previous` take its image instead.
+    pub(super) fn preload_images<El>(
+        &mut self,
+        previous: Option<&StyleBackground>,
+        handle: &Arc<ElementHandle<El>>,
+    ) where
+        El: Send + Sync + 'static,
+    {
+        for layer in &mut self.0 {
+            let BackgroundLayer::Image(image) = layer else {
+                continue;
+            };
+
+            let kept = previous.and_then(|previous| {
+                previous.0.iter().find_map(|layer| match layer {
+                    BackgroundLayer::Image(prev) if prev.source == image.source => {
+                        Some(prev.decoded.clone())
+                    }
+                    _ => None,
+                })
+            });
+
+            if let Some(decoded) = kept {
+                image.decoded = decoded;
+                continue;
+            }
+
+            if let Some(decoded) = ImageCache::get(&image.source) {
+                image.set_decoded(decoded);
+                continue;
+            }
+
+            let slot = image.decoded.clone();
+            let source = image.source.clone();
+            let handle = Arc::downgrade(handle);
+            ImageCache::preload(image.source.clone(), move |decoded| {
+                *slot.lock().unwrap() = Some((source, decoded));
+                if let Some(handle) = handle.upgrade() {
+                    handle.set_dirty();
+                }
+            });
+        }
+    }
+}
+
+impl<T> From<(T,)> for StyleBackground
+where
+    T: Into<BackgroundLayer>,
+{
+    fn from((layer,): (T,)) -> Self {
+        StyleBackground([layer.into()].into_iter().collect())
+    }
+}
+
+#[derive(Clone)]
+pub enum BackgroundLayer {
+    Color(Color),
+    Linear(LinearGradient),
+    Radial(RadialGradient),
+    Conic(ConicGradient),
+    Image(BackgroundImage),
+}
+
+impl From<Color> for BackgroundLayer {
+    fn from(value: Color) -> Self {
+        BackgroundLayer::Color(value)
+    }
+}
+
+impl From<LinearGradient> for BackgroundLayer {
+    fn from(value: LinearGradient) -> Self {
+        BackgroundLayer::Linear(value)
+    }
+}
+
+impl From<RadialGradient> for BackgroundLayer {
+    fn from(value: RadialGradient) -> Self {
+        BackgroundLayer::Radial(value)
+    }
+}
+
+impl From<ConicGradient> for BackgroundLayer {
+    fn from(value: ConicGradient) -> Self {
+        BackgroundLayer::Conic(value)
+    }
+}
+
+impl From<BackgroundImage> for BackgroundLayer {
+    fn from(value: BackgroundImage) -> Self {
+        BackgroundLayer::Image(value)
+    }
+}
+
+/// Color stops of a gradient. Stops without position are spread evenly
+/// between their neighbours, the first one defaults to `0.0` and the
+/// last one to `1.0`.
+#[derive(Clone, Default)]
+pub struct ColorStops(SmallVec<[(Color, Option<f32>); 4]>);
+
+impl ColorStops {
+    pub fn push(&mut self, color: Color, position: Option<f32>) {
+        self.0.push((color, position));
+    }
+
+    fn resolve(&self) -> (SmallVec<[Color; 4]>, SmallVec<[f32; 4]>) {
+        let colors = self.0.iter().map(|(color, _)| *color).collect();
+        let mut positions: SmallVec<[Option<f32>; 4]> =
+            self.0.iter().map(|(_, pos)| *pos).collect();
+
+        if let Some(first) = positions.first_mut() {
+            first.get_or_insert(0.0);
+        }
+        if let Some(last) = positions.last_mut() {
+            last.get_or_insert(1.0);
+        }
+
+        let mut resolved: SmallVec<[f32; 4]> = SmallVec::with_capacity(positions.len());
+        let mut index = 0;
+        while index < positions.len() {
+            if let Some(pos) = positions[index] {
+                // positions never go backwards
+                let prev = resolved.last().copied().unwrap_or(pos);
+                resolved.push(pos.max(prev));
+                index += 1;
+                continue;
+            }
+
+            let start = resolved[index - 1];
+            let end_index = (index..positions.len())
+                .find(|&i| positions[i].is_some())
+                .unwrap();
+            let end = positions[end_index].unwrap().max(start);
+            let step = (end - start) / (end_index - index + 1) as f32;
+
+            for n in 1..=(end_index - index) {
+                resolved.push(start + step * n as f32);
+            }
+            index = end_index;
+        }
+
+        (colors, resolved)
+    }
+}
+
+impl From<&[Color]> for ColorStops {
+    fn from(value: &[Color]) -> Self {
+        ColorStops(value.iter().map(|color| (*color, None)).collect())
+    }
+}
+
+impl<const N: usize> From<[Color; N]> for ColorStops {
+    fn from(value: [Color; N]) -> Self {
+        ColorStops::from(value.as_slice())
+    }
+}
+
+impl From<Vec<Color>> for ColorStops {
+    fn from(value: Vec<Color>) -> Self {
+        ColorStops::from(value.as_slice())
+    }
+}
+
+#[derive(Clone)]
+pub struct LinearGradient {
+    /// Direction in degrees, `0.0` goes upwards and `90.0` goes rightwards.
+    pub angle: f32,
+    pub stops: ColorStops,
+}
+
+impl LinearGradient {
+    pub fn new(angle: f32, colors: impl Into<ColorStops>) -> Self {
+        LinearGradient {
+            angle,
+            stops: colors.into(),
+        }
+    }
+
+    /// Append a color stop at `position`, in `0.0..=1.0`.
+    pub fn stop(mut self, color: Color, position: f32) -> Self {
+        self.stops.push(color, Some(position));
+        self
+    }
+
+    fn shader(&self, rect: &Rect) -> Option<Shader> {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        let half = (rect.width() * sin.abs() + rect.height() * cos.abs()) / 2.0;
+        let center = rect.center();
+        let delta = SkiaPoint::new(sin * half, -cos * half);
+
+        let (colors, positions) = self.stops.resolve();
+        Shader::linear_gradient(
+            (center - delta, center + delta),
+            colors.as_slice(),
+            positions.as_slice(),
+            TileMode::Clamp,
+            None,
+            None,
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct RadialGradient {
+    /// Center relative to the box, `(0.5, 0.5)` for the center of the box.
+    pub center: (f32, f32),
+
+    /// Radius of the gradient. Reaches the farthest corner if `None`.
+    pub radius: Option<Pixel>,
+    pub stops: ColorStops,
+}
+
+impl RadialGradient {
+    pub fn new(colors: impl Into<ColorStops>) -> Self {
+        RadialGradient {
+            center: (0.5, 0.5),
+            radius: None,
+            stops: colors.into(),
+        }
+    }
+
+    pub fn center(mut self, x: f32, y: f32) -> Self {
+        self.center = (x, y);
+        self
+    }
+
+    pub fn radius(mut self, radius: Pixel) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    pub fn stop(mut self, color: Color, position: f32) -> Self {
+        self.stops.push(color, Some(position));
+        self
+    }
+
+    fn shader(&self, rect: &Rect) -> Option<Shader> {
+        let center = relative_point(rect, self.center);
+        let radius = match self.radius {
+            Some(radius) => radius.to_physical(),
+            None => {
+                let dx = (center.x - rect.left).max(rect.right - center.x);
+                let dy = (center.y - rect.top).max(rect.bottom - center.y);
+                dx.hypot(dy)
+            }
+        };
+
+        let (colors, positions) = self.stops.resolve();
+        Shader::radial_gradient(
+            center,
+            radius,
+            colors.as_slice(),
+            positions.as_slice(),
+            TileMode::Clamp,
+            None,
+            None,
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct ConicGradient {
+    /// Center relative to the box, `(0.5, 0.5)` for the center of the box.
+    pub center: (f32, f32),
+
+    /// Where the gradient starts in degrees, clockwise from the top.
+    pub angle: f32,
+    pub stops: ColorStops,
+}
+
+impl ConicGradient {
+    pub fn new(colors: impl Into<ColorStops>) -> Self {
+        ConicGradient {
+            center: (0.5, 0.5),
+            angle: 0.0,
+            stops: colors.into(),
+        }
+    }
+
+    pub fn center(mut self, x: f32, y: f32) -> Self {
+        self.center = (x, y);
+        self
+    }
+
+    pub fn angle(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    pub fn stop(mut self, color: Color, position: f32) -> Self {
+        self.stops.push(color, Some(position));
+        self
+    }
+
+    fn shader(&self, rect: &Rect) -> Option<Shader> {
+        let center = relative_point(rect, self.center);
+
+        // sweep gradients of skia start rightwards
+        let matrix = Matrix::rotate_deg_pivot(self.angle - 90.0, center);
+
+        let (colors, positions) = self.stops.resolve();
+        Shader::sweep_gradient(
+            center,
+            colors.as_slice(),
+            positions.as_slice(),
+            TileMode::Clamp,
+            None,
+            None,
+            &matrix,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackgroundRepeat {
+    Repeat,
+    RepeatX,
+    RepeatY,
+    NoRepeat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BackgroundSize {
+    /// Natural size of the image.
+    Auto,
+
+    /// Scale to cover the whole box, keeping the aspect ratio.
+    Cover,
+
+    /// Scale to fit inside the box, keeping the aspect ratio.
+    Contain,
+
+    Exact(Pixel, Pixel),
+}
+
+/// An image background. Images not decoded yet are skipped, elements
+/// preload them with [`BoxStyles::preload_images`] to be redrawn once
+/// they are ready, use [`ImageCache::load`] ahead to show them on the
+/// first frame. A preloaded image is kept by the layer, so it is still
+/// drawn after being dropped from the cache.
+///
+/// [`BoxStyles::preload_images`]: super::BoxStyles::preload_images
+#[derive(Clone)]
+pub struct BackgroundImage {
+    pub source: ImageSource,
+    pub repeat: BackgroundRepeat,
+    pub size: BackgroundSize,
+
+    /// Position relative to the box, `(0.0, 0.0)` for the top left corner
+    /// and `(1.0, 1.0)` for the bottom right corner.
+    pub position: (f32, f32),
+
+    // shared by clones, the source is kept since it is a public field
+    decoded: Arc<Mutex<Option<(ImageSource, DecodedImage)>>>,
+}
+
+impl BackgroundImage {
+    pub fn new(source: impl Into<ImageSource>) -> Self {
+        BackgroundImage {
+            source: source.into(),
+            repeat: BackgroundRepeat::Repeat,
+            size: BackgroundSize::Auto,
+            position: (0.0, 0.0),
+            decoded: Default::default(),
+        }
+    }
+
+    pub fn repeat(mut self, repeat: BackgroundRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn size(mut self, size: BackgroundSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, x: f32, y: f32) -> Self {
+        self.position = (x, y);
+        self
+    }
+
+    fn set_decoded(&self, image: DecodedImage) {
+        *self.decoded.lock().unwrap() = Some((self.source.clone(), image));
+    }
+
+    fn image(&self) -> Option<DecodedImage> {
+        match &*self.decoded.lock().unwrap() {
+            Some((source, image)) if *source == self.source => Some(image.clone()),
+            _ => ImageCache::get(&self.source),
+        }
+    }
+
+    fn shader(&self, rect: &Rect) -> Option<Shader> {
+        let image = self.image()?;
+
+        let (width, height) = (image.width() as f32, image.height() as f32);
+        if width == 0.0 || height == 0.0 {
+            return None;
+        }
+
+        let (tile_width, tile_height) = match self.size {
+            BackgroundSize::Auto => (width, height),
+            BackgroundSize::Cover | BackgroundSize::Contain => {
+                let (scale_x, scale_y) = (rect.width() / width, rect.height() / height);
+                let scale = if self.size == BackgroundSize::Cover {
+                    scale_x.max(scale_y)
+                } else {
+                    scale_x.min(scale_y)
+                };
+                (width * scale, height * scale)
+            }
+            BackgroundSize::Exact(w, h) => (w.to_physical(), h.to_physical()),
+        };
+
+        if tile_width <= 0.0 || tile_height <= 0.0 {
+            return None;
+        }
+
+        let left = rect.left + (rect.width() - tile_width) * self.position.0;
+        let top = rect.top + (rect.height() - tile_height) * self.position.1;
+        let mut matrix = Matrix::translate((left, top));
+        matrix.pre_scale((tile_width / width, tile_height / height), None);
+
+        let tile_modes = match self.repeat {
+            BackgroundRepeat::Repeat => (TileMode::Repeat, TileMode::Repeat),
+            BackgroundRepeat::RepeatX => (TileMode::Repeat, TileMode::Decal),
+            BackgroundRepeat::RepeatY => (TileMode::Decal, TileMode::Repeat),
+            BackgroundRepeat::NoRepeat => (TileMode::Decal, TileMode::Decal),
+        };
+
+        image.image().to_shader(
+            tile_modes,
+            SamplingOptions::new(FilterMode::Linear, MipmapMode::None),
+            &matrix,
+        )
+    }
+}
+
+fn relative_point(rect: &Rect, (x, y): (f32, f32)) -> SkiaPoint {
+    SkiaPoint::new(rect.left + rect.width() * x, rect.top + rect.height() * y)
+}
+
+pub(super) fn draw_background(canvas: &mut Canvas, rrect: &RRect, background: &StyleBackground) {
+    let rect = rrect.rect();
+
+    for layer in background.0.iter().rev() {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+
+        let shader = match layer {
+            BackgroundLayer::Color(color) => {
+                paint.set_color(*color);
+                canvas.draw_rrect(rrect, &paint);
+                continue;
+            }
+            BackgroundLayer::Linear(gradient) => gradient.shader(rect),
+            BackgroundLayer::Radial(gradient) => gradient.shader(rect),
+            BackgroundLayer::Conic(gradient) => gradient.shader(rect),
+            BackgroundLayer::Image(image) => image.shader(rect),
+        };
+
+        if let Some(shader) = shader {
+            paint.set_shader(shader);
+            canvas.draw_rrect(rrect, &paint);
+        }
+    }
+}
@@ -1,5 +1,8 @@
-use irisia::{primitive::Pixel, Style};
-use irisia_core::skia_safe::{Point as SkiaPoint, RRect, Rect};
+use irisia::skia_safe::{Point as SkiaPoint, RRect, Rect};
+use irisia::{
+    primitive::{Direction, Pixel},
+    Style,
+};
 
 #[derive(Style, Clone)]
 pub enum StyleBorderRadius {
@@ -22,18 +25,78 @@ pub enum StyleBorderRadius {
     },
 
     Oval,
+
+    /// Radii of the corners at the start and end sides, which become
+    /// `Radii4` once resolved by [`BoxStyles::apply_direction`].
+    ///
+    /// [`BoxStyles::apply_direction`]: super::BoxStyles::apply_direction
+    Logical {
+        start_top: Pixel,
+        end_top: Pixel,
+        end_bottom: Pixel,
+        start_bottom: Pixel,
+    },
 }
 
 impl StyleBorderRadius {
     pub fn oval(&mut self) {
         *self = StyleBorderRadius::Oval;
     }
+
+    pub fn logical(
+        &mut self,
+        start_top: Pixel,
+        end_top: Pixel,
+        end_bottom: Pixel,
+        start_bottom: Pixel,
+    ) {
+        *self = StyleBorderRadius::Logical {
+            start_top,
+            end_top,
+            end_bottom,
+            start_bottom,
+        };
+    }
+
+    pub(super) fn resolve(&mut self, direction: Direction) {
+        let StyleBorderRadius::Logical {
+            start_top,
+            end_top,
+            end_bottom,
+            start_bottom,
+        } = *self
+        else {
+            return;
+        };
+
+        *self = if direction.is_rtl() {
+            StyleBorderRadius::Radii4 {
+                left_top: end_top,
+                right_top: start_top,
+                right_bottom: start_bottom,
+                left_bottom: end_bottom,
+            }
+        } else {
+            StyleBorderRadius::Radii4 {
+                left_top: start_top,
+                right_top: end_top,
+                right_bottom: end_bottom,
+                left_bottom: start_bottom,
+            }
+        };
+    }
 }
 
 pub(super) fn parse_border_radius(rect: &Rect, border_radius: &StyleBorderRadius) -> RRect {
     match border_radius {
         StyleBorderRadius::Radii(r) => RRect::new_rect_xy(&rect, r.to_physical(), r.to_physical()),
         StyleBorderRadius::Oval => RRect::new_oval(&rect),
+        // drawn before resolved, as if left to right
+        StyleBorderRadius::Logical { .. } => {
+            let mut radius = border_radius.clone();
+            radius.resolve(Direction::Ltr);
+            parse_border_radius(rect, &radius)
+        }
         StyleBorderRadius::Radii4 {
             left_top,
             right_top,
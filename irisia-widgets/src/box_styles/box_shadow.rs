@@ -1,8 +1,8 @@
-use irisia::{primitive::Pixel, skia_safe::Color, Style};
-use irisia_core::skia_safe::{
+use irisia::skia_safe::{
     canvas::SaveLayerRec, BlendMode, BlurStyle, Canvas, Color4f, ColorSpace, MaskFilter, Paint,
     RRect,
 };
+use irisia::{primitive::Pixel, skia_safe::Color, Style};
 
 #[derive(Style, Clone)]
 #[style(from = "radius, [spread,] [color]")]
@@ -1,20 +1,36 @@
+use std::sync::Arc;
+
 use irisia::{
-    primitive::{Point, Region},
-    read_style,
-    skia_safe::{Canvas, Rect},
+    element::ElementHandle,
+    primitive::{Direction, Pixel, Point, Region},
+    skia_safe::{Canvas, ClipOp, Point as SkiaPoint, RRect, Rect},
     style::StyleContainer,
+    StyleReader,
 };
 
 use crate::box_styles::{
+    background::{draw_background, StyleBackground},
     border::{draw_border, StyleBorder},
     border_radius::{parse_border_radius, StyleBorderRadius},
     box_shadow::draw_shadow,
 };
 
-use super::{box_shadow::StyleBoxShadow, margin::StyleMargin};
+use super::{box_shadow::StyleBoxShadow, margin::StyleMargin, padding::StylePadding};
 
 pub struct BoxStyleRenderer;
 
+/// Box styles read from a style container. Elements can keep it to draw
+/// their box on rendering, when the style container is not available.
+#[derive(StyleReader, Clone)]
+pub struct BoxStyles {
+    pub background: Option<StyleBackground>,
+    pub border: Option<StyleBorder>,
+    pub radius: StyleBorderRadius,
+    pub box_shadow: Option<StyleBoxShadow>,
+    pub margin: StyleMargin,
+    pub padding: StylePadding,
+}
+
 #[derive(Default)]
 struct BoundReduction {
     top: f32,
@@ -23,22 +39,29 @@ struct BoundReduction {
     left: f32,
 }
 
+impl BoundReduction {
+    fn start(&self) -> Point {
+        Point(
+            Pixel::from_physical(self.left),
+            Pixel::from_physical(self.top),
+        )
+    }
+
+    fn end(&self) -> Point {
+        Point(
+            Pixel::from_physical(self.right),
+            Pixel::from_physical(self.bottom),
+        )
+    }
+}
+
 impl BoxStyleRenderer {
     pub fn draw_border_limited(
         styles: &impl StyleContainer,
         canvas: &mut Canvas,
         maximum_region: Region,
     ) -> Region {
-        let BoundReduction {
-            top,
-            right,
-            bottom,
-            left,
-        } = Self::render(styles, canvas, maximum_region);
-        (
-            maximum_region.0 + Point(left, top),
-            maximum_region.1 - Point(right, bottom),
-        )
+        BoxStyles::read_style(styles).draw_border_limited(canvas, maximum_region)
     }
 
     pub fn draw_border_unlimited(
@@ -46,63 +69,143 @@ impl BoxStyleRenderer {
         canvas: &mut Canvas,
         content_region: Region,
     ) -> Region {
-        let BoundReduction {
-            top,
-            right,
-            bottom,
-            left,
-        } = Self::render(styles, canvas, content_region);
+        BoxStyles::read_style(styles).draw_border_unlimited(canvas, content_region)
+    }
+}
+
+impl BoxStyles {
+    /// Resolve the start and end sides of the margin, the padding and the
+    /// border radius to physical sides in `direction`. Left and right
+    /// sides are kept as they are. Call it on styles just read.
+    pub fn apply_direction(&mut self, direction: Direction) {
+        self.margin.resolve(direction);
+        self.padding.resolve(direction);
+        self.radius.resolve(direction);
+    }
+
+    /// Decode background images in background, and redraw the element of
+    /// `handle` once each of them is ready. Call it once on styles just
+    /// read, with the styles they replace as `previous` so that images of
+    /// unchanged sources are kept rather than loaded again. Images not
+    /// decoded are skipped when drawing.
+    pub fn preload_images<El>(
+        &mut self,
+        previous: Option<&BoxStyles>,
+        handle: &Arc<ElementHandle<El>>,
+    ) where
+        El: Send + Sync + 'static,
+    {
+        if let Some(background) = &mut self.background {
+            let previous = previous.and_then(|previous| previous.background.as_ref());
+            background.preload_images(previous, handle);
+        }
+    }
+
+    /// Draw the box inside `maximum_region`, returns the content region.
+    pub fn draw_border_limited(&self, canvas: &mut Canvas, maximum_region: Region) -> Region {
+        let reduction = self.render(canvas, maximum_region);
+        (
+            maximum_region.0 + reduction.start(),
+            maximum_region.1 - reduction.end(),
+        )
+    }
+
+    /// Content region of the box inside `maximum_region`, the same as
+    /// [`Self::draw_border_limited`] returns, without drawing.
+    pub fn content_region(&self, maximum_region: Region) -> Region {
+        let width = Pixel::from_physical(self.border_width());
+        let (margin, padding) = (&self.margin, &self.padding);
+        (
+            maximum_region.0
+                + Point(
+                    margin.left + width + padding.left,
+                    margin.top + width + padding.top,
+                ),
+            maximum_region.1
+                - Point(
+                    margin.right + width + padding.right,
+                    margin.bottom + width + padding.bottom,
+                ),
+        )
+    }
+
+    /// Draw the box around `content_region`, returns the whole region of the box.
+    pub fn draw_border_unlimited(&self, canvas: &mut Canvas, content_region: Region) -> Region {
+        let reduction = self.render(canvas, content_region);
         (
-            content_region.0 - Point(left, top),
-            content_region.1 + Point(right, bottom),
+            content_region.0 - reduction.start(),
+            content_region.1 + reduction.end(),
         )
     }
 
-    fn render(styles: &impl StyleContainer, canvas: &mut Canvas, region: Region) -> BoundReduction {
-        read_style!(styles in styles => {
-            border: Option<StyleBorder>,
-            radius: StyleBorderRadius,
-            box_shadow: Option<StyleBoxShadow>,
-            margin: StyleMargin,
-        });
-
-        let mut reduction = BoundReduction::default();
-
-        let rect = {
-            let StyleMargin {
-                top,
-                right,
-                bottom,
-                left,
-            } = styles.margin;
-
-            let (left, top, right, bottom) = (
-                left.to_physical(),
-                top.to_physical(),
-                right.to_physical(),
-                bottom.to_physical(),
-            );
-
-            reduction.left += left;
-            reduction.top += top;
-            reduction.right += right;
-            reduction.bottom += bottom;
-
-            Rect::new(
-                region.0 .0 + left,
-                region.0 .1 + top,
-                region.1 .0 - right,
-                region.1 .1 - bottom,
-            )
+    /// Clip the canvas to the inside of the border, around the content
+    /// region returned by drawing the box, following the border radius.
+    pub fn clip_content(&self, canvas: &mut Canvas, content_region: Region) {
+        let rect = Rect::new(
+            content_region.0 .0.to_physical(),
+            content_region.0 .1.to_physical(),
+            content_region.1 .0.to_physical(),
+            content_region.1 .1.to_physical(),
+        );
+
+        let width = self.border_width();
+        let padding = &self.padding;
+        let outer = Rect::new(
+            rect.left - padding.left.to_physical() - width,
+            rect.top - padding.top.to_physical() - width,
+            rect.right + padding.right.to_physical() + width,
+            rect.bottom + padding.bottom.to_physical() + width,
+        );
+        let mut rrect = parse_border_radius(&outer, &self.radius);
+        rrect.inset(SkiaPoint::new(width, width));
+        canvas.clip_rrect(rrect, ClipOp::Intersect, true);
+    }
+
+    fn border_width(&self) -> f32 {
+        self.border
+            .as_ref()
+            .map(|border| border.width.to_physical())
+            .unwrap_or_default()
+    }
+
+    fn outer_rrect(&self, region: Region) -> (RRect, BoundReduction) {
+        let StyleMargin {
+            top,
+            right,
+            bottom,
+            left,
+            ..
+        } = self.margin;
+
+        let reduction = BoundReduction {
+            left: left.to_physical(),
+            top: top.to_physical(),
+            right: right.to_physical(),
+            bottom: bottom.to_physical(),
         };
 
-        let rrect = parse_border_radius(&rect, &styles.radius);
+        let rect = Rect::new(
+            region.0 .0.to_physical() + reduction.left,
+            region.0 .1.to_physical() + reduction.top,
+            region.1 .0.to_physical() - reduction.right,
+            region.1 .1.to_physical() - reduction.bottom,
+        );
+
+        (parse_border_radius(&rect, &self.radius), reduction)
+    }
+
+    fn render(&self, canvas: &mut Canvas, region: Region) -> BoundReduction {
+        let (rrect, mut reduction) = self.outer_rrect(region);
 
-        if let Some(bs) = &styles.box_shadow {
+        if let Some(bs) = &self.box_shadow {
             draw_shadow(canvas, &rrect, bs);
         }
 
-        if let Some(border) = &styles.border {
+        if let Some(background) = &self.background {
+            draw_background(canvas, &rrect, background);
+        }
+
+        if let Some(border) = &self.border {
             let width = draw_border(canvas, rrect, border);
             reduction.left += width;
             reduction.top += width;
@@ -110,6 +213,10 @@ impl BoxStyleRenderer {
             reduction.bottom += width;
         }
 
+        reduction.left += self.padding.left.to_physical();
+        reduction.top += self.padding.top.to_physical();
+        reduction.right += self.padding.right.to_physical();
+        reduction.bottom += self.padding.bottom.to_physical();
         reduction
     }
 }
@@ -1,5 +1,13 @@
-use irisia_core::{primitive::Pixel, Style};
+use irisia::{
+    primitive::{Direction, Pixel},
+    Style,
+};
 
+/// Space outside the border. `left` and `right` are physical sides,
+/// `start` and `end` follow the direction and take precedence once
+/// resolved by [`BoxStyles::apply_direction`].
+///
+/// [`BoxStyles::apply_direction`]: super::BoxStyles::apply_direction
 #[derive(Style, Clone, Copy)]
 #[style(from, from = "", impl_default)]
 pub struct StyleMargin {
@@ -14,6 +22,39 @@ pub struct StyleMargin {
 
     #[style(option, default)]
     pub bottom: Pixel,
+
+    #[style(skip, default)]
+    pub start: Option<Pixel>,
+
+    #[style(skip, default)]
+    pub end: Option<Pixel>,
+}
+
+impl StyleMargin {
+    /// Margin at the start side, left in left to right direction.
+    pub fn start(&mut self, px: Pixel) {
+        self.start = Some(px);
+    }
+
+    /// Margin at the end side, right in left to right direction.
+    pub fn end(&mut self, px: Pixel) {
+        self.end = Some(px);
+    }
+
+    pub(super) fn resolve(&mut self, direction: Direction) {
+        let (start, end) = if direction.is_rtl() {
+            (&mut self.right, &mut self.left)
+        } else {
+            (&mut self.left, &mut self.right)
+        };
+
+        if let Some(px) = self.start {
+            *start = px;
+        }
+        if let Some(px) = self.end {
+            *end = px;
+        }
+    }
 }
 
 impl From<(Pixel,)> for StyleMargin {
@@ -23,6 +64,8 @@ impl From<(Pixel,)> for StyleMargin {
             top: px,
             right: px,
             bottom: px,
+            start: None,
+            end: None,
         }
     }
 }
@@ -34,6 +77,8 @@ impl From<(Pixel, Pixel)> for StyleMargin {
             top: y,
             right: x,
             bottom: y,
+            start: None,
+            end: None,
         }
     }
 }
@@ -1,13 +1,20 @@
+pub mod background;
 pub mod border;
 pub mod border_radius;
 pub mod box_shadow;
 pub mod box_style_renderer;
 pub mod margin;
+pub mod padding;
 
 pub use self::{
+    background::{
+        BackgroundImage, BackgroundLayer, BackgroundRepeat, BackgroundSize, ColorStops,
+        ConicGradient, LinearGradient, RadialGradient, StyleBackground,
+    },
     border::{DashStyle, StyleBorder},
     border_radius::StyleBorderRadius,
     box_shadow::StyleBoxShadow,
-    box_style_renderer::BoxStyleRenderer,
+    box_style_renderer::{BoxStyleRenderer, BoxStyles},
     margin::StyleMargin,
+    padding::StylePadding,
 };
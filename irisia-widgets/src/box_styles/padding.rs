@@ -0,0 +1,84 @@
+use irisia::{
+    primitive::{Direction, Pixel},
+    Style,
+};
+
+/// Space between the border and the content. `left` and `right` are
+/// physical sides, `start` and `end` follow the direction and take
+/// precedence once resolved by [`BoxStyles::apply_direction`].
+///
+/// [`BoxStyles::apply_direction`]: super::BoxStyles::apply_direction
+#[derive(Style, Clone, Copy)]
+#[style(from, from = "", impl_default)]
+pub struct StylePadding {
+    #[style(option, default)]
+    pub left: Pixel,
+
+    #[style(option, default)]
+    pub top: Pixel,
+
+    #[style(option, default)]
+    pub right: Pixel,
+
+    #[style(option, default)]
+    pub bottom: Pixel,
+
+    #[style(skip, default)]
+    pub start: Option<Pixel>,
+
+    #[style(skip, default)]
+    pub end: Option<Pixel>,
+}
+
+impl StylePadding {
+    /// Padding at the start side, left in left to right direction.
+    pub fn start(&mut self, px: Pixel) {
+        self.start = Some(px);
+    }
+
+    /// Padding at the end side, right in left to right direction.
+    pub fn end(&mut self, px: Pixel) {
+        self.end = Some(px);
+    }
+
+    pub(super) fn resolve(&mut self, direction: Direction) {
+        let (start, end) = if direction.is_rtl() {
+            (&mut self.right, &mut self.left)
+        } else {
+            (&mut self.left, &mut self.right)
+        };
+
+        if let Some(px) = self.start {
+            *start = px;
+        }
+        if let Some(px) = self.end {
+            *end = px;
+        }
+    }
+}
+
+impl From<(Pixel,)> for StylePadding {
+    fn from((px,): (Pixel,)) -> Self {
+        Self {
+            left: px,
+            top: px,
+            right: px,
+            bottom: px,
+            start: None,
+            end: None,
+        }
+    }
+}
+
+impl From<(Pixel, Pixel)> for StylePadding {
+    fn from((x, y): (Pixel, Pixel)) -> Self {
+        Self {
+            left: x,
+            top: y,
+            right: x,
+            bottom: y,
+            start: None,
+            end: None,
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use std::{sync::Arc, time::Duration};
+
+use irisia::{
+    element::{
+        ChildrenNodes, ChildrenSetter, Element, ElementHandle, RenderElement, UpdateElement,
+    },
+    primitive::{Direction, Region},
+    skia_safe::{textlayout::TextAlign, Point as SkiaPoint},
+    style::StyleContainer,
+    Event, Result, StyleReader, UpdateWith,
+};
+use tokio::task::JoinHandle;
+
+use crate::{
+    box_styles::BoxStyles,
+    interaction::{run_pressable, InteractionState, Pressable},
+    textbox::{layout::TextLayout, styles::StyleTextAlign, RichText, TextBoxStyles},
+};
+
+pub use self::styles::*;
+
+pub mod styles;
+
+/// Clickable button with a label. Emits [`ButtonActivated`] when clicked,
+/// or when `Enter` or `Space` is pressed while it is focused, unless it
+/// is disabled.
+///
+/// Styles of the button can be overridden in states, see
+/// [`StyleButtonHovered`], [`StyleButtonPressed`], [`StyleButtonFocused`]
+/// and [`StyleButtonDisabled`]. The label is centered unless it has a
+/// [`StyleTextAlign`].
+pub struct Button {
+    runtime: Option<JoinHandle<()>>,
+    label: RichText,
+    disabled: bool,
+    state: InteractionState,
+    styles: ButtonStyles,
+    direction: Direction,
+    layout: Option<(TextBoxStyles, TextLayout)>,
+}
+
+#[derive(StyleReader, Clone)]
+struct ButtonStyles {
+    text: TextBoxStyles,
+    box_styles: BoxStyles,
+    hovered: Option<StyleButtonHovered>,
+    pressed: Option<StyleButtonPressed>,
+    focused: Option<StyleButtonFocused>,
+    disabled: Option<StyleButtonDisabled>,
+}
+
+#[derive(Default)]
+pub struct ButtonProps {
+    pub label: RichText,
+    pub disabled: bool,
+}
+
+impl ButtonProps {
+    pub fn label(mut self, label: impl Into<RichText>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// A disabled button cannot be hovered, pressed, focused or activated.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// The button is clicked, or activated by keyboard.
+#[derive(Event, Clone, Copy)]
+pub struct ButtonActivated;
+
+impl Element for Button {
+    type BlankProps = ButtonProps;
+
+    fn layout<'a, Ch>(&mut self, _: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        setter.set_children(());
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        let (box_styles, text_styles) = self.current_styles();
+
+        let canvas = renderer.canvas();
+        let content_region = box_styles.draw_border_limited(canvas, draw_region);
+        let (start, end) = content_region;
+
+        if !matches!(&self.layout, Some((styles, _)) if *styles == text_styles) {
+            let layout = TextLayout::new(&self.label, &text_styles, self.direction);
+            self.layout = Some((text_styles, layout));
+        }
+        let layout = &mut self.layout.as_mut().unwrap().1;
+
+        let width = (end.0 - start.0).to_physical().max(0.0);
+        let height = (end.1 - start.1).to_physical().max(0.0);
+        layout.layout(width);
+
+        // center the label vertically
+        let top = ((height - layout.height()) / 2.0).max(0.0);
+        let origin = SkiaPoint::new(start.0.to_physical(), start.1.to_physical() + top);
+
+        let count = canvas.save();
+        box_styles.clip_content(canvas, content_region);
+        layout.paint(canvas, origin);
+        canvas.restore_to_count(count);
+
+        renderer.set_interact_region(draw_region);
+        Ok(())
+    }
+}
+
+impl<'a, Sty> UpdateWith<UpdateElement<'a, Self, ButtonProps, Sty>> for Button
+where
+    Sty: StyleContainer,
+{
+    fn create_with(updater: UpdateElement<'a, Self, ButtonProps, Sty>) -> Self {
+        let handle = updater.handle;
+        let mut button = Button {
+            runtime: Some(tokio::spawn(run_pressable(
+                Arc::downgrade(handle),
+                handle.event_dispatcher().clone(),
+                handle.global().clone(),
+            ))),
+            label: RichText::new(),
+            disabled: false,
+            state: InteractionState::default(),
+            styles: read_styles(updater.styles, None, handle),
+            direction: handle.direction(),
+            layout: None,
+        };
+
+        button.update_with(updater, false);
+        button
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, ButtonProps, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        let UpdateElement {
+            props: ButtonProps { label, disabled },
+            styles,
+            handle,
+        } = updater;
+
+        let direction = handle.direction();
+        self.styles = read_styles(styles, Some(&self.styles.box_styles), handle);
+        if self.label != label || self.direction != direction {
+            self.label = label;
+            self.direction = direction;
+            self.layout = None;
+        }
+
+        if disabled && !self.disabled {
+            self.state.pressed = false;
+            handle.blur();
+        }
+        self.disabled = disabled;
+
+        // box styles cannot be compared
+        false
+    }
+}
+
+impl Drop for Button {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            runtime.abort();
+        }
+    }
+}
+
+impl Pressable for Button {
+    type Activated = ButtonActivated;
+
+    const ACTIVATE_ON_ENTER: bool = true;
+
+    fn interaction(&mut self) -> &mut InteractionState {
+        &mut self.state
+    }
+
+    fn disabled(&self) -> bool {
+        self.disabled
+    }
+
+    fn activate(&mut self) -> Option<ButtonActivated> {
+        Some(ButtonActivated)
+    }
+}
+
+impl Button {
+    pub fn state(&self) -> InteractionState {
+        self.state
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Styles with overrides of the current state applied.
+    fn current_styles(&self) -> (BoxStyles, TextBoxStyles) {
+        let ButtonStyles {
+            text,
+            box_styles,
+            hovered,
+            pressed,
+            focused,
+            disabled,
+        } = &self.styles;
+
+        let mut box_styles = box_styles.clone();
+        let mut text = text.clone();
+        let mut apply = |state: Option<&StateStyles>| {
+            if let Some(state) = state {
+                state.apply(&mut box_styles, &mut text);
+            }
+        };
+
+        if self.disabled {
+            apply(disabled.as_ref().map(|s| &s.0));
+        } else {
+            let InteractionState {
+                hovered: is_hovered,
+                pressed: is_pressed,
+                focused: is_focused,
+            } = self.state;
+
+            // later states take precedence
+            if is_focused {
+                apply(focused.as_ref().map(|s| &s.0));
+            }
+            if is_hovered {
+                apply(hovered.as_ref().map(|s| &s.0));
+            }
+            if is_pressed {
+                apply(pressed.as_ref().map(|s| &s.0));
+            }
+        }
+
+        (box_styles, text)
+    }
+}
+
+fn read_styles(
+    styles: &impl StyleContainer,
+    previous: Option<&BoxStyles>,
+    handle: &Arc<ElementHandle<Button>>,
+) -> ButtonStyles {
+    let mut read = ButtonStyles::read_style(styles);
+    read.box_styles.apply_direction(handle.direction());
+    read.box_styles.preload_images(previous, handle);
+    if styles.get_style::<StyleTextAlign>().is_none() {
+        read.text.align = StyleTextAlign(TextAlign::Center);
+    }
+    read
+}
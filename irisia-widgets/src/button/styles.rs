@@ -0,0 +1,73 @@
+use irisia::{skia_safe::Color, style::StyleColor, Style};
+
+use crate::box_styles::{BackgroundLayer, BoxStyles, StyleBackground};
+use crate::textbox::TextBoxStyles;
+
+/// Styles overriding the normal styles of a button in a state. Styles
+/// not set keep the normal ones.
+#[derive(Clone, Default)]
+pub struct StateStyles {
+    pub background: Option<StyleBackground>,
+    pub border_color: Option<Color>,
+    pub color: Option<Color>,
+}
+
+impl StateStyles {
+    pub(super) fn apply(&self, box_styles: &mut BoxStyles, text: &mut TextBoxStyles) {
+        if let Some(background) = &self.background {
+            box_styles.background = Some(background.clone());
+        }
+        if let (Some(color), Some(border)) = (self.border_color, &mut box_styles.border) {
+            border.color = color;
+        }
+        if let Some(color) = self.color {
+            text.color = Some(StyleColor(color));
+        }
+    }
+}
+
+macro_rules! state_styles {
+    ($($(#[$meta:meta])* $Name:ident,)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Style, Clone)]
+            #[style(from = "", impl_default)]
+            pub struct $Name(#[style(default)] pub StateStyles);
+
+            impl $Name {
+                /// Add a background layer, layers added replace the
+                /// normal background together.
+                pub fn background(&mut self, layer: impl Into<BackgroundLayer>) {
+                    self.0
+                        .background
+                        .get_or_insert_with(Default::default)
+                        .0
+                        .push(layer.into());
+                }
+
+                pub fn border_color(&mut self, color: Color) {
+                    self.0.border_color = Some(color);
+                }
+
+                /// Color of the label.
+                pub fn color(&mut self, color: Color) {
+                    self.0.color = Some(color);
+                }
+            }
+        )*
+    };
+}
+
+state_styles! {
+    /// Overrides while the pointer is over the button.
+    StyleButtonHovered,
+
+    /// Overrides while the button is pressed by the pointer or `Space`.
+    StyleButtonPressed,
+
+    /// Overrides while the button is focused.
+    StyleButtonFocused,
+
+    /// Overrides while the button is disabled, other states are ignored.
+    StyleButtonDisabled,
+}
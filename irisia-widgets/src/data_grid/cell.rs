@@ -0,0 +1,178 @@
+use std::{sync::Arc, time::Duration};
+
+use irisia::{
+    element::{ChildrenNodes, ChildrenSetter, Element, RenderElement, UpdateElement},
+    primitive::{Direction, Pixel, Point, Region},
+    skia_safe::{Color, Path, Rect},
+    structure::boxed::Boxed,
+    style::StyleContainer,
+    Result, UpdateWith,
+};
+
+use crate::indicator::{fill, stroke};
+
+use super::column::SortOrder;
+
+/// Width of the sort arrow of a header.
+const ARROW_WIDTH: f32 = 8.0;
+
+pub(super) type ContentFn = Arc<dyn Fn() -> Boxed + Send + Sync>;
+
+/// Element holding the content of a cell or a header. The content is
+/// laid out in the region of the cell without horizontal padding.
+pub(super) struct GridCell {
+    props: GridCellProps,
+    direction: Direction,
+}
+
+#[derive(Default)]
+pub(super) struct GridCellProps {
+    pub content: Option<ContentFn>,
+    pub background: Option<Color>,
+
+    /// Color of lines at the bottom and the end side.
+    pub line: Option<Color>,
+    pub outline: Option<Color>,
+    pub sort: Option<SortOrder>,
+    pub padding: Pixel,
+
+    /// Region accepting pointer events, the cell out of it is covered.
+    pub clip: Region,
+}
+
+impl Element for GridCell {
+    type BlankProps = GridCellProps;
+
+    fn layout<'a, Ch>(&mut self, draw_region: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        let content = match &self.props.content {
+            Some(content) => content(),
+            None => Boxed::new(()),
+        };
+
+        // leave space for the sort arrow at the end side
+        let padding = self.props.padding;
+        let arrow = match self.props.sort {
+            Some(_) => Pixel(ARROW_WIDTH) + padding,
+            None => Pixel(0.0),
+        };
+        let (start_pad, end_pad) = match self.direction {
+            Direction::Ltr => (padding, padding + arrow),
+            Direction::Rtl => (padding + arrow, padding),
+        };
+
+        let (start, end) = draw_region;
+        let region = (
+            Point(start.0 + start_pad, start.1),
+            Point(end.0 - end_pad, end.1),
+        );
+        let _ = setter
+            .set_children(content)
+            .layout(std::iter::repeat(region));
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        let rect = to_rect(draw_region);
+        if let Some(color) = self.props.background {
+            renderer.canvas().draw_rect(rect, &fill(color));
+        }
+
+        renderer.render_children()?;
+        let canvas = renderer.canvas();
+        let rtl = self.direction.is_rtl();
+
+        if let Some(color) = self.props.line {
+            let paint = stroke(color, 1.0);
+            let edge = if rtl {
+                rect.left + 0.5
+            } else {
+                rect.right - 0.5
+            };
+            canvas.draw_line(
+                (rect.left, rect.bottom - 0.5),
+                (rect.right, rect.bottom - 0.5),
+                &paint,
+            );
+            canvas.draw_line((edge, rect.top), (edge, rect.bottom), &paint);
+        }
+
+        if let Some(order) = self.props.sort {
+            let padding = self.props.padding.to_physical();
+            let x = if rtl {
+                rect.left + padding
+            } else {
+                rect.right - padding - ARROW_WIDTH
+            };
+            let (y, half) = (rect.center_y(), ARROW_WIDTH / 2.0);
+            let (tip, base) = match order {
+                SortOrder::Ascending => (y - half / 2.0, y + half / 2.0),
+                SortOrder::Descending => (y + half / 2.0, y - half / 2.0),
+            };
+
+            let mut path = Path::new();
+            path.move_to((x, base))
+                .line_to((x + ARROW_WIDTH, base))
+                .line_to((x + half, tip))
+                .close();
+            canvas.draw_path(&path, &fill(Color::from_rgb(0x61, 0x61, 0x61)));
+        }
+
+        if let Some(color) = self.props.outline {
+            canvas.draw_rect(rect.with_inset((1.0, 1.0)), &stroke(color, 2.0));
+        }
+
+        match intersect(draw_region, self.props.clip) {
+            Some(region) => renderer.set_interact_region(region),
+            None => renderer.clear_interact_region(),
+        };
+        Ok(())
+    }
+}
+
+impl<'a, Sty> UpdateWith<UpdateElement<'a, Self, GridCellProps, Sty>> for GridCell
+where
+    Sty: StyleContainer,
+{
+    fn create_with(updater: UpdateElement<'a, Self, GridCellProps, Sty>) -> Self {
+        GridCell {
+            direction: updater.handle.direction(),
+            props: updater.props,
+        }
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, GridCellProps, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        self.props = updater.props;
+        self.direction = updater.handle.direction();
+
+        // contents cannot be compared
+        false
+    }
+}
+
+pub(super) fn to_rect((start, end): Region) -> Rect {
+    Rect::new(
+        start.0.to_physical(),
+        start.1.to_physical(),
+        end.0.to_physical(),
+        end.1.to_physical(),
+    )
+}
+
+fn intersect(a: Region, b: Region) -> Option<Region> {
+    let max = |a: Pixel, b: Pixel| if a > b { a } else { b };
+    let min = |a: Pixel, b: Pixel| if a < b { a } else { b };
+    let start = Point(max(a.0 .0, b.0 .0), max(a.0 .1, b.0 .1));
+    let end = Point(min(a.1 .0, b.1 .0), min(a.1 .1, b.1 .1));
+    (start.0 < end.0 && start.1 < end.1).then_some((start, end))
+}
@@ -0,0 +1,83 @@
+use irisia::primitive::Pixel;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Definition of a column of a data grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub title: String,
+    pub width: Pixel,
+    pub min_width: Pixel,
+    pub max_width: Pixel,
+
+    /// Whether the width can be changed by dragging the end edge of the
+    /// header.
+    pub resizable: bool,
+
+    /// Whether clicking the header requests sorting by this column.
+    pub sortable: bool,
+}
+
+impl Column {
+    /// Column `120px` wide, resizable between `40px` and any width, and
+    /// not sortable.
+    pub fn new(title: impl Into<String>) -> Self {
+        Column {
+            title: title.into(),
+            width: Pixel(120.0),
+            min_width: Pixel(40.0),
+            max_width: Pixel(f32::INFINITY),
+            resizable: true,
+            sortable: false,
+        }
+    }
+
+    pub fn width(mut self, width: Pixel) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn min_width(mut self, min_width: Pixel) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    pub fn max_width(mut self, max_width: Pixel) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+
+    /// Clamp `width` between `min_width` and `max_width`.
+    pub(super) fn clamp(&self, width: f32) -> f32 {
+        width.min(self.max_width.0).max(self.min_width.0)
+    }
+}
+
+/// Sort after clicking the header of `column` when sorted by `current`:
+/// ascending, then descending, then not sorted.
+pub(super) fn next_sort(
+    current: Option<(usize, SortOrder)>,
+    column: usize,
+) -> Option<(usize, SortOrder)> {
+    match current {
+        Some((sorted, SortOrder::Ascending)) if sorted == column => {
+            Some((column, SortOrder::Descending))
+        }
+        Some((sorted, SortOrder::Descending)) if sorted == column => None,
+        _ => Some((column, SortOrder::Ascending)),
+    }
+}
@@ -0,0 +1,632 @@
+//! Grid of cells in rows and columns, for large tables. Like
+//! toggles, the sort is owned by the parent: clicking a header emits the
+//! requested sort, and the parent sorts the rows and passes it back.
+
+use std::{
+    ops::Range,
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use irisia::{
+    element::{
+        ChildrenNodes, ChildrenSetter, Element, ElementHandle, RenderElement, UpdateElement,
+    },
+    primitive::{Direction, Pixel, Point, Region},
+    structure::{add_one, boxed::Boxed, once::Once, recycle::Recycle},
+    style::StyleContainer,
+    winit::event::VirtualKeyCode,
+    Event, Result, StyleReader, UpdateWith,
+};
+use tokio::task::JoinHandle;
+
+use crate::textbox::{TextBox, TextBoxProps};
+
+pub use self::{
+    column::{Column, SortOrder},
+    styles::*,
+};
+
+use self::{
+    cell::{to_rect, ContentFn, GridCell, GridCellProps},
+    styles::GridStyles,
+};
+
+mod cell;
+mod column;
+mod runtime;
+pub mod styles;
+
+/// Rows created before and after rows in view.
+const OVERSCAN: usize = 2;
+
+/// Distance from the end edge of a header where dragging resizes the
+/// column.
+const RESIZE_SLOP: f32 = 4.0;
+
+type KeyFn = Arc<dyn Fn(usize) -> u64 + Send + Sync>;
+type CellFn = Arc<dyn Fn(CellContext) -> Boxed + Send + Sync>;
+type HeaderFn = Arc<dyn Fn(usize) -> Boxed + Send + Sync>;
+
+/// Grid with a header sticking at the top, and rows scrolled by the
+/// mouse wheel, horizontally with `Shift`. Only rows in view have cell
+/// elements, whose contents are built by `cell`, usually a
+/// [`build!`](irisia::build) structure in a [`Boxed`].
+///
+/// Clicking a cell selects it and emits [`SelectionChanged`]. While
+/// focused, arrow keys move the selection, `PageUp` and `PageDown` by a
+/// page, `Home` and `End` to the first and the last column, or row with
+/// `Ctrl`. Clicking the header of a sortable column emits
+/// [`SortChanged`], and dragging the end edge of the header of a
+/// resizable column resizes it, emitting [`ColumnResized`] when
+/// released.
+pub struct DataGrid {
+    runtime: Option<JoinHandle<()>>,
+    handle: Weak<ElementHandle<Self>>,
+    columns: Vec<Column>,
+    widths: Vec<f32>,
+    rows: usize,
+    row_key: KeyFn,
+    cell: CellFn,
+    header: Option<HeaderFn>,
+    sort: Option<(usize, SortOrder)>,
+    selection: Option<CellPosition>,
+    focused: bool,
+    styles: GridStyles,
+    direction: Direction,
+
+    /// Horizontal and vertical offsets of the view.
+    scroll: (f32, f32),
+    reveal: Option<CellPosition>,
+    region: Region,
+    page_rows: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CellPosition {
+    pub row: usize,
+    pub column: usize,
+}
+
+/// Cell to build the content of.
+#[derive(Debug, Clone, Copy)]
+pub struct CellContext {
+    pub row: usize,
+    pub column: usize,
+    pub selected: bool,
+}
+
+pub struct DataGridProps {
+    pub columns: Vec<Column>,
+    pub rows: usize,
+
+    /// Key of the row at an index, the index by default.
+    pub row_key: KeyFn,
+
+    /// Content of a cell, empty by default.
+    pub cell: CellFn,
+
+    /// Content of the header of a column, the title by default.
+    pub header: Option<HeaderFn>,
+    pub sort: Option<(usize, SortOrder)>,
+}
+
+impl Default for DataGridProps {
+    fn default() -> Self {
+        DataGridProps {
+            columns: Vec::new(),
+            rows: 0,
+            row_key: Arc::new(|row| row as u64),
+            cell: Arc::new(|_| Boxed::new(())),
+            header: None,
+            sort: None,
+        }
+    }
+}
+
+impl DataGridProps {
+    pub fn column(mut self, column: Column) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    pub fn columns(mut self, columns: impl IntoIterator<Item = Column>) -> Self {
+        self.columns = columns.into_iter().collect();
+        self
+    }
+
+    pub fn rows(mut self, rows: usize) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Keys should be unique and stay the same for the same row when
+    /// rows are sorted, inserted or removed.
+    pub fn row_key<F>(mut self, row_key: F) -> Self
+    where
+        F: Fn(usize) -> u64 + Send + Sync + 'static,
+    {
+        self.row_key = Arc::new(row_key);
+        self
+    }
+
+    pub fn cell<F>(mut self, cell: F) -> Self
+    where
+        F: Fn(CellContext) -> Boxed + Send + Sync + 'static,
+    {
+        self.cell = Arc::new(cell);
+        self
+    }
+
+    pub fn header<F>(mut self, header: F) -> Self
+    where
+        F: Fn(usize) -> Boxed + Send + Sync + 'static,
+    {
+        self.header = Some(Arc::new(header));
+        self
+    }
+
+    pub fn sort(mut self, sort: Option<(usize, SortOrder)>) -> Self {
+        self.sort = sort;
+        self
+    }
+}
+
+/// Sort requested by clicking a header, `None` for not sorted.
+#[derive(Event, Clone, Copy)]
+pub struct SortChanged(pub Option<(usize, SortOrder)>);
+
+/// Selected cell changed by pointer or keyboard.
+#[derive(Event, Clone, Copy)]
+pub struct SelectionChanged(pub Option<CellPosition>);
+
+/// Width of a column after resized by dragging.
+#[derive(Event, Clone, Copy)]
+pub struct ColumnResized {
+    pub column: usize,
+    pub width: Pixel,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum GridKey {
+    Header(usize),
+    Cell(u64, usize),
+}
+
+/// Part of the grid at a position.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Hit {
+    Resize(usize),
+    Header(usize),
+    Cell(CellPosition),
+}
+
+impl Element for DataGrid {
+    type BlankProps = DataGridProps;
+
+    fn layout<'a, Ch>(&mut self, draw_region: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        self.region = draw_region;
+        let (start, end) = draw_region;
+        let width = (end.0 - start.0).0.max(0.0);
+        let row_height = self.row_height();
+        let body_height = ((end.1 - start.1).0 - self.header_height()).max(0.0);
+
+        if let Some(cell) = self.reveal.take() {
+            self.reveal_cell(cell, width, body_height);
+        }
+        let max_x = (self.widths.iter().sum::<f32>() - width).max(0.0);
+        let max_y = (self.rows as f32 * row_height - body_height).max(0.0);
+        self.scroll = (
+            self.scroll.0.clamp(0.0, max_x),
+            self.scroll.1.clamp(0.0, max_y),
+        );
+        self.page_rows = ((body_height / row_height) as usize).max(1);
+
+        let first = (self.scroll.1 / row_height) as usize;
+        let last = ((self.scroll.1 + body_height) / row_height).ceil() as usize;
+        let rows = first.saturating_sub(OVERSCAN)..(last + OVERSCAN).min(self.rows);
+
+        let header_bottom = start.1 + Pixel(self.header_height());
+        let header_clip = (start, Point(end.0, header_bottom));
+        let body_clip = (Point(start.0, header_bottom), end);
+
+        let this = &*self;
+        let cells = cells(rows, this.columns.len())
+            .map(|cell| {
+                (
+                    GridKey::Cell((this.row_key)(cell.row), cell.column),
+                    this.cell_props(cell, body_clip),
+                    this.cell_region(cell),
+                )
+            })
+            .chain((0..this.columns.len()).map(|column| {
+                (
+                    GridKey::Header(column),
+                    this.header_props(column, header_clip),
+                    this.header_region(column),
+                )
+            }));
+
+        // headers are the last to be drawn above cells scrolled under them
+        let mut regions = Vec::new();
+        let mut children = setter.set_children(
+            Recycle::new(cells.map(|(key, props, region)| {
+                regions.push(region);
+                (
+                    key,
+                    Once(add_one::<GridCell, _, _, _, _>(props, (), (), |_: &_| {})),
+                )
+            }))
+            .pool_size(this.columns.len() * (OVERSCAN + 1)),
+        );
+        let _ = children.layout(regions);
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        let canvas = renderer.canvas();
+        let count = canvas.save();
+        canvas.clip_rect(to_rect(draw_region), None, true);
+
+        let result = renderer.render_children().map(|_| ());
+        renderer.canvas().restore_to_count(count);
+        renderer.set_interact_region(draw_region);
+        result
+    }
+}
+
+impl<'a, Sty> UpdateWith<UpdateElement<'a, Self, DataGridProps, Sty>> for DataGrid
+where
+    Sty: StyleContainer,
+{
+    fn create_with(updater: UpdateElement<'a, Self, DataGridProps, Sty>) -> Self {
+        let handle = updater.handle;
+        let mut grid = DataGrid {
+            runtime: Some(tokio::spawn(runtime::run(
+                Arc::downgrade(handle),
+                handle.event_dispatcher().clone(),
+                handle.global().clone(),
+            ))),
+            handle: Arc::downgrade(handle),
+            columns: Vec::new(),
+            widths: Vec::new(),
+            rows: 0,
+            row_key: updater.props.row_key.clone(),
+            cell: updater.props.cell.clone(),
+            header: None,
+            sort: None,
+            selection: None,
+            focused: false,
+            styles: GridStyles::read_style(updater.styles),
+            direction: handle.direction(),
+            scroll: (0.0, 0.0),
+            reveal: None,
+            region: Default::default(),
+            page_rows: 1,
+        };
+
+        grid.update_with(updater, false);
+        grid
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, DataGridProps, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        let UpdateElement {
+            props:
+                DataGridProps {
+                    columns,
+                    rows,
+                    row_key,
+                    cell,
+                    header,
+                    sort,
+                },
+            styles,
+            handle,
+        } = updater;
+
+        // widths resized by dragging are kept until columns change
+        if self.columns != columns {
+            self.widths = columns.iter().map(|c| c.clamp(c.width.0)).collect();
+            self.columns = columns;
+        }
+
+        self.rows = rows;
+        self.selection = self
+            .selection
+            .filter(|cell| cell.row < rows && cell.column < self.columns.len());
+        self.row_key = row_key;
+        self.cell = cell;
+        self.header = header;
+        self.sort = sort;
+        self.styles = GridStyles::read_style(styles);
+        self.direction = handle.direction();
+
+        // contents cannot be compared
+        false
+    }
+}
+
+impl Drop for DataGrid {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            runtime.abort();
+        }
+    }
+}
+
+impl DataGrid {
+    pub fn selection(&self) -> Option<CellPosition> {
+        self.selection
+    }
+
+    /// Select `cell` and scroll it into view, without emitting
+    /// [`SelectionChanged`].
+    pub fn set_selection(&mut self, cell: Option<CellPosition>) {
+        self.select(cell);
+    }
+
+    pub fn sort(&self) -> Option<(usize, SortOrder)> {
+        self.sort
+    }
+
+    /// Width of `column`, which may be resized by dragging.
+    pub fn column_width(&self, column: usize) -> Pixel {
+        Pixel(self.widths[column])
+    }
+
+    /// Horizontal and vertical offsets of the view.
+    pub fn scroll_offset(&self) -> (Pixel, Pixel) {
+        (Pixel(self.scroll.0), Pixel(self.scroll.1))
+    }
+
+    pub fn scroll_by(&mut self, x: Pixel, y: Pixel) {
+        self.scroll.0 += x.0;
+        self.scroll.1 += y.0;
+        self.request_layout();
+    }
+
+    /// Scroll the least to put `row` in view.
+    pub fn scroll_to_row(&mut self, row: usize) {
+        let column = self.selection.map_or(0, |cell| cell.column);
+        self.reveal = Some(CellPosition { row, column });
+        self.request_layout();
+    }
+
+    /// Select `cell` and scroll it into view, returns whether the
+    /// selection changed.
+    fn select(&mut self, cell: Option<CellPosition>) -> bool {
+        let cell = cell.filter(|cell| cell.row < self.rows && cell.column < self.columns.len());
+        self.reveal = cell;
+        self.request_layout();
+
+        let changed = self.selection != cell;
+        self.selection = cell;
+        changed
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        self.request_layout();
+    }
+
+    fn resize_column(&mut self, column: usize, width: f32) {
+        self.widths[column] = self.columns[column].clamp(width);
+        self.request_layout();
+    }
+
+    fn request_layout(&self) {
+        if let Some(handle) = self.handle.upgrade() {
+            handle.request_layout();
+        }
+    }
+
+    /// Cell the key moves the selection to, `None` if not moved.
+    fn navigate(&self, key: VirtualKeyCode, ctrl: bool) -> Option<CellPosition> {
+        if self.rows == 0 || self.columns.is_empty() {
+            return None;
+        }
+
+        let Some(current) = self.selection else {
+            return Some(CellPosition { row: 0, column: 0 });
+        };
+        let (last_row, last_column) = (self.rows - 1, self.columns.len() - 1);
+        let (back, forth) = (
+            current.column.saturating_sub(1),
+            (current.column + 1).min(last_column),
+        );
+        let CellPosition {
+            mut row,
+            mut column,
+        } = current;
+
+        match key {
+            VirtualKeyCode::Up => row = row.saturating_sub(1),
+            VirtualKeyCode::Down => row = (row + 1).min(last_row),
+            VirtualKeyCode::Left if self.direction.is_rtl() => column = forth,
+            VirtualKeyCode::Left => column = back,
+            VirtualKeyCode::Right if self.direction.is_rtl() => column = back,
+            VirtualKeyCode::Right => column = forth,
+            VirtualKeyCode::PageUp => row = row.saturating_sub(self.page_rows),
+            VirtualKeyCode::PageDown => row = (row + self.page_rows).min(last_row),
+            VirtualKeyCode::Home if ctrl => row = 0,
+            VirtualKeyCode::Home => column = 0,
+            VirtualKeyCode::End if ctrl => row = last_row,
+            VirtualKeyCode::End => column = last_column,
+            _ => return None,
+        }
+
+        let moved = CellPosition { row, column };
+        (moved != current).then_some(moved)
+    }
+
+    fn hit(&self, position: Point) -> Option<Hit> {
+        let (start, end) = self.region;
+        let (x, y) = (position.0 .0, position.1 .0);
+        if x < start.0 .0 || x >= end.0 .0 || y < start.1 .0 || y >= end.1 .0 {
+            return None;
+        }
+
+        let column_at = || {
+            (0..self.columns.len()).find(|&column| {
+                let (left, right) = self.column_span(column);
+                left.0 <= x && x < right.0
+            })
+        };
+
+        let body_top = start.1 .0 + self.header_height();
+        if y < body_top {
+            let resize = (0..self.columns.len()).rev().find(|&column| {
+                let (left, right) = self.column_span(column);
+                let edge = if self.direction.is_rtl() { left } else { right };
+                self.columns[column].resizable && (edge.0 - x).abs() <= RESIZE_SLOP
+            });
+            return match resize {
+                Some(column) => Some(Hit::Resize(column)),
+                None => column_at().map(Hit::Header),
+            };
+        }
+
+        let row = ((y - body_top + self.scroll.1) / self.row_height()) as usize;
+        if row >= self.rows {
+            return None;
+        }
+        column_at().map(|column| Hit::Cell(CellPosition { row, column }))
+    }
+
+    /// Scroll the least to put `cell` in a view of `width` and
+    /// `body_height`.
+    fn reveal_cell(&mut self, cell: CellPosition, width: f32, body_height: f32) {
+        let row_height = self.row_height();
+        let top = cell.row as f32 * row_height;
+        self.scroll.1 = reveal(self.scroll.1, top..top + row_height, body_height);
+
+        if let Some(&column_width) = self.widths.get(cell.column) {
+            let left = self.widths[..cell.column].iter().sum::<f32>();
+            self.scroll.0 = reveal(self.scroll.0, left..left + column_width, width);
+        }
+    }
+
+    /// Left and right of `column` in the view, mirrored in RTL.
+    fn column_span(&self, column: usize) -> (Pixel, Pixel) {
+        let (start, end) = self.region;
+        let offset = self.widths[..column].iter().sum::<f32>() - self.scroll.0;
+        let width = self.widths[column];
+
+        if self.direction.is_rtl() {
+            (end.0 - Pixel(offset + width), end.0 - Pixel(offset))
+        } else {
+            (start.0 + Pixel(offset), start.0 + Pixel(offset + width))
+        }
+    }
+
+    fn cell_region(&self, cell: CellPosition) -> Region {
+        let (left, right) = self.column_span(cell.column);
+        let row_height = self.row_height();
+        let top = self.region.0 .1 .0 + self.header_height() + cell.row as f32 * row_height
+            - self.scroll.1;
+        (
+            Point(left, Pixel(top)),
+            Point(right, Pixel(top + row_height)),
+        )
+    }
+
+    fn header_region(&self, column: usize) -> Region {
+        let (left, right) = self.column_span(column);
+        let top = self.region.0 .1;
+        (
+            Point(left, top),
+            Point(right, top + Pixel(self.header_height())),
+        )
+    }
+
+    fn cell_props(&self, cell: CellPosition, clip: Region) -> GridCellProps {
+        let selected = self.selection == Some(cell);
+        let content = self.cell.clone();
+        let context = CellContext {
+            row: cell.row,
+            column: cell.column,
+            selected,
+        };
+        let accent = self.styles.accent.0;
+
+        GridCellProps {
+            content: Some(Arc::new(move || content(context))),
+            background: selected.then(|| accent.with_a(0x33)),
+            line: Some(self.styles.line_color.0),
+            outline: (selected && self.focused).then_some(accent),
+            sort: None,
+            padding: self.styles.cell_padding.0,
+            clip,
+        }
+    }
+
+    fn header_props(&self, column: usize, clip: Region) -> GridCellProps {
+        let content: ContentFn = match &self.header {
+            Some(header) => {
+                let header = header.clone();
+                Arc::new(move || header(column))
+            }
+            None => {
+                let title = self.columns[column].title.clone();
+                Arc::new(move || {
+                    Boxed::new(Once(add_one::<TextBox, _, _, _, _>(
+                        TextBoxProps::default().text(title.as_str()),
+                        (),
+                        (),
+                        |_: &_| {},
+                    )))
+                })
+            }
+        };
+
+        GridCellProps {
+            content: Some(content),
+            background: Some(self.styles.header_color.0),
+            line: Some(self.styles.line_color.0),
+            outline: None,
+            sort: self
+                .sort
+                .filter(|(sorted, _)| *sorted == column)
+                .map(|(_, order)| order),
+            padding: self.styles.cell_padding.0,
+            clip,
+        }
+    }
+
+    fn row_height(&self) -> f32 {
+        self.styles.row_height.0 .0.max(1.0)
+    }
+
+    fn header_height(&self) -> f32 {
+        self.styles.header_height.0 .0.max(0.0)
+    }
+}
+
+/// Cells of `rows` in order.
+fn cells(rows: Range<usize>, columns: usize) -> impl Iterator<Item = CellPosition> {
+    rows.flat_map(move |row| (0..columns).map(move |column| CellPosition { row, column }))
+}
+
+/// Offset scrolled the least from `offset` to put `span` in a view of
+/// `size`, the start of `span` is preferred if it is larger than the
+/// view.
+fn reveal(offset: f32, span: Range<f32>, size: f32) -> f32 {
+    if span.start < offset || span.end - span.start > size {
+        span.start
+    } else if span.end > offset + size {
+        span.end - size
+    } else {
+        offset
+    }
+}
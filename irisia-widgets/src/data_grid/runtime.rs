@@ -0,0 +1,175 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Weak,
+};
+
+use irisia::{
+    application::GlobalContent,
+    element::ElementHandle,
+    event::{
+        standard::{
+            Blured, ElementAbandoned, Focused, PointerDown, PointerEntered, PointerMove,
+            PointerOut, PointerUp,
+        },
+        EventDispatcher,
+    },
+    primitive::Pixel,
+    winit::event::{ElementState, KeyboardInput, ModifiersState, MouseScrollDelta},
+    StaticWindowEvent,
+};
+
+use super::{column::next_sort, ColumnResized, DataGrid, Hit, SelectionChanged, SortChanged};
+
+/// Distance scrolled by a line of the mouse wheel.
+const LINE_HEIGHT: Pixel = Pixel(40.0);
+
+pub(super) async fn run(
+    eh: Weak<ElementHandle<DataGrid>>,
+    ed: EventDispatcher,
+    global: Arc<GlobalContent>,
+) {
+    let win_ed = global.global_event_dispatcher();
+    let focused = AtomicBool::new(false);
+    let hovered = AtomicBool::new(false);
+
+    let pointer = async {
+        loop {
+            let PointerDown { position, .. } = ed.recv_sys::<PointerDown>().await;
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+            eh.focus();
+
+            let hit = eh.el_read().await.hit(position);
+            match hit {
+                Some(Hit::Resize(column)) => {
+                    let (start, rtl) = {
+                        let el = eh.el_read().await;
+                        (el.widths[column], el.direction.is_rtl())
+                    };
+
+                    // keep resizing when the pointer moves out of the grid
+                    eh.capture_pointer();
+                    loop {
+                        let current = tokio::select! {
+                            PointerMove { position, .. } = ed.recv_sys::<PointerMove>() => position,
+                            _ = ed.recv_sys::<PointerUp>() => break,
+                            _ = win_ed.recv_sys::<PointerUp>() => break,
+                        };
+
+                        let delta = (current.0 - position.0).0;
+                        let delta = if rtl { -delta } else { delta };
+                        eh.el_write().await.resize_column(column, start + delta);
+                    }
+                    eh.release_pointer();
+
+                    let width = eh.el_read().await.widths[column];
+                    if width != start {
+                        eh.event_dispatcher().emit(ColumnResized {
+                            column,
+                            width: Pixel(width),
+                        });
+                    }
+                }
+                Some(Hit::Header(column)) => {
+                    let PointerUp { position, .. } = win_ed.recv_sys::<PointerUp>().await;
+
+                    // only sort when released over the same header
+                    let sort = {
+                        let el = eh.el_read().await;
+                        (el.columns[column].sortable && el.hit(position) == hit)
+                            .then(|| next_sort(el.sort, column))
+                    };
+                    if let Some(sort) = sort {
+                        eh.event_dispatcher().emit(SortChanged(sort));
+                    }
+                }
+                Some(Hit::Cell(cell)) => {
+                    let changed = eh.el_write().await.select(Some(cell));
+                    if changed {
+                        eh.event_dispatcher().emit(SelectionChanged(Some(cell)));
+                    }
+                }
+                None => {}
+            }
+        }
+    };
+
+    let hover = async {
+        loop {
+            let value = tokio::select! {
+                _ = ed.recv_sys::<PointerEntered>() => true,
+                _ = ed.recv_sys::<PointerOut>() => false,
+            };
+            hovered.store(value, Ordering::Relaxed);
+        }
+    };
+
+    let focus = async {
+        loop {
+            let value = tokio::select! {
+                _ = ed.recv_sys::<Focused>() => true,
+                _ = ed.recv_sys::<Blured>() => false,
+            };
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            eh.el_write().await.set_focused(value);
+            focused.store(value, Ordering::Relaxed);
+        }
+    };
+
+    let window = async {
+        let mut lock = win_ed.lock();
+        let mut modifiers = ModifiersState::empty();
+        loop {
+            match lock.recv_sys::<StaticWindowEvent>().await {
+                StaticWindowEvent::ModifiersChanged(state) => modifiers = state,
+                StaticWindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode: Some(key),
+                            ..
+                        },
+                    ..
+                } if focused.load(Ordering::Relaxed) => {
+                    let Some(eh) = eh.upgrade() else {
+                        return;
+                    };
+
+                    let mut el = eh.el_write().await;
+                    let Some(cell) = el.navigate(key, modifiers.ctrl()) else {
+                        continue;
+                    };
+                    el.select(Some(cell));
+                    drop(el);
+                    eh.event_dispatcher().emit(SelectionChanged(Some(cell)));
+                }
+                StaticWindowEvent::MouseWheel { delta, .. } if hovered.load(Ordering::Relaxed) => {
+                    let Some(eh) = eh.upgrade() else {
+                        return;
+                    };
+
+                    // positive deltas scroll up and left
+                    let (x, y) = match delta {
+                        MouseScrollDelta::LineDelta(x, y) => (LINE_HEIGHT * -x, LINE_HEIGHT * -y),
+                        MouseScrollDelta::PixelDelta(position) => (
+                            -Pixel::from_physical(position.x as f32),
+                            -Pixel::from_physical(position.y as f32),
+                        ),
+                    };
+                    let (x, y) = if modifiers.shift() { (y, x) } else { (x, y) };
+                    eh.el_write().await.scroll_by(x, y);
+                }
+                _ => {}
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = ed.recv_sys::<ElementAbandoned>() => {}
+        _ = async { tokio::join!(pointer, hover, focus, window) } => {}
+    }
+}
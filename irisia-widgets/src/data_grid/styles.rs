@@ -0,0 +1,40 @@
+use irisia::{primitive::Pixel, skia_safe::Color, Style, StyleReader};
+
+use crate::toggle::StyleAccentColor;
+
+/// Height of rows of a data grid.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleRowHeight(#[style(default = "Pixel(32.0)")] pub Pixel);
+
+/// Height of the header of a data grid.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleHeaderHeight(#[style(default = "Pixel(36.0)")] pub Pixel);
+
+/// Horizontal space between the edges of a cell and its content.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCellPadding(#[style(default = "Pixel(8.0)")] pub Pixel);
+
+/// Background of the header of a data grid.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleHeaderColor(#[style(default = "Color::from_rgb(0xf5, 0xf5, 0xf5)")] pub Color);
+
+/// Color of lines between cells.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleGridLineColor(#[style(default = "Color::from_rgb(0xe0, 0xe0, 0xe0)")] pub Color);
+
+/// Styles of a data grid, the selected cell is drawn with
+/// [`StyleAccentColor`].
+#[derive(StyleReader, Clone, PartialEq)]
+pub(super) struct GridStyles {
+    pub row_height: StyleRowHeight,
+    pub header_height: StyleHeaderHeight,
+    pub cell_padding: StyleCellPadding,
+    pub header_color: StyleHeaderColor,
+    pub line_color: StyleGridLineColor,
+    pub accent: StyleAccentColor,
+}
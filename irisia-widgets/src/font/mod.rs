@@ -0,0 +1,173 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use irisia::{
+    anyhow::{anyhow, Context},
+    skia_safe::{
+        textlayout::{FontCollection, TypefaceFontProvider},
+        FontMgr,
+    },
+    Result,
+};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref REGISTRY: Mutex<RegistryInner> = Mutex::new(RegistryInner {
+        fonts: Vec::new(),
+        fallbacks: HashMap::new(),
+        default_families: Vec::new(),
+        generation: 0,
+    });
+    static ref COLLECTION: Mutex<Option<(u64, SharedCollection)>> = Mutex::new(None);
+}
+
+// skia font collections are not thread safe, so the collection is only
+// used while `COLLECTION` is locked
+struct SharedCollection(FontCollection);
+
+unsafe impl Send for SharedCollection {}
+
+struct RegistryInner {
+    fonts: Vec<(Option<String>, Arc<[u8]>)>,
+    fallbacks: HashMap<String, Vec<String>>,
+    default_families: Vec<String>,
+    generation: u64,
+}
+
+/// App-wide registry of fonts used by text elements.
+///
+/// Fonts registered here are found before the fonts installed in the
+/// system. A family can declare fallback families, which are tried in
+/// order for characters it has no glyph for, then the default families
+/// are tried, and at last whatever the system picks.
+///
+/// Register fonts before creating text elements, text laid out already
+/// is not laid out again when the registry changes.
+pub struct FontRegistry;
+
+impl FontRegistry {
+    /// Register a font file, returns its family name. With `alias`,
+    /// the font is registered under the alias instead of the family name
+    /// in the file.
+    pub fn register_file(path: impl AsRef<Path>, alias: Option<&str>) -> Result<String> {
+        let path = path.as_ref();
+        let data = std::fs::read(path)
+            .with_context(|| format!("cannot read font file `{}`", path.display()))?;
+        Self::register_bytes(data, alias)
+            .with_context(|| format!("cannot register font file `{}`", path.display()))
+    }
+
+    /// Register a font in bytes, like [`register_file`](Self::register_file).
+    pub fn register_bytes(data: impl Into<Arc<[u8]>>, alias: Option<&str>) -> Result<String> {
+        let data = data.into();
+        let typeface = FontMgr::new()
+            .new_from_data(&data, None)
+            .ok_or_else(|| anyhow!("unsupported font data"))?;
+
+        let family = match alias {
+            Some(alias) => alias.to_owned(),
+            None => typeface.family_name(),
+        };
+
+        let mut inner = REGISTRY.lock().unwrap();
+        inner.fonts.push((alias.map(ToOwned::to_owned), data));
+        inner.generation += 1;
+        Ok(family)
+    }
+
+    /// Families tried in order when `family` has no glyph for a character,
+    /// e.g. a CJK family then an emoji family after a Latin family.
+    /// Fallback families can declare their own fallbacks.
+    pub fn set_fallbacks(family: &str, fallbacks: &[&str]) {
+        let mut inner = REGISTRY.lock().unwrap();
+        inner.fallbacks.insert(
+            family.to_owned(),
+            fallbacks.iter().map(|&f| f.to_owned()).collect(),
+        );
+        inner.generation += 1;
+    }
+
+    /// Families used by text without a font family, and as the last
+    /// fallbacks of every family.
+    pub fn set_default_families(families: &[&str]) {
+        let mut inner = REGISTRY.lock().unwrap();
+        inner.default_families = families.iter().map(|&f| f.to_owned()).collect();
+        inner.generation += 1;
+    }
+
+    /// The whole chain of families tried for text in `family`, or in
+    /// the default families if it is `None`.
+    pub fn resolve(family: Option<&str>) -> Vec<String> {
+        let inner = REGISTRY.lock().unwrap();
+        let mut visited = HashSet::new();
+        let mut chain = Vec::new();
+
+        if let Some(family) = family {
+            inner.push_chain(family, &mut visited, &mut chain);
+        }
+        for family in &inner.default_families {
+            inner.push_chain(family, &mut visited, &mut chain);
+        }
+        chain
+    }
+
+    /// Call `f` with the font collection shared by all text elements,
+    /// which is built again after the registry changes. Skia font
+    /// collections are not thread safe, so paragraphs must be built and
+    /// laid out inside `f`, and other calls wait until `f` returns.
+    pub(crate) fn with_font_collection<R>(f: impl FnOnce(&FontCollection) -> R) -> R {
+        let mut collection = COLLECTION.lock().unwrap();
+
+        {
+            let inner = REGISTRY.lock().unwrap();
+            if !matches!(&*collection, Some((generation, _)) if *generation == inner.generation) {
+                *collection = Some((inner.generation, SharedCollection(inner.build_collection())));
+            }
+        }
+
+        let (_, SharedCollection(fc)) = collection.as_ref().unwrap();
+        f(fc)
+    }
+}
+
+impl RegistryInner {
+    fn push_chain<'a>(
+        &'a self,
+        family: &'a str,
+        visited: &mut HashSet<&'a str>,
+        chain: &mut Vec<String>,
+    ) {
+        if !visited.insert(family) {
+            return;
+        }
+
+        chain.push(family.to_owned());
+        for fallback in self.fallbacks.get(family).into_iter().flatten() {
+            self.push_chain(fallback, visited, chain);
+        }
+    }
+
+    fn build_collection(&self) -> FontCollection {
+        let font_mgr = FontMgr::new();
+        let mut provider = TypefaceFontProvider::new();
+        for (alias, data) in &self.fonts {
+            if let Some(typeface) = font_mgr.new_from_data(data, None) {
+                provider.register_typeface(typeface, alias.as_deref());
+            }
+        }
+
+        let mut fc = FontCollection::new();
+        fc.set_asset_font_manager(Some(provider.into()));
+        if self.default_families.is_empty() {
+            fc.set_default_font_manager(font_mgr, None);
+        } else {
+            fc.set_default_font_manager_and_family_names(font_mgr, &self.default_families);
+        }
+        fc.enable_font_fallback();
+        fc.paragraph_cache_mut().turn_on(true);
+        fc
+    }
+}
@@ -0,0 +1,428 @@
+use std::{
+    collections::{
+        hash_map::{DefaultHasher, Entry},
+        BTreeMap, HashMap, HashSet,
+    },
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use irisia::{
+    anyhow::{anyhow, Context},
+    skia_safe::{image::CachingHint, Data, Image},
+    Result,
+};
+use lazy_static::lazy_static;
+
+const DEFAULT_MEMORY_LIMIT: usize = 256 * 1024 * 1024;
+
+lazy_static! {
+    static ref CACHE: Mutex<CacheInner> = Mutex::new(CacheInner {
+        images: Lru::new(DEFAULT_MEMORY_LIMIT),
+        pending: HashMap::new(),
+        failed: HashSet::new(),
+    });
+}
+
+/// Where an image is loaded from. Sources equal to each other share
+/// the same decoded image in [`ImageCache`].
+#[derive(Clone)]
+pub enum ImageSource {
+    Path(PathBuf),
+    Bytes { data: Arc<[u8]>, hash: u64 },
+}
+
+impl ImageSource {
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        ImageSource::Path(path.into())
+    }
+
+    /// Encoded PNG, JPEG or WebP data. The data is hashed once here,
+    /// so keep the source rather than creating it again on every update.
+    pub fn bytes(data: impl Into<Arc<[u8]>>) -> Self {
+        let data = data.into();
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+
+        ImageSource::Bytes {
+            data,
+            hash: hasher.finish(),
+        }
+    }
+}
+
+impl PartialEq for ImageSource {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Path(a), Self::Path(b)) => a == b,
+            (Self::Bytes { data: a, hash: ha }, Self::Bytes { data: b, hash: hb }) => {
+                ha == hb && (Arc::ptr_eq(a, b) || a == b)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ImageSource {}
+
+impl Hash for ImageSource {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Path(path) => path.hash(state),
+            Self::Bytes { hash, .. } => hash.hash(state),
+        }
+    }
+}
+
+impl From<&str> for ImageSource {
+    fn from(value: &str) -> Self {
+        ImageSource::path(value)
+    }
+}
+
+impl From<&Path> for ImageSource {
+    fn from(value: &Path) -> Self {
+        ImageSource::path(value)
+    }
+}
+
+impl From<PathBuf> for ImageSource {
+    fn from(value: PathBuf) -> Self {
+        ImageSource::path(value)
+    }
+}
+
+impl From<&'static [u8]> for ImageSource {
+    fn from(value: &'static [u8]) -> Self {
+        ImageSource::bytes(value)
+    }
+}
+
+impl From<Vec<u8>> for ImageSource {
+    fn from(value: Vec<u8>) -> Self {
+        ImageSource::bytes(value)
+    }
+}
+
+/// A decoded raster image, cheap to clone.
+#[derive(Clone)]
+pub struct DecodedImage(Image);
+
+impl DecodedImage {
+    pub fn width(&self) -> u32 {
+        self.0.width() as _
+    }
+
+    pub fn height(&self) -> u32 {
+        self.0.height() as _
+    }
+
+    /// Bytes of memory taken by the pixels.
+    pub fn byte_size(&self) -> usize {
+        self.0.image_info().compute_min_byte_size()
+    }
+
+    pub fn image(&self) -> &Image {
+        &self.0
+    }
+}
+
+/// The decoded-image cache shared by all `Image` elements. The least
+/// recently used images are dropped once the memory limit is exceeded.
+pub struct ImageCache;
+
+type LoadResult = std::result::Result<DecodedImage, Arc<irisia::anyhow::Error>>;
+
+struct CacheInner {
+    images: Lru<ImageSource, DecodedImage>,
+
+    // callbacks waiting for sources being decoded
+    pending: HashMap<ImageSource, Vec<Box<dyn FnOnce(&LoadResult) + Send>>>,
+
+    // sources failed to load, which are not preloaded again
+    failed: HashSet<ImageSource>,
+}
+
+impl ImageCache {
+    /// Set the limit of memory taken by cached pixels, in bytes.
+    /// Images larger than the limit are never cached.
+    pub fn set_memory_limit(bytes: usize) {
+        CACHE.lock().unwrap().images.set_limit(bytes);
+    }
+
+    pub fn memory_limit() -> usize {
+        CACHE.lock().unwrap().images.limit()
+    }
+
+    pub fn memory_usage() -> usize {
+        CACHE.lock().unwrap().images.cost()
+    }
+
+    /// Remove `source` from the cache, it will be preloaded again even
+    /// if it failed to load.
+    pub fn remove(source: &ImageSource) {
+        let mut cache = CACHE.lock().unwrap();
+        cache.images.remove(source);
+        cache.failed.remove(source);
+    }
+
+    pub fn clear() {
+        let mut cache = CACHE.lock().unwrap();
+        cache.images.clear();
+        cache.failed.clear();
+    }
+
+    pub fn get(source: &ImageSource) -> Option<DecodedImage> {
+        CACHE.lock().unwrap().images.get(source).cloned()
+    }
+
+    /// Get the decoded image of `source`, decoding it on the blocking
+    /// thread pool of tokio if not cached.
+    pub async fn load(source: ImageSource) -> Result<DecodedImage> {
+        if let Some(image) = Self::get(&source) {
+            return Ok(image);
+        }
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        Self::decode_shared(source, move |result| {
+            let _ = tx.send(result.clone());
+        });
+        rx.await?.map_err(|err| anyhow!("{err:#}"))
+    }
+
+    /// Start decoding `source` in background, and call `on_ready` with the
+    /// image once it is decoded, even if it is too large to be cached.
+    /// Does nothing if `source` is cached or failed to load, calls of the
+    /// same source share a decoding.
+    pub fn preload(source: ImageSource, on_ready: impl FnOnce(DecodedImage) + Send + 'static) {
+        {
+            let mut cache = CACHE.lock().unwrap();
+            if cache.images.contains_key(&source) || cache.failed.contains(&source) {
+                return;
+            }
+        }
+
+        Self::decode_shared(source, |result| match result {
+            Ok(image) => on_ready(image.clone()),
+            Err(err) => {
+                irisia::warning!(target: irisia::log::RENDER, "cannot load image: {err:#}")
+            }
+        });
+    }
+
+    /// Decode `source` and call `waiter` with the result, sharing the
+    /// decoding with other calls of the same source in progress.
+    fn decode_shared(source: ImageSource, waiter: impl FnOnce(&LoadResult) + Send + 'static) {
+        match CACHE.lock().unwrap().pending.entry(source.clone()) {
+            Entry::Occupied(mut waiting) => {
+                waiting.get_mut().push(Box::new(waiter));
+                return;
+            }
+            Entry::Vacant(vacant) => {
+                vacant.insert(vec![Box::new(waiter)]);
+            }
+        }
+
+        tokio::spawn(async move {
+            let result = {
+                let source = source.clone();
+                match tokio::task::spawn_blocking(move || decode(&source)).await {
+                    Ok(result) => result.map_err(Arc::new),
+                    Err(err) => Err(Arc::new(err.into())),
+                }
+            };
+
+            let waiting = {
+                let mut cache = CACHE.lock().unwrap();
+                match &result {
+                    Ok(image) => {
+                        cache
+                            .images
+                            .insert(source.clone(), image.clone(), image.byte_size());
+                        cache.failed.remove(&source);
+                    }
+                    Err(_) => {
+                        cache.failed.insert(source.clone());
+                    }
+                }
+                cache.pending.remove(&source)
+            };
+
+            for waiter in waiting.into_iter().flatten() {
+                waiter(&result);
+            }
+        });
+    }
+}
+
+/// Values with a cost, the least recently used ones are dropped once the
+/// total cost exceeds the limit.
+pub(crate) struct Lru<K, V> {
+    entries: HashMap<K, LruEntry<V>>,
+    // keys by the clock of their last use, the oldest first
+    order: BTreeMap<u64, K>,
+    clock: u64,
+    cost: usize,
+    limit: usize,
+}
+
+struct LruEntry<V> {
+    value: V,
+    cost: usize,
+    last_used: u64,
+}
+
+impl<K, V> Lru<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new(limit: usize) -> Self {
+        Lru {
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            clock: 0,
+            cost: 0,
+            limit,
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn cost(&self) -> usize {
+        self.cost
+    }
+
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.evict();
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Get the value of `key` and mark it as the most recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+
+        self.order.remove(&entry.last_used);
+        self.order.insert(self.clock, key.clone());
+        entry.last_used = self.clock;
+        Some(&entry.value)
+    }
+
+    /// Values costing more than the limit are never stored, returns
+    /// whether `value` is stored.
+    pub fn insert(&mut self, key: K, value: V, cost: usize) -> bool {
+        if cost > self.limit {
+            return false;
+        }
+
+        self.remove(&key);
+        self.clock += 1;
+        self.order.insert(self.clock, key.clone());
+        self.entries.insert(
+            key,
+            LruEntry {
+                value,
+                cost,
+                last_used: self.clock,
+            },
+        );
+        self.cost += cost;
+        self.evict();
+        true
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let entry = self.entries.remove(key)?;
+        self.order.remove(&entry.last_used);
+        self.cost -= entry.cost;
+        Some(entry.value)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.cost = 0;
+    }
+
+    fn evict(&mut self) {
+        while self.cost > self.limit {
+            let Some((_, key)) = self.order.pop_first() else {
+                break;
+            };
+
+            if let Some(entry) = self.entries.remove(&key) {
+                self.cost -= entry.cost;
+            }
+        }
+    }
+}
+
+fn decode(source: &ImageSource) -> Result<DecodedImage> {
+    let data = match source {
+        ImageSource::Path(path) => {
+            let bytes = std::fs::read(path)
+                .with_context(|| format!("cannot read image `{}`", path.display()))?;
+            Data::new_copy(&bytes)
+        }
+        ImageSource::Bytes { data, .. } => Data::new_copy(data),
+    };
+
+    Image::from_encoded(data)
+        .and_then(|image| image.to_raster_image(CachingHint::Disallow))
+        .map(DecodedImage)
+        .ok_or_else(|| anyhow!("unsupported or corrupted image data"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lru;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut lru = Lru::new(3);
+        lru.insert("a", 1, 1);
+        lru.insert("b", 2, 1);
+        lru.insert("c", 3, 1);
+
+        // `a` becomes the most recently used, so `b` goes first
+        assert_eq!(lru.get(&"a"), Some(&1));
+        lru.insert("d", 4, 1);
+        assert!(!lru.contains_key(&"b"));
+        assert!(lru.contains_key(&"a"));
+        assert_eq!(lru.cost(), 3);
+
+        lru.set_limit(1);
+        assert!(!lru.contains_key(&"c"));
+        assert!(!lru.contains_key(&"a"));
+        assert_eq!(lru.get(&"d"), Some(&4));
+        assert_eq!(lru.cost(), 1);
+    }
+
+    #[test]
+    fn never_stores_values_over_limit() {
+        let mut lru = Lru::new(3);
+        lru.insert("a", 1, 2);
+        assert!(!lru.insert("big", 2, 4));
+        assert!(lru.contains_key(&"a"));
+        assert_eq!(lru.cost(), 2);
+    }
+
+    #[test]
+    fn insert_replaces_cost() {
+        let mut lru = Lru::new(3);
+        lru.insert("a", 1, 2);
+        lru.insert("b", 2, 1);
+        lru.insert("a", 3, 1);
+        assert_eq!(lru.cost(), 2);
+
+        assert_eq!(lru.remove(&"a"), Some(3));
+        assert_eq!(lru.cost(), 1);
+    }
+}
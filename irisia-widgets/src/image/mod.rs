@@ -0,0 +1,251 @@
+use std::{
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use irisia::{
+    element::{
+        ChildrenNodes, ChildrenSetter, Element, ElementHandle, RenderElement, UpdateElement,
+    },
+    primitive::Region,
+    skia_safe::{Canvas, FilterMode, MipmapMode, Paint, Rect, SamplingOptions},
+    style::StyleContainer,
+    Result, StyleReader, UpdateWith,
+};
+use tokio::task::JoinHandle;
+
+use crate::box_styles::BoxStyles;
+
+pub use self::{
+    cache::{DecodedImage, ImageCache, ImageSource},
+    styles::*,
+};
+
+pub(crate) use self::cache::Lru;
+
+mod cache;
+pub mod styles;
+
+/// Shows a PNG, JPEG or WebP image. The image is decoded asynchronously,
+/// and the placeholder is drawn until it is ready.
+pub struct Image {
+    handle: Weak<ElementHandle<Self>>,
+    source: Option<ImageSource>,
+    state: LoadState,
+    styles: ImageStyles,
+}
+
+enum LoadState {
+    Empty,
+    Loading(JoinHandle<()>),
+    Ready(DecodedImage),
+    Failed,
+}
+
+#[derive(StyleReader, Clone)]
+struct ImageStyles {
+    box_styles: BoxStyles,
+    fit: StyleImageFit,
+    align: StyleImageAlign,
+    placeholder: StyleImagePlaceholder,
+}
+
+#[derive(Default)]
+pub struct ImageProps {
+    pub source: Option<ImageSource>,
+}
+
+impl ImageProps {
+    pub fn source(mut self, source: impl Into<ImageSource>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+}
+
+impl Element for Image {
+    type BlankProps = ImageProps;
+
+    fn layout<'a, Ch>(&mut self, _: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        setter.set_children(());
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        let canvas = renderer.canvas();
+        let box_styles = &self.styles.box_styles;
+        let content_region = box_styles.draw_border_limited(canvas, draw_region);
+        let dst = region_to_rect(content_region);
+        if dst.is_empty() {
+            return Ok(());
+        }
+
+        canvas.save();
+        box_styles.clip_content(canvas, content_region);
+
+        match &self.state {
+            LoadState::Ready(image) => {
+                let rect = fit_rect(image, dst, self.styles.fit.0, self.styles.align);
+                canvas.draw_image_rect_with_sampling_options(
+                    image.image(),
+                    None,
+                    rect,
+                    SamplingOptions::new(FilterMode::Linear, MipmapMode::None),
+                    &Paint::default(),
+                );
+            }
+            LoadState::Loading(_) | LoadState::Failed => {
+                draw_placeholder(canvas, dst, &self.styles.placeholder);
+            }
+            LoadState::Empty => {}
+        }
+
+        canvas.restore();
+        renderer.set_interact_region(draw_region);
+        Ok(())
+    }
+}
+
+impl<'a, Sty> UpdateWith<UpdateElement<'a, Self, ImageProps, Sty>> for Image
+where
+    Sty: StyleContainer,
+{
+    fn create_with(updater: UpdateElement<'a, Self, ImageProps, Sty>) -> Self {
+        let mut image = Image {
+            handle: Arc::downgrade(updater.handle),
+            source: None,
+            state: LoadState::Empty,
+            styles: read_styles(updater.styles, None, updater.handle),
+        };
+
+        image.set_source(updater.props.source);
+        image
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, ImageProps, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        self.styles = read_styles(
+            updater.styles,
+            Some(&self.styles.box_styles),
+            updater.handle,
+        );
+        self.set_source(updater.props.source);
+
+        // box styles cannot be compared
+        false
+    }
+}
+
+impl Image {
+    fn set_source(&mut self, source: Option<ImageSource>) {
+        if self.source == source {
+            return;
+        }
+
+        if let LoadState::Loading(task) = &self.state {
+            task.abort();
+        }
+
+        self.state = match &source {
+            None => LoadState::Empty,
+            Some(source) => match ImageCache::get(source) {
+                Some(image) => LoadState::Ready(image),
+                None => LoadState::Loading(self.spawn_load(source.clone())),
+            },
+        };
+        self.source = source;
+    }
+
+    fn spawn_load(&self, source: ImageSource) -> JoinHandle<()> {
+        let handle = self.handle.clone();
+
+        tokio::spawn(async move {
+            let result = ImageCache::load(source.clone()).await;
+
+            let handle = match handle.upgrade() {
+                Some(h) => h,
+                None => return,
+            };
+
+            let mut this = handle.el_write().await;
+            if this.source.as_ref() != Some(&source) {
+                return;
+            }
+
+            this.state = match result {
+                Ok(image) => LoadState::Ready(image),
+                Err(err) => {
+                    irisia::warning!(target: irisia::log::RENDER, "cannot load image: {err:#}");
+                    LoadState::Failed
+                }
+            };
+        })
+    }
+}
+
+impl Drop for Image {
+    fn drop(&mut self) {
+        if let LoadState::Loading(task) = &self.state {
+            task.abort();
+        }
+    }
+}
+
+fn region_to_rect((start, end): Region) -> Rect {
+    Rect::new(
+        start.0.to_physical(),
+        start.1.to_physical(),
+        end.0.to_physical(),
+        end.1.to_physical(),
+    )
+}
+
+fn fit_rect(image: &DecodedImage, dst: Rect, fit: ImageFit, align: StyleImageAlign) -> Rect {
+    let (width, height) = (image.width() as f32, image.height() as f32);
+    if width == 0.0 || height == 0.0 {
+        return Rect::new_empty();
+    }
+
+    let contain = (dst.width() / width).min(dst.height() / height);
+    let (scale_x, scale_y) = match fit {
+        ImageFit::Fill => (dst.width() / width, dst.height() / height),
+        ImageFit::Contain => (contain, contain),
+        ImageFit::Cover => {
+            let cover = (dst.width() / width).max(dst.height() / height);
+            (cover, cover)
+        }
+        ImageFit::None => (1.0, 1.0),
+        ImageFit::ScaleDown => (contain.min(1.0), contain.min(1.0)),
+    };
+
+    let (width, height) = (width * scale_x, height * scale_y);
+    let left = dst.left + (dst.width() - width) * align.x;
+    let top = dst.top + (dst.height() - height) * align.y;
+    Rect::from_xywh(left, top, width, height)
+}
+
+fn draw_placeholder(canvas: &mut Canvas, dst: Rect, placeholder: &StyleImagePlaceholder) {
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true).set_color(placeholder.0);
+    canvas.draw_rect(dst, &paint);
+}
+
+fn read_styles(
+    styles: &impl StyleContainer,
+    previous: Option<&BoxStyles>,
+    handle: &Arc<ElementHandle<Image>>,
+) -> ImageStyles {
+    let mut styles = ImageStyles::read_style(styles);
+    styles.box_styles.apply_direction(handle.direction());
+    styles.box_styles.preload_images(previous, handle);
+    styles
+}
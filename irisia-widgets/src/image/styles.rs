@@ -0,0 +1,88 @@
+use irisia::{skia_safe::Color, Style};
+
+/// How an image is resized to fit the content region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFit {
+    /// Stretch to fill the region, ignoring the aspect ratio.
+    Fill,
+
+    /// Scale to fit inside the region, keeping the aspect ratio.
+    Contain,
+
+    /// Scale to cover the whole region, keeping the aspect ratio.
+    /// The overflowing part is clipped.
+    Cover,
+
+    /// Keep the natural size.
+    None,
+
+    /// Like `Contain`, but never scale the image up.
+    ScaleDown,
+}
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from = "", impl_default)]
+pub struct StyleImageFit(#[style(default = "ImageFit::Contain")] pub ImageFit);
+
+impl StyleImageFit {
+    pub fn fill(&mut self) {
+        self.0 = ImageFit::Fill;
+    }
+
+    pub fn contain(&mut self) {
+        self.0 = ImageFit::Contain;
+    }
+
+    pub fn cover(&mut self) {
+        self.0 = ImageFit::Cover;
+    }
+
+    pub fn none(&mut self) {
+        self.0 = ImageFit::None;
+    }
+
+    pub fn scale_down(&mut self) {
+        self.0 = ImageFit::ScaleDown;
+    }
+}
+
+/// Alignment of the image in the content region, `0.0` for left or top
+/// and `1.0` for right or bottom.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, from = "", impl_default)]
+pub struct StyleImageAlign {
+    #[style(option, default = "0.5")]
+    pub x: f32,
+
+    #[style(option, default = "0.5")]
+    pub y: f32,
+}
+
+impl StyleImageAlign {
+    pub fn left(&mut self) {
+        self.x = 0.0;
+    }
+
+    pub fn right(&mut self) {
+        self.x = 1.0;
+    }
+
+    pub fn top(&mut self) {
+        self.y = 0.0;
+    }
+
+    pub fn bottom(&mut self) {
+        self.y = 1.0;
+    }
+
+    pub fn center(&mut self) {
+        self.x = 0.5;
+        self.y = 0.5;
+    }
+}
+
+/// Color filling the content region while the image is loading
+/// or failed to load.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleImagePlaceholder(#[style(default = "Color::from_rgb(0xE0, 0xE0, 0xE0)")] pub Color);
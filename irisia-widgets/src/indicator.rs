@@ -0,0 +1,72 @@
+//! Painting shared by controls drawing indicators, like checkboxes and
+//! thumbs of sliders.
+
+use irisia::skia_safe::{
+    canvas::SaveLayerRec, paint::Cap, Canvas, Color, Paint, PaintStyle, RRect, Rect,
+};
+
+use crate::interaction::InteractionState;
+
+/// Opacity of disabled controls.
+const DISABLED_OPACITY: u8 = 0x61;
+
+/// Fade everything drawn until the canvas is restored.
+pub(crate) fn fade_disabled(canvas: &mut Canvas) {
+    let mut paint = Paint::default();
+    paint.set_alpha(DISABLED_OPACITY);
+    canvas.save_layer(&SaveLayerRec::default().paint(&paint));
+}
+
+pub(crate) fn fill(color: Color) -> Paint {
+    let mut paint = Paint::default();
+    paint.set_anti_alias(true).set_color(color);
+    paint
+}
+
+pub(crate) fn stroke(color: Color, width: f32) -> Paint {
+    let mut paint = fill(color);
+    paint
+        .set_style(PaintStyle::Stroke)
+        .set_stroke_width(width)
+        .set_stroke_cap(Cap::Round);
+    paint
+}
+
+/// Draw the halo of a hovered or pressed indicator, and the ring of a
+/// focused one, around `indicator`.
+pub(crate) fn draw_state(
+    canvas: &mut Canvas,
+    indicator: &RRect,
+    accent: Color,
+    state: InteractionState,
+    disabled: bool,
+) {
+    if disabled {
+        return;
+    }
+
+    let spread = indicator.height() * 0.3;
+    let halo = match state {
+        InteractionState { pressed: true, .. } => Some(0x3d),
+        InteractionState { hovered: true, .. } => Some(0x1f),
+        _ => None,
+    };
+    if let Some(alpha) = halo {
+        let mut rrect = *indicator;
+        rrect.outset((spread, spread));
+        canvas.draw_rrect(rrect, &fill(accent.with_a(alpha)));
+    }
+
+    if state.focused {
+        let width = (indicator.height() * 0.1).max(1.0);
+        let mut rrect = *indicator;
+        rrect.outset((spread / 2.0, spread / 2.0));
+        canvas.draw_rrect(rrect, &stroke(accent.with_a(0x99), width));
+    }
+}
+
+/// Rounded rect of `rect` with radius relative to its height.
+pub(crate) fn rounded(rect: Rect, radius: f32) -> RRect {
+    let radius = rect.height() * radius;
+    RRect::new_rect_xy(rect, radius, radius)
+}
@@ -0,0 +1,173 @@
+//! Interaction shared by elements pressed like a button.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Weak,
+};
+
+use irisia::{
+    application::GlobalContent,
+    element::ElementHandle,
+    event::{
+        standard::{
+            Blured, ElementAbandoned, Focused, PointerDown, PointerEntered, PointerOut, PointerUp,
+        },
+        EventDispatcher,
+    },
+    winit::event::{ElementState, KeyboardInput, VirtualKeyCode},
+    Event, StaticWindowEvent,
+};
+
+/// Interaction state of an element.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct InteractionState {
+    pub hovered: bool,
+    pub pressed: bool,
+    pub focused: bool,
+}
+
+/// Element activated by a click, or by `Space` while focused.
+pub(crate) trait Pressable: Send + Sync + 'static {
+    type Activated: Event;
+
+    /// Whether `Enter` activates the element too.
+    const ACTIVATE_ON_ENTER: bool;
+
+    fn interaction(&mut self) -> &mut InteractionState;
+
+    fn disabled(&self) -> bool;
+
+    /// Event to emit when activated, `None` to emit nothing.
+    fn activate(&mut self) -> Option<Self::Activated>;
+}
+
+/// Run the interaction of a pressable element until it is abandoned.
+pub(crate) async fn run_pressable<El: Pressable>(
+    eh: Weak<ElementHandle<El>>,
+    ed: EventDispatcher,
+    global: Arc<GlobalContent>,
+) {
+    let win_ed = global.global_event_dispatcher();
+    let focused = AtomicBool::new(false);
+
+    let pointer = async {
+        loop {
+            ed.recv_sys::<PointerDown>().await;
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+            if eh.el_read().await.disabled() {
+                continue;
+            }
+
+            eh.focus();
+            eh.el_write().await.interaction().pressed = true;
+
+            // released anywhere, but only activates over the element
+            win_ed.recv_sys::<PointerUp>().await;
+            let activated = {
+                let mut el = eh.el_write().await;
+                let state = std::mem::take(&mut el.interaction().pressed);
+                if state && el.interaction().hovered && !el.disabled() {
+                    el.activate()
+                } else {
+                    None
+                }
+            };
+            if let Some(event) = activated {
+                eh.event_dispatcher().emit(event);
+            }
+        }
+    };
+
+    let hover = async {
+        loop {
+            let hovered = tokio::select! {
+                _ = ed.recv_sys::<PointerEntered>() => true,
+                _ = ed.recv_sys::<PointerOut>() => false,
+            };
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+            eh.el_write().await.interaction().hovered = hovered;
+        }
+    };
+
+    let focus = async {
+        loop {
+            let value = tokio::select! {
+                _ = ed.recv_sys::<Focused>() => true,
+                _ = ed.recv_sys::<Blured>() => false,
+            };
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            let mut el = eh.el_write().await;
+            if value && el.disabled() {
+                drop(el);
+                eh.blur();
+                continue;
+            }
+            el.interaction().focused = value;
+            if !value {
+                el.interaction().pressed = false;
+            }
+            focused.store(value, Ordering::Relaxed);
+        }
+    };
+
+    let keyboard = async {
+        let mut lock = win_ed.lock();
+        loop {
+            let (state, key) = match lock.recv_sys::<StaticWindowEvent>().await {
+                StaticWindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state,
+                            virtual_keycode:
+                                Some(key @ (VirtualKeyCode::Return | VirtualKeyCode::Space)),
+                            ..
+                        },
+                    ..
+                } if focused.load(Ordering::Relaxed) => (state, key),
+                _ => continue,
+            };
+
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            // `Enter` activates when pressed, `Space` presses the element
+            // and activates when released
+            let activated = {
+                let mut el = eh.el_write().await;
+                let activate = match (key, state) {
+                    _ if el.disabled() => false,
+                    (VirtualKeyCode::Return, ElementState::Pressed) => El::ACTIVATE_ON_ENTER,
+                    (VirtualKeyCode::Space, ElementState::Pressed) => {
+                        el.interaction().pressed = true;
+                        false
+                    }
+                    (VirtualKeyCode::Space, ElementState::Released) => {
+                        std::mem::take(&mut el.interaction().pressed)
+                    }
+                    _ => false,
+                };
+                if activate {
+                    el.activate()
+                } else {
+                    None
+                }
+            };
+            if let Some(event) = activated {
+                eh.event_dispatcher().emit(event);
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = ed.recv_sys::<ElementAbandoned>() => {}
+        _ = async { tokio::join!(pointer, hover, focus, keyboard) } => {}
+    }
+}
@@ -1,6 +1,15 @@
-extern crate irisia_core as irisia;
-
 pub mod box_styles;
+pub mod button;
+pub mod data_grid;
+pub mod font;
+pub mod image;
+mod indicator;
+pub mod interaction;
+pub mod slider;
+pub mod svg;
+pub mod text_input;
 pub mod textbox;
+pub mod toggle;
+pub mod virtual_list;
 
-pub use irisia_core::*;
+pub use irisia::*;
@@ -0,0 +1,265 @@
+use irisia::{
+    primitive::{Direction, Point, Region},
+    skia_safe::{Canvas, Point as SkiaPoint, RRect, Rect},
+    winit::event::VirtualKeyCode,
+    Event,
+};
+
+use crate::{
+    indicator::{draw_state, fade_disabled, fill, stroke},
+    interaction::InteractionState,
+};
+
+use super::{styles::SliderStyles, Orientation, SliderOptions};
+
+/// At most this many tick marks are drawn.
+const MAX_TICKS: usize = 1000;
+
+/// Slider element with some thumbs, driven by the slider runtime.
+pub(super) trait SliderElement: Send + Sync + 'static {
+    type Input: Event;
+    type Changed: Event;
+
+    fn core(&self) -> &SliderCore;
+
+    fn core_mut(&mut self) -> &mut SliderCore;
+
+    /// Values in the props, in ascending order.
+    fn values(&self) -> &[f64];
+
+    fn input(values: &[f64]) -> Self::Input;
+
+    fn changed(values: &[f64]) -> Self::Changed;
+}
+
+/// State and painting shared by sliders.
+pub(super) struct SliderCore {
+    pub options: SliderOptions,
+    pub styles: SliderStyles,
+    pub direction: Direction,
+    pub focused: bool,
+    pub hovered: Option<usize>,
+
+    /// Thumb dragged by pointer or moved by keyboard.
+    pub active: usize,
+    pub dragging: bool,
+
+    /// Values while dragging, shown instead of the values in props.
+    pub pending: Option<Vec<f64>>,
+
+    /// Positions of `min` and `max` in the last frame.
+    axis: Option<(SkiaPoint, SkiaPoint)>,
+}
+
+impl SliderCore {
+    pub fn new(options: SliderOptions, styles: SliderStyles, direction: Direction) -> Self {
+        SliderCore {
+            options,
+            styles,
+            direction,
+            focused: false,
+            hovered: None,
+            active: 0,
+            dragging: false,
+            pending: None,
+            axis: None,
+        }
+    }
+
+    /// Returns `true` if the slider becomes disabled.
+    pub fn update(
+        &mut self,
+        options: SliderOptions,
+        styles: SliderStyles,
+        direction: Direction,
+    ) -> bool {
+        let disabling = options.disabled && !self.options.disabled;
+        if disabling {
+            self.dragging = false;
+            self.pending = None;
+        }
+
+        self.options = options;
+        self.styles = styles;
+        self.direction = direction;
+        disabling
+    }
+
+    pub fn render(&mut self, canvas: &mut Canvas, draw_region: Region, values: &[f64]) {
+        let styles = &self.styles;
+        let options = &self.options;
+        let values = self.pending.as_deref().unwrap_or(values);
+
+        let count = canvas.save();
+        if options.disabled {
+            fade_disabled(canvas);
+        }
+
+        let (start, end) = styles.box_styles.draw_border_limited(canvas, draw_region);
+        let rect = Rect::new(
+            start.0.to_physical(),
+            start.1.to_physical(),
+            end.0.to_physical(),
+            end.1.to_physical(),
+        );
+
+        // thumbs stay inside the content region at both ends
+        let size = styles.thumb_size.0.to_physical();
+        let radius = size / 2.0;
+        let (min_at, max_at) = match options.orientation {
+            Orientation::Horizontal => {
+                let y = rect.center_y();
+                let left = SkiaPoint::new(rect.left + radius, y);
+                let right = SkiaPoint::new(rect.right - radius, y);
+                if self.direction.is_rtl() {
+                    (right, left)
+                } else {
+                    (left, right)
+                }
+            }
+            Orientation::Vertical => {
+                let x = rect.center_x();
+                (
+                    SkiaPoint::new(x, rect.bottom - radius),
+                    SkiaPoint::new(x, rect.top + radius),
+                )
+            }
+        };
+        let at = |value: f64| lerp(min_at, max_at, options.fraction(value));
+
+        let thickness = styles.track_thickness.0.to_physical();
+        canvas.draw_line(min_at, max_at, &stroke(styles.track.0, thickness));
+
+        let (from, to) = match values {
+            [value] => (options.min, *value),
+            [first, .., last] => (*first, *last),
+            [] => (options.min, options.min),
+        };
+        canvas.draw_line(at(from), at(to), &stroke(styles.accent.0, thickness));
+
+        if let Some(interval) = options.ticks.filter(|interval| *interval > 0.0) {
+            let paint = fill(styles.mark.0);
+            let ticks = ((options.max - options.min) / interval).floor();
+            if (0.0..MAX_TICKS as f64).contains(&ticks) {
+                for index in 0..=ticks as usize {
+                    let value = options.min + interval * index as f64;
+                    canvas.draw_circle(at(value), thickness / 4.0, &paint);
+                }
+            }
+        }
+
+        for (index, value) in values.iter().enumerate() {
+            let center = at(*value);
+            let thumb = Rect::from_xywh(center.x - radius, center.y - radius, size, size);
+            let state = InteractionState {
+                hovered: self.hovered == Some(index),
+                pressed: self.dragging && self.active == index,
+                focused: self.focused && self.active == index,
+            };
+            draw_state(
+                canvas,
+                &RRect::new_oval(thumb),
+                styles.accent.0,
+                state,
+                options.disabled,
+            );
+            canvas.draw_circle(center, radius, &fill(styles.accent.0));
+        }
+
+        canvas.restore_to_count(count);
+        self.axis = Some((min_at, max_at));
+    }
+
+    /// Value at the projection of `point` on the track.
+    pub fn value_at(&self, point: Point) -> Option<f64> {
+        let (min_at, max_at) = self.axis?;
+        let point = SkiaPoint::new(point.0.to_physical(), point.1.to_physical());
+
+        let axis = max_at - min_at;
+        let offset = point - min_at;
+        let length = axis.x * axis.x + axis.y * axis.y;
+        let fraction = if length > 0.0 {
+            ((offset.x * axis.x + offset.y * axis.y) / length).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        Some(self.options.normalize(self.options.value_at(fraction)))
+    }
+
+    /// Thumb under `point`.
+    pub fn thumb_at(&self, point: Point, values: &[f64]) -> Option<usize> {
+        let radius = self.styles.thumb_size.0.to_physical() / 2.0;
+        let point = SkiaPoint::new(point.0.to_physical(), point.1.to_physical());
+        let nearest = self.nearest_thumb_physical(point, values)?;
+        (self.distance(point, values[nearest])? <= radius).then_some(nearest)
+    }
+
+    /// Thumb to drag when pressed at `point`.
+    pub fn nearest_thumb(&self, point: Point, values: &[f64]) -> Option<usize> {
+        let point = SkiaPoint::new(point.0.to_physical(), point.1.to_physical());
+        self.nearest_thumb_physical(point, values)
+    }
+
+    /// Value of the active thumb after pressing `key`.
+    pub fn key_value(&self, key: VirtualKeyCode, value: f64) -> Option<f64> {
+        let SliderOptions { min, max, .. } = self.options;
+        let step = self.options.key_step();
+
+        // `Left` and `Right` follow the track when it is horizontal
+        let reversed =
+            self.options.orientation == Orientation::Horizontal && self.direction.is_rtl();
+        let value = match key {
+            VirtualKeyCode::Home => min,
+            VirtualKeyCode::End => max,
+            VirtualKeyCode::PageUp => value + step * 10.0,
+            VirtualKeyCode::PageDown => value - step * 10.0,
+            VirtualKeyCode::Up => value + step,
+            VirtualKeyCode::Down => value - step,
+            VirtualKeyCode::Right if reversed => value - step,
+            VirtualKeyCode::Right => value + step,
+            VirtualKeyCode::Left if reversed => value + step,
+            VirtualKeyCode::Left => value - step,
+            _ => return None,
+        };
+        Some(self.options.normalize(value))
+    }
+
+    fn nearest_thumb_physical(&self, point: SkiaPoint, values: &[f64]) -> Option<usize> {
+        let distances = values
+            .iter()
+            .map(|value| self.distance(point, *value))
+            .collect::<Option<Vec<f32>>>()?;
+
+        // the later thumb wins a tie, so that stacked thumbs of a range
+        // can be pulled apart to the end side
+        distances
+            .iter()
+            .enumerate()
+            .rev()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+
+    fn distance(&self, point: SkiaPoint, value: f64) -> Option<f32> {
+        let (min_at, max_at) = self.axis?;
+        let center = lerp(min_at, max_at, self.options.fraction(value));
+        Some((point.x - center.x).hypot(point.y - center.y))
+    }
+}
+
+/// `values` with the value at `index` moved to `value`, kept between
+/// its neighbors.
+pub(super) fn moved(values: &[f64], index: usize, value: f64) -> Vec<f64> {
+    let low = index
+        .checked_sub(1)
+        .map_or(f64::NEG_INFINITY, |prev| values[prev]);
+    let high = values.get(index + 1).copied().unwrap_or(f64::INFINITY);
+
+    let mut values = values.to_vec();
+    values[index] = value.max(low).min(high);
+    values
+}
+
+fn lerp(from: SkiaPoint, to: SkiaPoint, t: f32) -> SkiaPoint {
+    SkiaPoint::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t)
+}
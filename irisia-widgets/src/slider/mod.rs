@@ -0,0 +1,320 @@
+//! Sliders choosing values between a minimum and a maximum. Like
+//! toggles, the values are owned by the parent: new values are emitted
+//! as events and passed back through the props.
+
+use std::{sync::Arc, time::Duration};
+
+use irisia::{
+    element::{
+        ChildrenNodes, ChildrenSetter, Element, ElementHandle, RenderElement, UpdateElement,
+    },
+    primitive::Region,
+    style::StyleContainer,
+    Event, Result, StyleReader, UpdateWith,
+};
+use tokio::task::JoinHandle;
+
+use crate::box_styles::BoxStyles;
+
+pub use self::{
+    range::{RangeSlider, RangeSliderChanged, RangeSliderInput, RangeSliderProps},
+    styles::*,
+};
+
+use self::{
+    base::{SliderCore, SliderElement},
+    styles::SliderStyles,
+};
+
+mod base;
+mod range;
+mod runtime;
+pub mod styles;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// From the start side to the end side.
+    #[default]
+    Horizontal,
+
+    /// From bottom to top.
+    Vertical,
+}
+
+/// Options shared by sliders.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SliderOptions {
+    pub min: f64,
+    pub max: f64,
+
+    /// Values are `min` plus multiples of `step`, any value if `0.0`.
+    pub step: f64,
+    pub orientation: Orientation,
+
+    /// Interval between tick marks from `min`, no tick marks if `None`.
+    pub ticks: Option<f64>,
+    pub disabled: bool,
+}
+
+impl Default for SliderOptions {
+    fn default() -> Self {
+        SliderOptions {
+            min: 0.0,
+            max: 100.0,
+            step: 1.0,
+            orientation: Orientation::Horizontal,
+            ticks: None,
+            disabled: false,
+        }
+    }
+}
+
+impl SliderOptions {
+    /// Clamp `value` between `min` and `max`, and snap it to `step`.
+    pub fn normalize(&self, value: f64) -> f64 {
+        if self.max <= self.min {
+            return self.min;
+        }
+
+        let value = value.clamp(self.min, self.max);
+        if self.step <= 0.0 {
+            return value;
+        }
+
+        let snapped = self.min + ((value - self.min) / self.step).round() * self.step;
+        snapped.min(self.max)
+    }
+
+    /// Distance an arrow key moves a thumb, a hundredth of the range if
+    /// there is no step.
+    fn key_step(&self) -> f64 {
+        if self.step > 0.0 {
+            self.step
+        } else {
+            (self.max - self.min) / 100.0
+        }
+    }
+
+    /// Position of `value` from `min` at `0.0` to `max` at `1.0`.
+    fn fraction(&self, value: f64) -> f32 {
+        if self.max <= self.min {
+            return 0.0;
+        }
+        ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0) as f32
+    }
+
+    fn value_at(&self, fraction: f32) -> f64 {
+        self.min + (self.max - self.min) * fraction as f64
+    }
+}
+
+/// Builder methods of props with `options: SliderOptions`.
+macro_rules! slider_options {
+    ($Props:ident) => {
+        impl $Props {
+            pub fn min(mut self, min: f64) -> Self {
+                self.options.min = min;
+                self
+            }
+
+            pub fn max(mut self, max: f64) -> Self {
+                self.options.max = max;
+                self
+            }
+
+            /// Values are `min` plus multiples of `step`, any value if `0.0`.
+            pub fn step(mut self, step: f64) -> Self {
+                self.options.step = step;
+                self
+            }
+
+            pub fn orientation(mut self, orientation: $crate::slider::Orientation) -> Self {
+                self.options.orientation = orientation;
+                self
+            }
+
+            pub fn vertical(self) -> Self {
+                self.orientation($crate::slider::Orientation::Vertical)
+            }
+
+            /// Draw tick marks every `interval` from `min`.
+            pub fn ticks(mut self, interval: f64) -> Self {
+                self.options.ticks = Some(interval);
+                self
+            }
+
+            pub fn disabled(mut self, disabled: bool) -> Self {
+                self.options.disabled = disabled;
+                self
+            }
+        }
+    };
+}
+
+use slider_options;
+
+/// Slider with a thumb. Dragging the thumb, or pressing the track to
+/// move it there, emits [`SliderInput`] on every move and
+/// [`SliderChanged`] when released. While focused, arrow keys move the
+/// thumb a step, `PageUp` and `PageDown` ten steps, and `Home` and `End`
+/// to the ends, emitting both events.
+pub struct Slider {
+    runtime: Option<JoinHandle<()>>,
+    value: f64,
+    core: SliderCore,
+}
+
+#[derive(Default)]
+pub struct SliderProps {
+    pub value: f64,
+    pub options: SliderOptions,
+}
+
+impl SliderProps {
+    pub fn value(mut self, value: f64) -> Self {
+        self.value = value;
+        self
+    }
+}
+
+slider_options!(SliderProps);
+
+/// The value while the thumb is moving.
+#[derive(Event, Clone, Copy)]
+pub struct SliderInput(pub f64);
+
+/// The value after the thumb is released, or moved by keyboard.
+#[derive(Event, Clone, Copy)]
+pub struct SliderChanged(pub f64);
+
+impl Element for Slider {
+    type BlankProps = SliderProps;
+
+    fn layout<'a, Ch>(&mut self, _: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        setter.set_children(());
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        self.core
+            .render(renderer.canvas(), draw_region, &[self.value]);
+        renderer.set_interact_region(draw_region);
+        Ok(())
+    }
+}
+
+impl<'a, Sty> UpdateWith<UpdateElement<'a, Self, SliderProps, Sty>> for Slider
+where
+    Sty: StyleContainer,
+{
+    fn create_with(updater: UpdateElement<'a, Self, SliderProps, Sty>) -> Self {
+        let handle = updater.handle;
+        let mut slider = Slider {
+            runtime: Some(tokio::spawn(runtime::run(
+                Arc::downgrade(handle),
+                handle.event_dispatcher().clone(),
+                handle.global().clone(),
+            ))),
+            value: 0.0,
+            core: SliderCore::new(
+                updater.props.options,
+                read_styles(updater.styles, None, handle),
+                handle.direction(),
+            ),
+        };
+
+        slider.update_with(updater, false);
+        slider
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, SliderProps, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        let UpdateElement {
+            props: SliderProps { value, options },
+            styles,
+            handle,
+        } = updater;
+
+        let direction = handle.direction();
+        let read = read_styles(styles, Some(&self.core.styles.box_styles), handle);
+        if self.core.update(options, read, direction) {
+            handle.release_pointer();
+            handle.blur();
+        }
+        self.value = options.normalize(value);
+
+        // box styles cannot be compared
+        false
+    }
+}
+
+impl Drop for Slider {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            runtime.abort();
+        }
+    }
+}
+
+impl SliderElement for Slider {
+    type Input = SliderInput;
+    type Changed = SliderChanged;
+
+    fn core(&self) -> &SliderCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut SliderCore {
+        &mut self.core
+    }
+
+    fn values(&self) -> &[f64] {
+        std::slice::from_ref(&self.value)
+    }
+
+    fn input(values: &[f64]) -> SliderInput {
+        SliderInput(values[0])
+    }
+
+    fn changed(values: &[f64]) -> SliderChanged {
+        SliderChanged(values[0])
+    }
+}
+
+impl Slider {
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn options(&self) -> &SliderOptions {
+        &self.core.options
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.core.dragging
+    }
+}
+
+fn read_styles<El>(
+    styles: &impl StyleContainer,
+    previous: Option<&BoxStyles>,
+    handle: &Arc<ElementHandle<El>>,
+) -> SliderStyles
+where
+    El: Send + Sync + 'static,
+{
+    let mut read = SliderStyles::read_style(styles);
+    read.box_styles.apply_direction(handle.direction());
+    read.box_styles.preload_images(previous, handle);
+    read
+}
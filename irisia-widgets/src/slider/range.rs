@@ -0,0 +1,171 @@
+use std::{sync::Arc, time::Duration};
+
+use irisia::{
+    element::{ChildrenNodes, ChildrenSetter, Element, RenderElement, UpdateElement},
+    primitive::Region,
+    style::StyleContainer,
+    Event, Result, UpdateWith,
+};
+use tokio::task::JoinHandle;
+
+use super::{
+    base::{SliderCore, SliderElement},
+    read_styles, runtime, slider_options, SliderOptions,
+};
+
+/// Slider with two thumbs choosing a range, the thumbs cannot pass each
+/// other. Emits [`RangeSliderInput`] while a thumb is moving and
+/// [`RangeSliderChanged`] when it is released. Keys move the thumb
+/// pressed last, the low one at first, like [`Slider`](super::Slider).
+pub struct RangeSlider {
+    runtime: Option<JoinHandle<()>>,
+    values: [f64; 2],
+    core: SliderCore,
+}
+
+#[derive(Default)]
+pub struct RangeSliderProps {
+    pub low: f64,
+    pub high: f64,
+    pub options: SliderOptions,
+}
+
+impl RangeSliderProps {
+    pub fn range(mut self, low: f64, high: f64) -> Self {
+        self.low = low;
+        self.high = high;
+        self
+    }
+}
+
+slider_options!(RangeSliderProps);
+
+/// The low and high values while a thumb is moving.
+#[derive(Event, Clone, Copy)]
+pub struct RangeSliderInput(pub f64, pub f64);
+
+/// The low and high values after a thumb is released, or moved by
+/// keyboard.
+#[derive(Event, Clone, Copy)]
+pub struct RangeSliderChanged(pub f64, pub f64);
+
+impl Element for RangeSlider {
+    type BlankProps = RangeSliderProps;
+
+    fn layout<'a, Ch>(&mut self, _: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        setter.set_children(());
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        self.core
+            .render(renderer.canvas(), draw_region, &self.values);
+        renderer.set_interact_region(draw_region);
+        Ok(())
+    }
+}
+
+impl<'a, Sty> UpdateWith<UpdateElement<'a, Self, RangeSliderProps, Sty>> for RangeSlider
+where
+    Sty: StyleContainer,
+{
+    fn create_with(updater: UpdateElement<'a, Self, RangeSliderProps, Sty>) -> Self {
+        let handle = updater.handle;
+        let mut slider = RangeSlider {
+            runtime: Some(tokio::spawn(runtime::run(
+                Arc::downgrade(handle),
+                handle.event_dispatcher().clone(),
+                handle.global().clone(),
+            ))),
+            values: [0.0; 2],
+            core: SliderCore::new(
+                updater.props.options,
+                read_styles(updater.styles, None, handle),
+                handle.direction(),
+            ),
+        };
+
+        slider.update_with(updater, false);
+        slider
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, RangeSliderProps, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        let UpdateElement {
+            props: RangeSliderProps { low, high, options },
+            styles,
+            handle,
+        } = updater;
+
+        let direction = handle.direction();
+        let read = read_styles(styles, Some(&self.core.styles.box_styles), handle);
+        if self.core.update(options, read, direction) {
+            handle.release_pointer();
+            handle.blur();
+        }
+
+        let (low, high) = (options.normalize(low), options.normalize(high));
+        self.values = [low.min(high), low.max(high)];
+
+        // box styles cannot be compared
+        false
+    }
+}
+
+impl Drop for RangeSlider {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            runtime.abort();
+        }
+    }
+}
+
+impl SliderElement for RangeSlider {
+    type Input = RangeSliderInput;
+    type Changed = RangeSliderChanged;
+
+    fn core(&self) -> &SliderCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut SliderCore {
+        &mut self.core
+    }
+
+    fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    fn input(values: &[f64]) -> RangeSliderInput {
+        RangeSliderInput(values[0], values[1])
+    }
+
+    fn changed(values: &[f64]) -> RangeSliderChanged {
+        RangeSliderChanged(values[0], values[1])
+    }
+}
+
+impl RangeSlider {
+    /// The low and high values.
+    pub fn range(&self) -> (f64, f64) {
+        (self.values[0], self.values[1])
+    }
+
+    pub fn options(&self) -> &SliderOptions {
+        &self.core.options
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.core.dragging
+    }
+}
@@ -0,0 +1,202 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Weak,
+};
+
+use irisia::{
+    application::GlobalContent,
+    element::ElementHandle,
+    event::{
+        standard::{
+            Blured, ElementAbandoned, Focused, PointerDown, PointerMove, PointerOut, PointerUp,
+        },
+        EventDispatcher,
+    },
+    primitive::Point,
+    winit::event::{ElementState, KeyboardInput},
+    StaticWindowEvent,
+};
+
+use super::base::{moved, SliderElement};
+
+pub(super) async fn run<El: SliderElement>(
+    eh: Weak<ElementHandle<El>>,
+    ed: EventDispatcher,
+    global: Arc<GlobalContent>,
+) {
+    let win_ed = global.global_event_dispatcher();
+    let focused = AtomicBool::new(false);
+
+    // keep tracking when the pointer moves out of the slider, captured
+    // on press so a quick release is not missed
+    if let Some(eh) = eh.upgrade() {
+        eh.set_capture_pointer_on_press(true);
+    }
+
+    let pointer = async {
+        loop {
+            let PointerDown { position, .. } = ed.recv_sys::<PointerDown>().await;
+            // listened before anything is awaited, the capture tells if the
+            // release came even earlier
+            let mut released = ed.recv::<PointerUp>();
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            let (start, first) = {
+                let mut el = eh.el_write().await;
+                if el.core().options.disabled {
+                    eh.release_pointer();
+                    continue;
+                }
+                let start = el.values().to_vec();
+                let Some(active) = el.core().nearest_thumb(position, &start) else {
+                    eh.release_pointer();
+                    continue;
+                };
+
+                let core = el.core_mut();
+                core.active = active;
+                core.dragging = true;
+                (start, drag_to(&mut *el, position))
+            };
+
+            eh.focus();
+            if let Some(values) = first {
+                eh.event_dispatcher().emit(El::input(&values));
+            }
+
+            while eh.is_capturing_pointer() {
+                let position = tokio::select! {
+                    PointerMove { position, .. } = ed.recv_sys::<PointerMove>() => position,
+                    _ = &mut released => break,
+                };
+
+                let values = drag_to(&mut *eh.el_write().await, position);
+                if let Some(values) = values {
+                    eh.event_dispatcher().emit(El::input(&values));
+                }
+            }
+
+            eh.release_pointer();
+            let end = {
+                let mut el = eh.el_write().await;
+                let core = el.core_mut();
+                core.dragging = false;
+                core.pending.take()
+            };
+            if let Some(values) = end.filter(|values| *values != start) {
+                eh.event_dispatcher().emit(El::changed(&values));
+            }
+        }
+    };
+
+    let hover = async {
+        loop {
+            let hovered = tokio::select! {
+                PointerMove { position, .. } = ed.recv_sys::<PointerMove>() => Some(position),
+                _ = ed.recv_sys::<PointerOut>() => None,
+            };
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            // only redraw when another thumb is hovered
+            let hovered = {
+                let el = eh.el_read().await;
+                let core = el.core();
+                let values = core.pending.as_deref().unwrap_or(el.values());
+                let hovered = hovered.and_then(|position| core.thumb_at(position, values));
+                (core.hovered != hovered).then_some(hovered)
+            };
+            if let Some(hovered) = hovered {
+                eh.el_write().await.core_mut().hovered = hovered;
+            }
+        }
+    };
+
+    let focus = async {
+        loop {
+            let value = tokio::select! {
+                _ = ed.recv_sys::<Focused>() => true,
+                _ = ed.recv_sys::<Blured>() => false,
+            };
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            let mut el = eh.el_write().await;
+            if value && el.core().options.disabled {
+                drop(el);
+                eh.blur();
+                continue;
+            }
+            el.core_mut().focused = value;
+            focused.store(value, Ordering::Relaxed);
+        }
+    };
+
+    let keyboard = async {
+        let mut lock = win_ed.lock();
+        loop {
+            let key = match lock.recv_sys::<StaticWindowEvent>().await {
+                StaticWindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode: Some(key),
+                            ..
+                        },
+                    ..
+                } if focused.load(Ordering::Relaxed) => key,
+                _ => continue,
+            };
+
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            // every step is both an input and a change
+            let values = {
+                let el = eh.el_read().await;
+                let core = el.core();
+                let values = el.values();
+                if core.options.disabled || core.dragging || values.is_empty() {
+                    continue;
+                }
+
+                let active = core.active.min(values.len() - 1);
+                core.key_value(key, values[active])
+                    .map(|value| moved(values, active, value))
+                    .filter(|moved| moved != values)
+            };
+            if let Some(values) = values {
+                eh.event_dispatcher().emit(El::input(&values));
+                eh.event_dispatcher().emit(El::changed(&values));
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = ed.recv_sys::<ElementAbandoned>() => {}
+        _ = async { tokio::join!(pointer, hover, focus, keyboard) } => {}
+    }
+}
+
+/// Move the active thumb to `position`, returns the new values if they
+/// changed.
+fn drag_to<El: SliderElement>(el: &mut El, position: Point) -> Option<Vec<f64>> {
+    let value = el.core().value_at(position)?;
+    let current = match &el.core().pending {
+        Some(pending) => pending.clone(),
+        None => el.values().to_vec(),
+    };
+
+    let core = el.core_mut();
+    let values = moved(&current, core.active, value);
+    if values == current {
+        return None;
+    }
+    core.pending = Some(values.clone());
+    Some(values)
+}
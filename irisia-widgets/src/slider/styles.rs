@@ -0,0 +1,26 @@
+use irisia::{primitive::Pixel, Style, StyleReader};
+
+use crate::{
+    box_styles::BoxStyles,
+    toggle::{StyleAccentColor, StyleMarkColor, StyleTrackColor},
+};
+
+/// Diameter of thumbs of a slider.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleThumbSize(#[style(default = "Pixel(18.0)")] pub Pixel);
+
+/// Thickness of the track of a slider.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTrackThickness(#[style(default = "Pixel(4.0)")] pub Pixel);
+
+#[derive(StyleReader, Clone)]
+pub(super) struct SliderStyles {
+    pub box_styles: BoxStyles,
+    pub thumb_size: StyleThumbSize,
+    pub track_thickness: StyleTrackThickness,
+    pub accent: StyleAccentColor,
+    pub mark: StyleMarkColor,
+    pub track: StyleTrackColor,
+}
@@ -0,0 +1,206 @@
+use std::sync::{Arc, Mutex};
+
+use irisia::{
+    anyhow::{anyhow, Context},
+    skia_safe::{svg::Dom, Size},
+    Result,
+};
+use lazy_static::lazy_static;
+use xml::{
+    attribute::OwnedAttribute,
+    reader::{EventReader, XmlEvent},
+    writer::{EmitterConfig, XmlEvent as WriterEvent},
+};
+
+use crate::image::{ImageSource, Lru};
+
+/// Used when the root `svg` element has neither size nor `viewBox`.
+const DEFAULT_SIZE: (f32, f32) = (300.0, 150.0);
+
+const DEFAULT_SOURCE_LIMIT: usize = 16 * 1024 * 1024;
+
+lazy_static! {
+    static ref CACHE: Mutex<Lru<ImageSource, Arc<SvgDocument>>> =
+        Mutex::new(Lru::new(DEFAULT_SOURCE_LIMIT));
+}
+
+/// A parsed SVG document with its intrinsic size. Documents are shared
+/// between threads by [`SvgCache`], which is fine since `Dom` is
+/// reference counted atomically and never mutated after parsing.
+pub struct SvgDocument {
+    dom: Dom,
+    size: Size,
+}
+
+impl SvgDocument {
+    /// Parse a document. External resources are never loaded: links
+    /// other than fragments and `data:` URLs are removed before skia
+    /// parses the document, whose default loader would fetch them.
+    pub fn parse(svg: &[u8]) -> Result<Self> {
+        let (svg, size) = strip_external_links(svg)?;
+        let mut dom = Dom::from_bytes(&svg).map_err(|_| anyhow!("cannot parse svg document"))?;
+        let size = Size::new(size.0, size.1);
+
+        // the container is never resized, so that the document can be
+        // shared and rendered without mutation
+        dom.set_container_size(size);
+        Ok(SvgDocument { dom, size })
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    pub fn dom(&self) -> &Dom {
+        &self.dom
+    }
+}
+
+/// Parsed SVG documents shared by all `Svg` elements, so repeated icons
+/// are parsed only once. The least recently used documents are dropped
+/// once their sources exceed the limit.
+pub struct SvgCache;
+
+impl SvgCache {
+    /// Set the limit of the total size of cached sources, in bytes.
+    /// Documents larger than the limit are never cached.
+    pub fn set_source_limit(bytes: usize) {
+        CACHE.lock().unwrap().set_limit(bytes);
+    }
+
+    pub fn source_limit() -> usize {
+        CACHE.lock().unwrap().limit()
+    }
+
+    pub fn get(source: &ImageSource) -> Option<Arc<SvgDocument>> {
+        CACHE.lock().unwrap().get(source).cloned()
+    }
+
+    /// Get the parsed document of `source`, reading and parsing it on
+    /// the blocking thread pool of tokio if not cached.
+    pub async fn load(source: ImageSource) -> Result<Arc<SvgDocument>> {
+        if let Some(doc) = Self::get(&source) {
+            return Ok(doc);
+        }
+
+        let (doc, size) = {
+            let source = source.clone();
+            tokio::task::spawn_blocking(move || read_and_parse(&source)).await??
+        };
+
+        let doc = Arc::new(doc);
+        CACHE.lock().unwrap().insert(source, doc.clone(), size);
+        Ok(doc)
+    }
+
+    pub fn remove(source: &ImageSource) {
+        CACHE.lock().unwrap().remove(source);
+    }
+
+    pub fn clear() {
+        CACHE.lock().unwrap().clear();
+    }
+}
+
+/// Parsed document and the size of its source.
+fn read_and_parse(source: &ImageSource) -> Result<(SvgDocument, usize)> {
+    match source {
+        ImageSource::Path(path) => {
+            let svg = std::fs::read(path)
+                .with_context(|| format!("cannot read svg `{}`", path.display()))?;
+            Ok((SvgDocument::parse(&svg)?, svg.len()))
+        }
+        ImageSource::Bytes { data, .. } => Ok((SvgDocument::parse(data)?, data.len())),
+    }
+}
+
+/// Write the document again without links to external resources,
+/// returns it with the intrinsic size of its root `svg` element.
+// `Dom` of skia-safe 0.62 neither exposes its root node nor takes a
+// resource loader, so the document goes through an xml parser first
+fn strip_external_links(svg: &[u8]) -> Result<(Vec<u8>, (f32, f32))> {
+    let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .create_writer(Vec::with_capacity(svg.len()));
+    let mut size = None;
+
+    for event in EventReader::new(svg) {
+        let event = event.context("cannot parse svg document")?;
+        let written = match &event {
+            XmlEvent::StartDocument { .. } => continue,
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            } => {
+                if size.is_none() {
+                    if name.local_name != "svg" {
+                        return Err(anyhow!("root element of svg document is not `svg`"));
+                    }
+                    size = Some(intrinsic_size(attributes).unwrap_or(DEFAULT_SIZE));
+                }
+
+                let attributes: Vec<_> = attributes
+                    .iter()
+                    .filter(|attr| !is_external_link(attr))
+                    .map(OwnedAttribute::borrow)
+                    .collect();
+
+                writer.write(WriterEvent::StartElement {
+                    name: name.borrow(),
+                    attributes: attributes.into(),
+                    namespace: namespace.borrow(),
+                })
+            }
+            event => match event.as_writer_event() {
+                Some(event) => writer.write(event),
+                None => continue,
+            },
+        };
+        written.context("cannot write svg document")?;
+    }
+
+    let size = size.ok_or_else(|| anyhow!("svg document is empty"))?;
+    Ok((writer.into_inner(), size))
+}
+
+fn is_external_link(attr: &OwnedAttribute) -> bool {
+    attr.name.local_name == "href"
+        && !attr.value.starts_with('#')
+        && !attr.value.starts_with("data:")
+}
+
+/// Size of the root `svg` element, from its `width` and `height`,
+/// or from its `viewBox` if they are missing or relative.
+fn intrinsic_size(attributes: &[OwnedAttribute]) -> Option<(f32, f32)> {
+    let attribute = |name: &str| {
+        attributes
+            .iter()
+            .find(|attr| attr.name.namespace.is_none() && attr.name.local_name == name)
+            .map(|attr| attr.value.as_str())
+    };
+
+    let width = attribute("width").and_then(parse_length);
+    let height = attribute("height").and_then(parse_length);
+    let view_box = attribute("viewBox").and_then(|view_box| {
+        let mut values = view_box
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .skip(2)
+            .map(|s| s.parse::<f32>().ok());
+        Some((values.next()??, values.next()??))
+    });
+
+    match (width, height, view_box) {
+        (Some(w), Some(h), _) => Some((w, h)),
+        (Some(w), None, Some((vw, vh))) if vw > 0.0 => Some((w, w * vh / vw)),
+        (None, Some(h), Some((vw, vh))) if vh > 0.0 => Some((h * vw / vh, h)),
+        (_, _, view_box) => view_box,
+    }
+}
+
+fn parse_length(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let value = value.strip_suffix("px").unwrap_or(value);
+    value.parse().ok().filter(|v: &f32| *v > 0.0)
+}
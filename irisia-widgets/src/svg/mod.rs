@@ -0,0 +1,208 @@
+use std::{
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use irisia::{
+    element::{
+        ChildrenNodes, ChildrenSetter, Element, ElementHandle, RenderElement, UpdateElement,
+    },
+    primitive::Region,
+    skia_safe::{canvas::SaveLayerRec, color_filters, BlendMode, Paint, Rect},
+    style::{StyleColor, StyleContainer},
+    Result, StyleReader, UpdateWith,
+};
+use tokio::task::JoinHandle;
+
+use crate::{box_styles::BoxStyles, image::ImageSource};
+
+pub use self::document::{SvgCache, SvgDocument};
+
+mod document;
+
+/// Renders an SVG document scaled into the content region, keeping
+/// its aspect ratio. With `color` set, every painted pixel of the
+/// document is filled with the color, which suits monochrome icons.
+pub struct Svg {
+    handle: Weak<ElementHandle<Self>>,
+    source: Option<ImageSource>,
+    document: Option<Arc<SvgDocument>>,
+    loading: Option<JoinHandle<()>>,
+    styles: SvgStyles,
+}
+
+#[derive(StyleReader, Clone)]
+struct SvgStyles {
+    box_styles: BoxStyles,
+    color: Option<StyleColor>,
+}
+
+#[derive(Default)]
+pub struct SvgProps {
+    pub source: Option<ImageSource>,
+}
+
+impl SvgProps {
+    /// Path of an SVG file, or the document itself in bytes.
+    pub fn source(mut self, source: impl Into<ImageSource>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+}
+
+impl Element for Svg {
+    type BlankProps = SvgProps;
+
+    fn layout<'a, Ch>(&mut self, _: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        setter.set_children(());
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        let canvas = renderer.canvas();
+        let (start, end) = self
+            .styles
+            .box_styles
+            .draw_border_limited(canvas, draw_region);
+
+        let document = match &self.document {
+            Some(doc) => doc,
+            None => return Ok(()),
+        };
+
+        let dst = Rect::new(
+            start.0.to_physical(),
+            start.1.to_physical(),
+            end.0.to_physical(),
+            end.1.to_physical(),
+        );
+        let size = document.size();
+        if dst.is_empty() || size.is_empty() {
+            return Ok(());
+        }
+
+        let scale = (dst.width() / size.width).min(dst.height() / size.height);
+        let left = dst.left + (dst.width() - size.width * scale) / 2.0;
+        let top = dst.top + (dst.height() - size.height * scale) / 2.0;
+
+        let count = canvas.save();
+        canvas.clip_rect(dst, None, true);
+
+        if let Some(StyleColor(color)) = &self.styles.color {
+            let mut paint = Paint::default();
+            paint.set_color_filter(color_filters::blend(*color, BlendMode::SrcIn));
+            canvas.save_layer(&SaveLayerRec::default().bounds(&dst).paint(&paint));
+        }
+
+        canvas.translate((left, top)).scale((scale, scale));
+        document.dom().render(canvas);
+        canvas.restore_to_count(count);
+
+        renderer.set_interact_region(draw_region);
+        Ok(())
+    }
+}
+
+impl<'a, Sty> UpdateWith<UpdateElement<'a, Self, SvgProps, Sty>> for Svg
+where
+    Sty: StyleContainer,
+{
+    fn create_with(updater: UpdateElement<'a, Self, SvgProps, Sty>) -> Self {
+        let mut svg = Svg {
+            handle: Arc::downgrade(updater.handle),
+            source: None,
+            document: None,
+            loading: None,
+            styles: read_styles(updater.styles, None, updater.handle),
+        };
+
+        svg.set_source(updater.props.source);
+        svg
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, SvgProps, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        self.styles = read_styles(
+            updater.styles,
+            Some(&self.styles.box_styles),
+            updater.handle,
+        );
+        self.set_source(updater.props.source);
+
+        // box styles cannot be compared
+        false
+    }
+}
+
+impl Svg {
+    fn set_source(&mut self, source: Option<ImageSource>) {
+        if self.source == source {
+            return;
+        }
+
+        if let Some(task) = self.loading.take() {
+            task.abort();
+        }
+
+        self.document = source.as_ref().and_then(SvgCache::get);
+        if let (Some(source), None) = (&source, &self.document) {
+            self.loading = Some(self.spawn_load(source.clone()));
+        }
+        self.source = source;
+    }
+
+    fn spawn_load(&self, source: ImageSource) -> JoinHandle<()> {
+        let handle = self.handle.clone();
+
+        tokio::spawn(async move {
+            let result = SvgCache::load(source.clone()).await;
+
+            let handle = match handle.upgrade() {
+                Some(h) => h,
+                None => return,
+            };
+
+            let mut this = handle.el_write().await;
+            if this.source.as_ref() != Some(&source) {
+                return;
+            }
+
+            this.loading = None;
+            match result {
+                Ok(doc) => this.document = Some(doc),
+                Err(err) => {
+                    irisia::warning!(target: irisia::log::RENDER, "cannot load svg: {err:#}");
+                }
+            }
+        })
+    }
+}
+
+impl Drop for Svg {
+    fn drop(&mut self) {
+        if let Some(task) = &self.loading {
+            task.abort();
+        }
+    }
+}
+
+fn read_styles(
+    styles: &impl StyleContainer,
+    previous: Option<&BoxStyles>,
+    handle: &Arc<ElementHandle<Svg>>,
+) -> SvgStyles {
+    let mut styles = SvgStyles::read_style(styles);
+    styles.box_styles.apply_direction(handle.direction());
+    styles.box_styles.preload_images(previous, handle);
+    styles
+}
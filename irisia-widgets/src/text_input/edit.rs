@@ -0,0 +1,213 @@
+use std::ops::Range;
+
+use crate::textbox::navigation;
+
+/// Text being edited, with the caret and the selection. Positions are
+/// byte offsets in the value, always on char boundaries.
+#[derive(Default)]
+pub(super) struct EditState {
+    pub value: String,
+    pub caret: usize,
+    pub anchor: usize,
+    pub composition: Option<Composition>,
+}
+
+/// Text being composed in an IME, shown at the caret but not inserted
+/// into the value until committed.
+pub(super) struct Composition {
+    pub text: String,
+
+    /// Caret in the composing text, in bytes.
+    pub cursor: Option<(usize, usize)>,
+}
+
+impl EditState {
+    pub fn selection(&self) -> Range<usize> {
+        self.caret.min(self.anchor)..self.caret.max(self.anchor)
+    }
+
+    pub fn has_selection(&self) -> bool {
+        self.caret != self.anchor
+    }
+
+    pub fn set_value(&mut self, value: String) {
+        self.value = value;
+        self.caret = self.value.len();
+        self.anchor = self.caret;
+        self.composition = None;
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.caret = self.value.len();
+    }
+
+    /// Move the caret to `pos`, keeps the anchor if `extend` is `true`.
+    pub fn move_to(&mut self, pos: usize, extend: bool) {
+        self.caret = pos;
+        if !extend {
+            self.anchor = pos;
+        }
+    }
+
+    /// Replace the selection with `text`. Newlines are replaced with
+    /// spaces unless `multiline`, and `text` is cut to fit in
+    /// `max_length` characters. Returns `true` if the value changed.
+    pub fn insert(&mut self, text: &str, multiline: bool, max_length: Option<usize>) -> bool {
+        let deleted = self.delete_selection();
+
+        let mut text = if multiline {
+            text.replace("\r\n", "\n")
+        } else {
+            text.replace("\r\n", " ").replace('\n', " ")
+        };
+
+        if let Some(max) = max_length {
+            let room = max.saturating_sub(self.value.chars().count());
+            if let Some((index, _)) = text.char_indices().nth(room) {
+                text.truncate(index);
+            }
+        }
+
+        if text.is_empty() {
+            return deleted;
+        }
+
+        self.value.insert_str(self.caret, &text);
+        self.caret += text.len();
+        self.anchor = self.caret;
+        true
+    }
+
+    pub fn delete_selection(&mut self) -> bool {
+        if !self.has_selection() {
+            return false;
+        }
+
+        let range = self.selection();
+        self.caret = range.start;
+        self.anchor = range.start;
+        self.value.replace_range(range, "");
+        true
+    }
+
+    /// Delete the selection, or the character or word before the caret.
+    pub fn delete_backward(&mut self, word: bool) -> bool {
+        if self.has_selection() {
+            return self.delete_selection();
+        }
+
+        self.anchor = if word {
+            self.prev_word(self.caret)
+        } else {
+            self.prev_char(self.caret)
+        };
+        self.delete_selection()
+    }
+
+    /// Delete the selection, or the character or word after the caret.
+    pub fn delete_forward(&mut self, word: bool) -> bool {
+        if self.has_selection() {
+            return self.delete_selection();
+        }
+
+        self.anchor = if word {
+            self.next_word(self.caret)
+        } else {
+            self.next_char(self.caret)
+        };
+        self.delete_selection()
+    }
+
+    pub fn prev_char(&self, pos: usize) -> usize {
+        navigation::prev_char(&self.value, pos)
+    }
+
+    pub fn next_char(&self, pos: usize) -> usize {
+        navigation::next_char(&self.value, pos)
+    }
+
+    pub fn prev_word(&self, pos: usize) -> usize {
+        navigation::prev_word(&self.value, pos)
+    }
+
+    pub fn next_word(&self, pos: usize) -> usize {
+        navigation::next_word(&self.value, pos)
+    }
+
+    pub fn line_start(&self, pos: usize) -> usize {
+        navigation::line_start(&self.value, pos)
+    }
+
+    pub fn line_end(&self, pos: usize) -> usize {
+        navigation::line_end(&self.value, pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(value: &str) -> EditState {
+        let mut state = EditState::default();
+        state.set_value(value.to_owned());
+        state
+    }
+
+    #[test]
+    fn insert_cut_to_max_length() {
+        let mut edit = state("ab");
+        assert!(edit.insert("cdef", false, Some(4)));
+        assert_eq!(edit.value, "abcd");
+        assert_eq!(edit.caret, 4);
+
+        assert!(!edit.insert("x", false, Some(4)));
+        assert_eq!(edit.value, "abcd");
+    }
+
+    #[test]
+    fn max_length_counts_chars() {
+        let mut edit = state("é");
+        assert!(edit.insert("😀😀😀", false, Some(3)));
+        assert_eq!(edit.value, "é😀😀");
+        assert_eq!(edit.caret, edit.value.len());
+    }
+
+    #[test]
+    fn insert_replaces_selection_within_max_length() {
+        let mut edit = state("abcd");
+        edit.move_to(1, false);
+        edit.move_to(3, true);
+
+        assert!(edit.insert("xyz", false, Some(4)));
+        assert_eq!(edit.value, "axyd");
+        assert_eq!(edit.caret, 3);
+        assert!(!edit.has_selection());
+    }
+
+    #[test]
+    fn single_line_replaces_newlines() {
+        let mut edit = state("");
+        edit.insert("a\r\nb\nc", false, None);
+        assert_eq!(edit.value, "a b c");
+
+        let mut edit = state("");
+        edit.insert("a\r\nb", true, None);
+        assert_eq!(edit.value, "a\nb");
+    }
+
+    #[test]
+    fn delete() {
+        let mut edit = state("aé");
+        assert!(edit.delete_backward(false));
+        assert_eq!(edit.value, "a");
+        assert_eq!(edit.caret, 1);
+
+        let mut edit = state("foo bar");
+        edit.move_to(0, false);
+        assert!(edit.delete_forward(true));
+        assert_eq!(edit.value, "bar");
+
+        assert!(!state("").delete_backward(true));
+    }
+}
@@ -0,0 +1,575 @@
+use std::{
+    sync::{Arc, Weak},
+    time::{Duration, Instant},
+};
+
+use irisia::{
+    element::{
+        ChildrenNodes, ChildrenSetter, Element, ElementHandle, RenderElement, UpdateElement,
+    },
+    primitive::{Direction, Pixel, Point, Region},
+    skia_safe::{Paint, Point as SkiaPoint, Rect},
+    style::{StyleColor, StyleContainer},
+    winit::event::{ModifiersState, VirtualKeyCode},
+    Event, Result, StyleReader, UpdateWith,
+};
+use tokio::task::JoinHandle;
+
+use crate::{
+    box_styles::BoxStyles,
+    textbox::{
+        layout::TextLayout,
+        styles::{TextOverflow, WordBreak},
+        RichText, Span, TextBoxStyles,
+    },
+};
+
+use self::edit::{Composition, EditState};
+
+pub use self::styles::*;
+
+mod edit;
+mod runtime;
+pub mod styles;
+
+const BLINK_INTERVAL: Duration = Duration::from_millis(530);
+
+/// Single line text input.
+pub type TextInput = TextEditor<false>;
+
+/// Multi-line text input.
+pub type TextArea = TextEditor<true>;
+
+/// Editable text. Emits [`TextChanged`] every time the value is edited,
+/// and [`TextSubmitted`] when `Enter` is pressed in a [`TextInput`].
+///
+/// The value is owned by the editor. Setting the `value` prop replaces
+/// it only if the prop differs from the last one set.
+pub struct TextEditor<const MULTILINE: bool> {
+    handle: Weak<ElementHandle<Self>>,
+    runtime: Option<JoinHandle<()>>,
+    edit: EditState,
+    value_prop: Option<String>,
+    placeholder: String,
+    password: bool,
+    max_length: Option<usize>,
+    styles: TextEditorStyles,
+    direction: Direction,
+    layout: Option<TextLayout>,
+    placeholder_layout: Option<TextLayout>,
+    width: f32,
+    scroll_x: f32,
+    origin: SkiaPoint,
+    focused: bool,
+    blink_start: Instant,
+}
+
+#[derive(StyleReader, Clone)]
+struct TextEditorStyles {
+    text: TextBoxStyles,
+    box_styles: BoxStyles,
+    caret: StyleCaretColor,
+    selection: StyleSelectionColor,
+    placeholder: StylePlaceholderColor,
+}
+
+#[derive(Default)]
+pub struct TextEditorProps {
+    pub value: Option<String>,
+    pub placeholder: String,
+    pub password: bool,
+    pub max_length: Option<usize>,
+}
+
+impl TextEditorProps {
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Show every character as a dot, and disable IME.
+    pub fn password(mut self, password: bool) -> Self {
+        self.password = password;
+        self
+    }
+
+    /// Maximum count of characters.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+}
+
+/// The value is edited by the user.
+#[derive(Event, Clone)]
+pub struct TextChanged(pub String);
+
+/// `Enter` is pressed in a [`TextInput`].
+#[derive(Event, Clone)]
+pub struct TextSubmitted(pub String);
+
+enum EditorEvent {
+    Changed(String),
+    Submitted(String),
+
+    // clipboard requests, done by the runtime out of the element lock
+    Copy { text: String, cut: bool },
+    Paste,
+}
+
+impl<const MULTILINE: bool> Element for TextEditor<MULTILINE> {
+    type BlankProps = TextEditorProps;
+
+    fn layout<'a, Ch>(&mut self, _: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        setter.set_children(());
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        let canvas = renderer.canvas();
+        let box_styles = &self.styles.box_styles;
+        let content_region = box_styles.draw_border_limited(canvas, draw_region);
+        let (start, end) = content_region;
+
+        let count = canvas.save();
+        box_styles.clip_content(canvas, content_region);
+
+        self.width = (end.0 - start.0).to_physical().max(0.0);
+        let content_origin = SkiaPoint::new(start.0.to_physical(), start.1.to_physical());
+
+        if self.edit.value.is_empty() && self.edit.composition.is_none() {
+            let layout = self.placeholder_layout.get_or_insert_with(|| {
+                let mut styles = self.styles.text.clone();
+                styles.color = Some(StyleColor(self.styles.placeholder.0));
+                TextLayout::new(
+                    &RichText::from(self.placeholder.as_str()),
+                    &styles,
+                    self.direction,
+                )
+            });
+            layout.layout(self.width);
+            layout.paint(canvas, content_origin);
+        }
+
+        let caret_pos = self.caret_display();
+        let selection = self.selection_display();
+        let layout = self.ensure_layout();
+        let caret = layout.caret_rect(caret_pos);
+        let longest_line = layout.longest_line();
+
+        if !MULTILINE {
+            let mut scroll_x = self.scroll_x;
+            if caret.left - scroll_x > self.width {
+                scroll_x = caret.left - self.width;
+            }
+            scroll_x = scroll_x
+                .min(caret.left)
+                .min((longest_line - self.width).max(0.0))
+                .max(0.0);
+            self.scroll_x = scroll_x;
+        }
+        self.origin = content_origin - SkiaPoint::new(self.scroll_x, 0.0);
+
+        let layout = self.layout.as_ref().unwrap();
+        if let Some(range) = selection {
+            let mut paint = Paint::default();
+            paint.set_color(self.styles.selection.0);
+            for rect in layout.rects_for_range(range) {
+                canvas.draw_rect(rect.with_offset(self.origin), &paint);
+            }
+        }
+
+        layout.paint(canvas, self.origin);
+
+        // the candidate window of IME follows the caret
+        if let Some(eh) = self.handle.upgrade() {
+            let caret = caret.with_offset(self.origin);
+            eh.set_ime_caret((self.focused && !self.password).then(|| {
+                (
+                    Point(
+                        Pixel::from_physical(caret.left),
+                        Pixel::from_physical(caret.top),
+                    ),
+                    Point(
+                        Pixel::from_physical(caret.right),
+                        Pixel::from_physical(caret.bottom),
+                    ),
+                )
+            }));
+        }
+
+        let blink_phase = self.blink_start.elapsed().as_millis() / BLINK_INTERVAL.as_millis();
+        if self.focused && blink_phase % 2 == 0 {
+            let mut paint = Paint::default();
+            paint.set_color(self.styles.caret.0);
+            let width = Pixel(1.0).to_physical();
+            canvas.draw_rect(
+                Rect::new(caret.left, caret.top, caret.left + width, caret.bottom)
+                    .with_offset(self.origin),
+                &paint,
+            );
+        }
+
+        canvas.restore_to_count(count);
+        renderer.set_interact_region(draw_region);
+        Ok(())
+    }
+}
+
+impl<'a, Sty, const MULTILINE: bool> UpdateWith<UpdateElement<'a, Self, TextEditorProps, Sty>>
+    for TextEditor<MULTILINE>
+where
+    Sty: StyleContainer,
+{
+    fn create_with(updater: UpdateElement<'a, Self, TextEditorProps, Sty>) -> Self {
+        let handle = updater.handle;
+        let mut editor = TextEditor {
+            handle: Arc::downgrade(handle),
+            runtime: Some(tokio::spawn(runtime::run(
+                Arc::downgrade(handle),
+                handle.event_dispatcher().clone(),
+                handle.global().clone(),
+            ))),
+            edit: EditState::default(),
+            value_prop: None,
+            placeholder: String::new(),
+            password: false,
+            max_length: None,
+            styles: read_styles::<MULTILINE>(updater.styles, None, handle),
+            direction: handle.direction(),
+            layout: None,
+            placeholder_layout: None,
+            width: 0.0,
+            scroll_x: 0.0,
+            origin: SkiaPoint::default(),
+            focused: false,
+            blink_start: Instant::now(),
+        };
+
+        editor.update_with(updater, false);
+        editor
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, TextEditorProps, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        let UpdateElement {
+            props:
+                TextEditorProps {
+                    value,
+                    placeholder,
+                    password,
+                    max_length,
+                },
+            styles,
+            handle,
+        } = updater;
+
+        self.styles = read_styles::<MULTILINE>(styles, Some(&self.styles.box_styles), handle);
+        self.direction = handle.direction();
+        self.placeholder = placeholder;
+        self.password = password;
+        self.max_length = max_length;
+
+        if value != self.value_prop {
+            if let Some(value) = &value {
+                let mut edit = EditState::default();
+                edit.insert(value, MULTILINE, max_length);
+                self.edit.set_value(edit.value);
+            }
+            self.value_prop = value;
+        }
+
+        self.layout = None;
+        self.placeholder_layout = None;
+
+        // box styles cannot be compared
+        false
+    }
+}
+
+impl<const MULTILINE: bool> Drop for TextEditor<MULTILINE> {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            runtime.abort();
+        }
+    }
+}
+
+impl<const MULTILINE: bool> TextEditor<MULTILINE> {
+    /// Replace the value, the caret is moved to the end.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        let mut edit = EditState::default();
+        edit.insert(&value.into(), MULTILINE, self.max_length);
+        self.edit.set_value(edit.value);
+        self.edited();
+    }
+
+    pub fn value(&self) -> &str {
+        &self.edit.value
+    }
+
+    fn edited(&mut self) {
+        self.layout = None;
+        self.caret_moved();
+    }
+
+    fn caret_moved(&mut self) {
+        self.blink_start = Instant::now();
+    }
+
+    fn ensure_layout(&mut self) -> &TextLayout {
+        if self.layout.is_none() {
+            self.layout = Some(TextLayout::new(
+                &self.display_text(),
+                &self.styles.text,
+                self.direction,
+            ));
+        }
+
+        let layout = self.layout.as_mut().unwrap();
+        layout.layout(self.width);
+        layout
+    }
+
+    fn display_text(&self) -> RichText {
+        let mask = |text: &str| match self.password {
+            true => "\u{2022}".repeat(text.chars().count()),
+            false => text.to_owned(),
+        };
+
+        let value = &self.edit.value;
+        match &self.edit.composition {
+            Some(comp) => {
+                let at = self.edit.caret;
+                RichText::new()
+                    .push(mask(&value[..at]))
+                    .push(Span::new(comp.text.as_str()).underline(true))
+                    .push(mask(&value[at..]))
+            }
+            None => RichText::from(mask(value)),
+        }
+    }
+
+    /// Length of `text` in the layout, in UTF-16 code units.
+    fn units(&self, text: &str) -> usize {
+        match self.password {
+            true => text.chars().count(),
+            false => text.encode_utf16().count(),
+        }
+    }
+
+    fn to_display(&self, pos: usize) -> usize {
+        let mut units = self.units(&self.edit.value[..pos]);
+        if let Some(comp) = &self.edit.composition {
+            if pos > self.edit.caret {
+                units += comp.text.encode_utf16().count();
+            }
+        }
+        units
+    }
+
+    fn from_display(&self, mut units: usize) -> usize {
+        let value = &self.edit.value;
+        if let Some(comp) = &self.edit.composition {
+            let at = self.units(&value[..self.edit.caret]);
+            let len = comp.text.encode_utf16().count();
+            if units > at {
+                if units <= at + len {
+                    return self.edit.caret;
+                }
+                units -= len;
+            }
+        }
+
+        let mut counted = 0;
+        for (index, ch) in value.char_indices() {
+            if counted >= units {
+                return index;
+            }
+            counted += if self.password { 1 } else { ch.len_utf16() };
+        }
+        value.len()
+    }
+
+    fn caret_display(&self) -> usize {
+        match &self.edit.composition {
+            Some(Composition { text, cursor }) => {
+                let cursor = cursor.map_or(text.len(), |(_, end)| end);
+                self.units(&self.edit.value[..self.edit.caret])
+                    + text[..cursor].encode_utf16().count()
+            }
+            None => self.to_display(self.edit.caret),
+        }
+    }
+
+    fn selection_display(&self) -> Option<std::ops::Range<usize>> {
+        if !self.edit.has_selection() || self.edit.composition.is_some() {
+            return None;
+        }
+
+        let range = self.edit.selection();
+        Some(self.to_display(range.start)..self.to_display(range.end))
+    }
+
+    /// Position in the value nearest to `point` in the window.
+    fn position_at(&mut self, point: Point) -> usize {
+        let local = SkiaPoint::new(point.0.to_physical(), point.1.to_physical()) - self.origin;
+        let units = self.ensure_layout().position_at(local);
+        self.from_display(units)
+    }
+
+    /// Position in the value on the line `lines` away from the caret.
+    fn vertical(&mut self, lines: f32) -> usize {
+        let caret_pos = self.caret_display();
+        let caret = self.ensure_layout().caret_rect(caret_pos);
+        let target = SkiaPoint::new(caret.left, caret.center_y() + caret.height() * lines);
+        let units = self.ensure_layout().position_at(target);
+        self.from_display(units)
+    }
+
+    fn insert(&mut self, text: &str) -> Option<EditorEvent> {
+        let changed = self.edit.insert(text, MULTILINE, self.max_length);
+        self.changed(changed)
+    }
+
+    /// Delete the selection once it is copied to the clipboard, unless it
+    /// changed meanwhile.
+    fn delete_cut(&mut self, text: &str) -> Option<EditorEvent> {
+        if self.edit.value[self.edit.selection()] != *text {
+            return None;
+        }
+
+        let changed = self.edit.delete_selection();
+        self.changed(changed)
+    }
+
+    fn changed(&mut self, changed: bool) -> Option<EditorEvent> {
+        self.edited();
+        changed.then(|| EditorEvent::Changed(self.edit.value.clone()))
+    }
+
+    fn set_composition(
+        &mut self,
+        text: String,
+        cursor: Option<(usize, usize)>,
+    ) -> Option<EditorEvent> {
+        let changed = self.edit.delete_selection();
+        self.edit.composition = (!text.is_empty()).then_some(Composition { text, cursor });
+        self.changed(changed)
+    }
+
+    fn handle_key(
+        &mut self,
+        key: VirtualKeyCode,
+        modifiers: ModifiersState,
+    ) -> Option<EditorEvent> {
+        use VirtualKeyCode::*;
+
+        if self.edit.composition.is_some() {
+            return None;
+        }
+
+        // arrows move visually, which is backward in right to left text
+        let key = match (key, self.direction) {
+            (Left, Direction::Rtl) => Right,
+            (Right, Direction::Rtl) => Left,
+            _ => key,
+        };
+
+        let shift = modifiers.shift();
+        let word = modifiers.ctrl() || modifiers.alt();
+        let command = modifiers.ctrl() || modifiers.logo();
+        let edit = &mut self.edit;
+        let caret = edit.caret;
+        let collapse = edit.has_selection() && !shift;
+
+        let target = match key {
+            Left if collapse => edit.selection().start,
+            Right if collapse => edit.selection().end,
+            Left if modifiers.logo() => edit.line_start(caret),
+            Right if modifiers.logo() => edit.line_end(caret),
+            Left if word => edit.prev_word(caret),
+            Right if word => edit.next_word(caret),
+            Left => edit.prev_char(caret),
+            Right => edit.next_char(caret),
+            Home if modifiers.ctrl() => 0,
+            End if modifiers.ctrl() => edit.value.len(),
+            Home => edit.line_start(caret),
+            End => edit.line_end(caret),
+            Up if MULTILINE => self.vertical(-1.0),
+            Down if MULTILINE => self.vertical(1.0),
+            Up => 0,
+            Down => edit.value.len(),
+            Back => {
+                let changed = edit.delete_backward(word);
+                return self.changed(changed);
+            }
+            Delete => {
+                let changed = edit.delete_forward(word);
+                return self.changed(changed);
+            }
+            Return | NumpadEnter if MULTILINE => return self.insert("\n"),
+            Return | NumpadEnter => return Some(EditorEvent::Submitted(edit.value.clone())),
+            C | X if command => {
+                if !edit.has_selection() || self.password {
+                    return None;
+                }
+
+                return Some(EditorEvent::Copy {
+                    text: edit.value[edit.selection()].to_owned(),
+                    cut: key == X,
+                });
+            }
+            V if command => return Some(EditorEvent::Paste),
+            A if command => {
+                edit.select_all();
+                self.caret_moved();
+                return None;
+            }
+            _ => return None,
+        };
+
+        self.edit.move_to(target, shift);
+        self.caret_moved();
+        None
+    }
+}
+
+fn read_styles<const MULTILINE: bool>(
+    styles: &impl StyleContainer,
+    previous: Option<&BoxStyles>,
+    handle: &Arc<ElementHandle<TextEditor<MULTILINE>>>,
+) -> TextEditorStyles {
+    let mut styles = TextEditorStyles::read_style(styles);
+    styles.box_styles.apply_direction(handle.direction());
+    styles.box_styles.preload_images(previous, handle);
+    let text = &mut styles.text;
+
+    // the layout inserts characters to break all, which positions in
+    // the value cannot map to
+    text.word_break.0 = if MULTILINE {
+        WordBreak::Normal
+    } else {
+        WordBreak::NoWrap
+    };
+    if !MULTILINE {
+        text.max_lines.0 = None;
+        text.overflow.0 = TextOverflow::Clip;
+    }
+    styles
+}
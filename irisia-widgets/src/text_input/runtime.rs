@@ -0,0 +1,248 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex as SyncMutex, Weak,
+};
+
+use irisia::{
+    application::GlobalContent,
+    clipboard::Clipboard,
+    element::ElementHandle,
+    event::{
+        standard::{
+            Blured, ElementAbandoned, Focused, ImeCommit, ImePreedit, PointerDown, PointerMove,
+            PointerUp,
+        },
+        EventDispatcher,
+    },
+    winit::event::{ElementState, KeyboardInput, ModifiersState},
+    Result, StaticWindowEvent,
+};
+
+use super::{EditorEvent, TextChanged, TextEditor, TextSubmitted, BLINK_INTERVAL};
+
+pub(super) async fn run<const MULTILINE: bool>(
+    eh: Weak<ElementHandle<TextEditor<MULTILINE>>>,
+    ed: EventDispatcher,
+    global: Arc<GlobalContent>,
+) {
+    let win_ed = global.global_event_dispatcher();
+    let focused = AtomicBool::new(false);
+    let modifiers = SyncMutex::new(ModifiersState::empty());
+
+    let pointer = async {
+        loop {
+            let pd = ed.recv_sys::<PointerDown>().await;
+            if !pd.is_current {
+                ed.recv_sys::<PointerUp>().await;
+                continue;
+            }
+
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+            eh.focus();
+
+            let extend = modifiers.lock().unwrap().shift();
+            {
+                let mut el = eh.el_write().await;
+                let pos = el.position_at(pd.position);
+                el.edit.move_to(pos, extend);
+                el.caret_moved();
+            }
+
+            loop {
+                let pm = tokio::select! {
+                    pm = win_ed.recv_sys::<PointerMove>() => pm,
+                    _ = win_ed.recv_sys::<PointerUp>() => break,
+                };
+
+                let mut el = eh.el_write().await;
+                let pos = el.position_at(pm.position);
+                el.edit.move_to(pos, true);
+                el.caret_moved();
+            }
+        }
+    };
+
+    let keyboard = async {
+        let mut lock = win_ed.lock();
+        loop {
+            let event = lock.recv_sys::<StaticWindowEvent>().await;
+            match &event {
+                StaticWindowEvent::ModifiersChanged(state) => {
+                    *modifiers.lock().unwrap() = *state;
+                    continue;
+                }
+                StaticWindowEvent::ReceivedCharacter(_)
+                | StaticWindowEvent::KeyboardInput { .. }
+                    if focused.load(Ordering::Relaxed) => {}
+                _ => continue,
+            }
+
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            let state = *modifiers.lock().unwrap();
+            let result = eh.el_write().await.handle_window_event(event, state);
+            emit_editor_event(&eh, result).await;
+        }
+    };
+
+    let preedit = async {
+        let mut lock = ed.lock();
+        loop {
+            let ImePreedit { text, cursor } = lock.recv_sys::<ImePreedit>().await;
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            let result = eh.el_write().await.set_composition(text, cursor);
+            emit_editor_event(&eh, result).await;
+        }
+    };
+
+    let commit = async {
+        let mut lock = ed.lock();
+        loop {
+            let ImeCommit(text) = lock.recv_sys::<ImeCommit>().await;
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            let result = {
+                let mut el = eh.el_write().await;
+                el.edit.composition = None;
+                el.insert(&text)
+            };
+            emit_editor_event(&eh, result).await;
+        }
+    };
+
+    let focus = async {
+        loop {
+            ed.recv_sys::<Focused>().await;
+            set_focused(&eh, &focused, true).await;
+            ed.recv_sys::<Blured>().await;
+            set_focused(&eh, &focused, false).await;
+        }
+    };
+
+    let blink = async {
+        let mut interval = tokio::time::interval(BLINK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if !focused.load(Ordering::Relaxed) {
+                continue;
+            }
+            match eh.upgrade() {
+                Some(eh) => eh.set_dirty(),
+                None => return,
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = ed.recv_sys::<ElementAbandoned>() => {}
+        _ = async { tokio::join!(pointer, keyboard, preedit, commit, focus, blink) } => {}
+    }
+}
+
+async fn set_focused<const MULTILINE: bool>(
+    eh: &Weak<ElementHandle<TextEditor<MULTILINE>>>,
+    focused: &AtomicBool,
+    value: bool,
+) {
+    focused.store(value, Ordering::Relaxed);
+    let Some(eh) = eh.upgrade() else {
+        return;
+    };
+
+    let mut el = eh.el_write().await;
+    el.focused = value;
+    if !value {
+        el.edit.composition = None;
+        el.edited();
+    }
+    el.caret_moved();
+}
+
+async fn emit_editor_event<const MULTILINE: bool>(
+    eh: &ElementHandle<TextEditor<MULTILINE>>,
+    event: Option<EditorEvent>,
+) {
+    let event = match event {
+        Some(EditorEvent::Copy { text, cut }) => {
+            let copied = {
+                let text = text.clone();
+                with_clipboard(move |clipboard| clipboard.write_text(&text)).await
+            };
+            if let Err(err) = copied {
+                irisia::warning!(target: irisia::log::CLIPBOARD, "cannot copy text: {err:#}");
+                return;
+            }
+
+            if !cut {
+                return;
+            }
+            eh.el_write().await.delete_cut(&text)
+        }
+        Some(EditorEvent::Paste) => match with_clipboard(|clipboard| clipboard.read_text()).await {
+            Ok(Some(text)) => eh.el_write().await.insert(&text),
+            Ok(None) => return,
+            Err(err) => {
+                irisia::warning!(target: irisia::log::CLIPBOARD, "cannot paste text: {err:#}");
+                return;
+            }
+        },
+        event => event,
+    };
+
+    match event {
+        Some(EditorEvent::Changed(value)) => eh.event_dispatcher().emit(TextChanged(value)),
+        Some(EditorEvent::Submitted(value)) => eh.event_dispatcher().emit(TextSubmitted(value)),
+        _ => {}
+    }
+}
+
+/// Platform clipboards may block, so they are used on the blocking
+/// thread pool of tokio.
+async fn with_clipboard<T, F>(f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(Clipboard) -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || f(Clipboard::global())).await?
+}
+
+impl<const MULTILINE: bool> TextEditor<MULTILINE> {
+    fn handle_window_event(
+        &mut self,
+        event: StaticWindowEvent,
+        modifiers: ModifiersState,
+    ) -> Option<EditorEvent> {
+        match event {
+            StaticWindowEvent::ReceivedCharacter(ch) => {
+                // shortcuts, but AltGr comes as Ctrl+Alt on windows and
+                // types characters
+                let shortcut = (modifiers.ctrl() && !modifiers.alt()) || modifiers.logo();
+                if ch.is_control() || shortcut {
+                    return None;
+                }
+                self.insert(ch.encode_utf8(&mut [0; 4]))
+            }
+
+            StaticWindowEvent::KeyboardInput {
+                input:
+                    KeyboardInput {
+                        state: ElementState::Pressed,
+                        virtual_keycode: Some(key),
+                        ..
+                    },
+                ..
+            } => self.handle_key(key, modifiers),
+
+            _ => None,
+        }
+    }
+}
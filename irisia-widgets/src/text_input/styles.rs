@@ -0,0 +1,16 @@
+use irisia::{skia_safe::Color, Style};
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleCaretColor(#[style(default = "Color::BLACK")] pub Color);
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleSelectionColor(
+    #[style(default = "Color::from_argb(0x66, 0x4d, 0x90, 0xfe)")] pub Color,
+);
+
+/// Color of the placeholder, shown when the value is empty.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StylePlaceholderColor(#[style(default = "Color::from_rgb(0x9E, 0x9E, 0x9E)")] pub Color);
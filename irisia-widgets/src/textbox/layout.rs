@@ -0,0 +1,467 @@
+use std::ops::Range;
+
+use irisia::{
+    primitive::Direction,
+    skia_safe::{
+        textlayout::{
+            FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, RectHeightStyle,
+            RectWidthStyle, TextDirection, TextStyle,
+        },
+        Canvas, Point as SkiaPoint, Rect,
+    },
+};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::font::FontRegistry;
+
+use super::{
+    rich_text::{RichText, Span},
+    styles::{LineHeight, TextOverflow, WordBreak},
+    TextBoxStyles,
+};
+
+/// Laid out rich text. Skia has no spacing between paragraphs, so every
+/// paragraph separated by `\n` is a skia paragraph on its own, stacked
+/// vertically. Positions in text are in UTF-16 code units over the whole
+/// text, newlines included, and may differ from byte offsets in the plain
+/// text by characters inserted for laying out.
+pub(crate) struct TextLayout {
+    paragraphs: Vec<HardParagraph>,
+    paragraph_style: ParagraphStyle,
+    spacing: f32,
+    max_lines: Option<usize>,
+    ellipsis: Option<String>,
+    word_break: WordBreak,
+    width: Option<f32>,
+}
+
+struct HardParagraph {
+    // runs with char indices of characters inserted for laying out
+    runs: Vec<(TextStyle, String, Vec<usize>)>,
+    utf16_start: usize,
+    utf16_len: usize,
+    built: Option<(Option<usize>, Paragraph)>,
+    top: f32,
+    visible: bool,
+}
+
+impl TextLayout {
+    pub fn new(text: &RichText, styles: &TextBoxStyles, direction: Direction) -> Self {
+        let line_height = styles.line_height.0;
+        let mut base = super::parse_text_style(styles);
+        apply_line_height(&mut base, line_height);
+
+        let mut paragraph_style = ParagraphStyle::new();
+        paragraph_style
+            .set_text_style(&base)
+            .set_text_align(styles.align.0)
+            .set_text_direction(match direction {
+                Direction::Ltr => TextDirection::LTR,
+                Direction::Rtl => TextDirection::RTL,
+            });
+
+        let mut builder = RunsBuilder {
+            paragraphs: vec![Vec::new()],
+            line_height,
+            break_all: styles.word_break.0 == WordBreak::BreakAll,
+        };
+        for span in text.spans() {
+            builder.push_span(span, &base);
+        }
+
+        let mut utf16_start = 0;
+        let paragraphs = builder
+            .paragraphs
+            .into_iter()
+            .map(|runs| {
+                let utf16_len = runs
+                    .iter()
+                    .map(|(_, text, _)| text.encode_utf16().count())
+                    .sum();
+                let paragraph = HardParagraph {
+                    runs,
+                    utf16_start,
+                    utf16_len,
+                    built: None,
+                    top: 0.0,
+                    visible: false,
+                };
+                utf16_start += utf16_len + 1;
+                paragraph
+            })
+            .collect();
+
+        TextLayout {
+            paragraphs,
+            paragraph_style,
+            spacing: styles.paragraph_spacing.0.to_physical(),
+            max_lines: styles.max_lines.0,
+            ellipsis: match &styles.overflow.0 {
+                TextOverflow::Clip => None,
+                TextOverflow::Ellipsis(e) => Some(e.clone()),
+            },
+            word_break: styles.word_break.0,
+            width: None,
+        }
+    }
+
+    /// Lay out the text in `width`, does nothing if it is laid out in
+    /// the same width already.
+    pub fn layout(&mut self, width: f32) {
+        if self.width == Some(width) {
+            return;
+        }
+        self.width = Some(width);
+
+        let no_wrap = self.word_break == WordBreak::NoWrap;
+        let mut remaining = self.max_lines;
+        let mut top = 0.0;
+
+        FontRegistry::with_font_collection(|font_collection| {
+            for hp in &mut self.paragraphs {
+                hp.visible = remaining != Some(0);
+                if !hp.visible {
+                    continue;
+                }
+
+                // without wrapping, an ellipsis can only end a line cut by the width
+                let max_lines = match (no_wrap, &self.ellipsis) {
+                    (true, Some(_)) => Some(remaining.unwrap_or(1).min(1)),
+                    _ => remaining,
+                };
+
+                let paragraph = hp.build(
+                    font_collection,
+                    &self.paragraph_style,
+                    max_lines,
+                    self.ellipsis.as_deref(),
+                );
+                paragraph.layout(width);
+                if no_wrap && self.ellipsis.is_none() {
+                    let intrinsic = paragraph.max_intrinsic_width().ceil();
+                    if intrinsic > width {
+                        paragraph.layout(intrinsic);
+                    }
+                }
+
+                hp.top = top;
+                top += paragraph.height() + self.spacing;
+                remaining = remaining.map(|r| r.saturating_sub(paragraph.line_number()));
+            }
+        });
+    }
+
+    pub fn paint(&self, canvas: &mut Canvas, origin: SkiaPoint) {
+        for (hp, paragraph) in self.laid_out() {
+            paragraph.paint(canvas, origin + SkiaPoint::new(0.0, hp.top));
+        }
+    }
+
+    /// Position in text nearest to `point`, which is relative to the
+    /// top left corner of the text.
+    pub fn position_at(&self, point: SkiaPoint) -> usize {
+        let found = self
+            .laid_out()
+            .take_while(|(hp, _)| hp.top <= point.y)
+            .last()
+            .or_else(|| self.laid_out().next());
+
+        match found {
+            Some((hp, paragraph)) => {
+                let local = point - SkiaPoint::new(0.0, hp.top);
+                hp.utf16_start + paragraph.get_glyph_position_at_coordinate(local).position as usize
+            }
+            None => 0,
+        }
+    }
+
+    /// Range of the visual line at `pos`, so lines wrapped by the width
+    /// are separated. Trailing spaces of wrapped lines are excluded
+    /// unless `with_spaces`, so that the end is still on the line.
+    pub fn line_range(&self, pos: usize, with_spaces: bool) -> Range<usize> {
+        let found = self
+            .laid_out()
+            .take_while(|(hp, _)| hp.utf16_start <= pos)
+            .last();
+        let Some((hp, paragraph)) = found else {
+            return pos..pos;
+        };
+
+        // indices of line metrics are in UTF-16 code units
+        let local = (pos - hp.utf16_start).min(hp.utf16_len);
+        let lines = paragraph.get_line_metrics();
+        let Some(index) = lines.iter().rposition(|line| line.start_index <= local) else {
+            return hp.utf16_start..hp.utf16_start + hp.utf16_len;
+        };
+
+        let line = &lines[index];
+        let end = if with_spaces || index + 1 == lines.len() {
+            line.end_index
+        } else {
+            line.end_excluding_whitespaces
+        };
+        hp.utf16_start + line.start_index..hp.utf16_start + end.min(hp.utf16_len)
+    }
+
+    /// Rects covering text in `range`, relative to the top left corner
+    /// of the text.
+    pub fn rects_for_range(&self, range: Range<usize>) -> Vec<Rect> {
+        let mut rects = Vec::new();
+        for (hp, paragraph) in self.laid_out() {
+            let start = range.start.max(hp.utf16_start);
+            let end = range.end.min(hp.utf16_start + hp.utf16_len);
+            if start >= end {
+                continue;
+            }
+
+            let local = start - hp.utf16_start..end - hp.utf16_start;
+            rects.extend(
+                paragraph
+                    .get_rects_for_range(local, RectHeightStyle::Tight, RectWidthStyle::Tight)
+                    .into_iter()
+                    .map(|text_box| text_box.rect.with_offset((0.0, hp.top))),
+            );
+        }
+        rects
+    }
+
+    /// Caret before the text at `pos`, a rect with zero width and the
+    /// height of the line, relative to the top left corner of the text.
+    pub fn caret_rect(&self, pos: usize) -> Rect {
+        let found = self
+            .laid_out()
+            .take_while(|(hp, _)| hp.utf16_start <= pos)
+            .last();
+        let Some((hp, paragraph)) = found else {
+            return Rect::new_empty();
+        };
+
+        let local = (pos - hp.utf16_start).min(hp.utf16_len);
+        let rect_of = |range: Range<usize>| {
+            paragraph
+                .get_rects_for_range(range, RectHeightStyle::Max, RectWidthStyle::Tight)
+                .first()
+                .map(|text_box| text_box.rect)
+        };
+
+        // a character takes 2 code units if it is out of the BMP
+        let after = (1..=2)
+            .filter(|n| local + n <= hp.utf16_len)
+            .find_map(|n| rect_of(local..local + n))
+            .map(|rect| (rect.left, rect));
+        let before = || {
+            (1..=2)
+                .filter(|&n| local >= n)
+                .find_map(|n| rect_of(local - n..local))
+                .map(|rect| (rect.right, rect))
+        };
+
+        match after.or_else(before) {
+            Some((x, rect)) => Rect::new(x, rect.top + hp.top, x, rect.bottom + hp.top),
+            None => Rect::from_xywh(0.0, hp.top, 0.0, paragraph.height()),
+        }
+    }
+
+    /// Text in `range`, without characters inserted for laying out.
+    pub fn text(&self, range: Range<usize>) -> String {
+        let mut text = String::new();
+        let mut units = 0;
+        for (ch, inserted) in self.chars() {
+            if range.contains(&units) && !inserted {
+                text.push(ch);
+            }
+            units += ch.len_utf16();
+        }
+        text
+    }
+
+    /// Byte offset in the plain text of position `pos`.
+    pub fn to_text_offset(&self, pos: usize) -> usize {
+        let mut units = 0;
+        let mut offset = 0;
+        for (ch, inserted) in self.chars() {
+            if units >= pos {
+                break;
+            }
+            units += ch.len_utf16();
+            if !inserted {
+                offset += ch.len_utf8();
+            }
+        }
+        offset
+    }
+
+    /// Position of byte offset `offset` in the plain text.
+    pub fn from_text_offset(&self, offset: usize) -> usize {
+        let mut units = 0;
+        let mut bytes = 0;
+        for (ch, inserted) in self.chars() {
+            if !inserted {
+                if bytes >= offset {
+                    break;
+                }
+                bytes += ch.len_utf8();
+            }
+            units += ch.len_utf16();
+        }
+        units
+    }
+
+    /// Height of the laid out text, spacing after the last paragraph
+    /// excluded.
+    pub fn height(&self) -> f32 {
+        self.laid_out()
+            .last()
+            .map_or(0.0, |(hp, paragraph)| hp.top + paragraph.height())
+    }
+
+    /// Width of the longest line.
+    pub fn longest_line(&self) -> f32 {
+        self.laid_out()
+            .map(|(_, paragraph)| paragraph.longest_line())
+            .fold(0.0, f32::max)
+    }
+
+    /// Laid out characters, with whether they are inserted for laying out.
+    fn chars(&self) -> impl Iterator<Item = (char, bool)> + '_ {
+        self.paragraphs
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, hp)| {
+                let newline = (index != 0).then_some(('\n', false));
+                let runs = hp.runs.iter().flat_map(move |(_, run, inserted)| {
+                    run.chars()
+                        .enumerate()
+                        .map(move |(index, ch)| (ch, inserted.binary_search(&index).is_ok()))
+                });
+                newline.into_iter().chain(runs)
+            })
+    }
+
+    fn laid_out(&self) -> impl Iterator<Item = (&HardParagraph, &Paragraph)> {
+        self.paragraphs.iter().filter_map(|hp| match &hp.built {
+            Some((_, paragraph)) if hp.visible => Some((hp, paragraph)),
+            _ => None,
+        })
+    }
+}
+
+impl HardParagraph {
+    fn build(
+        &mut self,
+        font_collection: &FontCollection,
+        style: &ParagraphStyle,
+        max_lines: Option<usize>,
+        ellipsis: Option<&str>,
+    ) -> &mut Paragraph {
+        if !matches!(&self.built, Some((built_max, _)) if *built_max == max_lines) {
+            let mut style = style.clone();
+            style.set_max_lines(max_lines);
+            if let (Some(_), Some(ellipsis)) = (max_lines, ellipsis) {
+                style.set_ellipsis(ellipsis);
+            }
+
+            let mut builder = ParagraphBuilder::new(&style, font_collection.clone());
+            for (text_style, text, _) in &self.runs {
+                builder.push_style(text_style);
+                builder.add_text(text);
+                builder.pop();
+            }
+            self.built = Some((max_lines, builder.build()));
+        }
+
+        &mut self.built.as_mut().unwrap().1
+    }
+}
+
+/// Flattens spans into runs of one text style, split into paragraphs.
+struct RunsBuilder {
+    paragraphs: Vec<Vec<(TextStyle, String, Vec<usize>)>>,
+    line_height: LineHeight,
+    break_all: bool,
+}
+
+impl RunsBuilder {
+    fn push_span(&mut self, span: &Span, parent: &TextStyle) {
+        let mut style = span.style.apply(parent);
+        apply_line_height(&mut style, self.line_height);
+
+        for (index, text) in span.text.split('\n').enumerate() {
+            if index != 0 {
+                self.paragraphs.push(Vec::new());
+            }
+            if text.is_empty() {
+                continue;
+            }
+
+            let (text, inserted) = if self.break_all {
+                break_all(text)
+            } else {
+                (text.to_owned(), Vec::new())
+            };
+            self.paragraphs
+                .last_mut()
+                .unwrap()
+                .push((style.clone(), text, inserted));
+        }
+
+        for child in &span.children {
+            self.push_span(child, &style);
+        }
+    }
+}
+
+fn apply_line_height(style: &mut TextStyle, line_height: LineHeight) {
+    let height = match line_height {
+        LineHeight::Normal => return,
+        LineHeight::Scale(scale) => scale,
+        LineHeight::Fixed(height) => height.to_physical() / style.font_size(),
+    };
+
+    style.set_height(height).set_height_override(true);
+}
+
+/// Allow breaking lines between any two grapheme clusters by inserting
+/// zero width spaces, skia has no option for it. Returns the text with
+/// char indices of the inserted spaces.
+fn break_all(text: &str) -> (String, Vec<usize>) {
+    let mut output = String::with_capacity(text.len() * 2);
+    let mut inserted = Vec::new();
+    let mut chars = 0;
+
+    for grapheme in text.graphemes(true) {
+        let joined = match (output.chars().next_back(), grapheme.chars().next()) {
+            (Some(prev), Some(next)) => is_joining(prev) && is_joining(next),
+            _ => true,
+        };
+
+        // letters of joining scripts change their shapes when separated
+        if !joined {
+            inserted.push(chars);
+            output.push('\u{200B}');
+            chars += 1;
+        }
+
+        output.push_str(grapheme);
+        chars += grapheme.chars().count();
+    }
+
+    (output, inserted)
+}
+
+/// Whether `ch` is of a script whose letters join their neighbours.
+fn is_joining(ch: char) -> bool {
+    matches!(ch,
+        '\u{0600}'..='\u{077F}' // arabic, syriac
+        | '\u{07C0}'..='\u{07FF}' // n'ko
+        | '\u{0840}'..='\u{086F}' // mandaic, syriac
+        | '\u{08A0}'..='\u{08FF}' // arabic
+        | '\u{1800}'..='\u{18AF}' // mongolian
+        | '\u{A840}'..='\u{A87F}' // phags-pa
+        | '\u{FB50}'..='\u{FDFF}' // arabic presentation forms
+        | '\u{FE70}'..='\u{FEFC}'
+        | '\u{10AC0}'..='\u{10AFF}' // manichaean
+        | '\u{10F30}'..='\u{10F6F}' // sogdian
+    )
+}
@@ -1,54 +1,262 @@
-use std::ops::Range;
+use std::{
+    ops::Range,
+    sync::{Arc, Weak},
+    time::Duration,
+};
 
 use irisia::{
-    primitive::Region,
-    skia_safe::{Color4f, ColorSpace, Paint},
-};
-use irisia_core::{
-    element::Element,
+    element::{
+        ChildrenNodes, ChildrenSetter, Element, ElementHandle, RenderElement, UpdateElement,
+    },
+    primitive::{Direction, Pixel, Point, Region},
     skia_safe::{
-        font_style::Width,
-        textlayout::{FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, TextStyle},
-        Color, FontMgr, FontStyle, Point as SkiaPoint,
+        font_style::Width, textlayout::TextStyle, Color, FontStyle, Paint, Point as SkiaPoint,
     },
     style::{StyleColor, StyleContainer},
-    StyleReader,
+    Result, StyleReader, UpdateWith,
 };
 use styles::*;
 
-use crate::box_styles::BoxStyleRenderer;
+use crate::{box_styles::BoxStyles, font::FontRegistry};
+
+use self::{
+    layout::TextLayout,
+    selection::{SelectionRtMgr, TextSelection},
+};
 
-use self::selection::SelectionRtMgr;
+pub use self::rich_text::{RichText, Span, SpanStyle};
 
+pub(crate) mod layout;
+pub(crate) mod navigation;
+mod rich_text;
 mod selection;
 pub mod styles;
 
+/// Shows rich text. Spans of the text can override the styles of the
+/// text box, see [`Span`] and [`rich_text!`](crate::rich_text).
+///
+/// With `user_select`, text can be selected by pointer or keyboard once
+/// the text box is focused, and copied with `Ctrl+C`.
 pub struct TextBox {
-    font_collection: FontCollection,
-    paragraph: Option<Paragraph>,
-    previous_state: Option<OwnedState>,
-    selection: Option<Range<usize>>,
+    text: RichText,
+    plain_text: String,
+    selection: Option<TextSelection>,
+    user_select: bool,
+    styles: TextBoxStyles,
+    box_styles: BoxStyles,
+    direction: Direction,
+    layout: Option<TextLayout>,
+    content_origin: Point,
     selection_rt_mgr: SelectionRtMgr,
+    handle: Weak<ElementHandle<Self>>,
 }
 
-#[derive(StyleReader, PartialEq)]
-struct TextBoxStyles {
-    font_size: StyleFontSize,
-    slant: StyleFontSlant,
-    weight: StyleFontWeight,
-    color: Option<StyleColor>,
+#[derive(StyleReader, Clone, PartialEq)]
+pub(crate) struct TextBoxStyles {
+    pub font_family: StyleFontFamily,
+    pub font_size: StyleFontSize,
+    pub slant: StyleFontSlant,
+    pub weight: StyleFontWeight,
+    pub color: Option<StyleColor>,
+    pub align: StyleTextAlign,
+    pub line_height: StyleLineHeight,
+    pub paragraph_spacing: StyleParagraphSpacing,
+    pub max_lines: StyleMaxLines,
+    pub overflow: StyleTextOverflow,
+    pub word_break: StyleWordBreak,
 }
 
 #[derive(Default)]
-pub struct Props<'a> {
-    pub text: &'a str,
+pub struct TextBoxProps {
+    pub text: RichText,
     pub user_select: bool,
 }
 
-#[derive(PartialEq)]
-struct OwnedState {
-    text: String,
-    styles: TextBoxStyles,
-    user_select: bool,
-    drawing_region: Region,
+impl TextBoxProps {
+    pub fn text(mut self, text: impl Into<RichText>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    pub fn user_select(mut self, user_select: bool) -> Self {
+        self.user_select = user_select;
+        self
+    }
+}
+
+impl Element for TextBox {
+    type BlankProps = TextBoxProps;
+
+    fn layout<'a, Ch>(&mut self, draw_region: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        setter.set_children(());
+
+        // the height fitting the text, for containers sizing by content
+        let (start, end) = self.box_styles.content_region(draw_region);
+        let layout = self.text_layout(end.0 - start.0);
+        let height = Pixel::from_physical(layout.height());
+        if let Some(handle) = self.handle.upgrade() {
+            let reduction = (draw_region.1 .1 - draw_region.0 .1) - (end.1 - start.1);
+            handle.set_preferred_height(Some(height + reduction));
+        }
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        let canvas = renderer.canvas();
+        let content_region = self.box_styles.draw_border_limited(canvas, draw_region);
+        let (start, end) = content_region;
+
+        let selection = self.selection();
+        self.content_origin = start;
+        let origin = SkiaPoint::new(start.0.to_physical(), start.1.to_physical());
+        let count = canvas.save();
+        self.box_styles.clip_content(canvas, content_region);
+
+        // laid out already unless the region changed after layout
+        let layout = self.text_layout(end.0 - start.0);
+
+        if let Some(range) = selection {
+            let range = layout.from_text_offset(range.start)..layout.from_text_offset(range.end);
+            let mut paint = Paint::default();
+            paint.set_color(Color::from_argb(0x66, 0x4d, 0x90, 0xfe));
+
+            for rect in layout.rects_for_range(range) {
+                canvas.draw_rect(rect.with_offset(origin), &paint);
+            }
+        }
+
+        layout.paint(canvas, origin);
+        canvas.restore_to_count(count);
+
+        renderer.set_interact_region(draw_region);
+        Ok(())
+    }
+}
+
+impl<'a, Sty> UpdateWith<UpdateElement<'a, Self, TextBoxProps, Sty>> for TextBox
+where
+    Sty: StyleContainer,
+{
+    fn create_with(updater: UpdateElement<'a, Self, TextBoxProps, Sty>) -> Self {
+        let mut text_box = TextBox {
+            text: RichText::new(),
+            plain_text: String::new(),
+            selection: None,
+            user_select: false,
+            styles: TextBoxStyles::read_style(updater.styles),
+            // read by `update_with`, which preloads background images
+            box_styles: BoxStyles::read_style(&()),
+            direction: Direction::Ltr,
+            layout: None,
+            content_origin: Point::default(),
+            selection_rt_mgr: SelectionRtMgr::new(),
+            handle: Arc::downgrade(updater.handle),
+        };
+
+        text_box.update_with(updater, false);
+        text_box
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, TextBoxProps, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        let UpdateElement {
+            props: TextBoxProps { text, user_select },
+            styles,
+            handle,
+        } = updater;
+
+        let new_styles = TextBoxStyles::read_style(styles);
+        let direction = handle.direction();
+        if self.text != text {
+            self.plain_text = text.plain_text();
+            self.selection = None;
+        }
+        if self.text != text || self.styles != new_styles || self.direction != direction {
+            self.text = text;
+            self.styles = new_styles;
+            self.direction = direction;
+            self.layout = None;
+        }
+        let mut box_styles = BoxStyles::read_style(styles);
+        box_styles.apply_direction(direction);
+        box_styles.preload_images(Some(&self.box_styles), handle);
+        self.box_styles = box_styles;
+
+        if self.user_select != user_select {
+            if user_select {
+                self.selection_rt_mgr.start_runtime(handle);
+            } else {
+                self.selection_rt_mgr.stop_runtime();
+                self.selection = None;
+            }
+            self.user_select = user_select;
+        }
+
+        // box styles cannot be compared
+        false
+    }
+}
+
+impl TextBox {
+    /// Selected range in bytes of the plain text, see
+    /// [`RichText::plain_text`]. `None` if nothing is selected.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        self.selection
+            .map(|sel| sel.range())
+            .filter(|range| !range.is_empty())
+    }
+
+    /// Select `range` in bytes of the plain text, or clear the selection
+    /// with `None`. The range is clamped to the text and to char
+    /// boundaries.
+    pub fn set_selection(&mut self, range: Option<Range<usize>>) {
+        self.selection = range.map(|range| TextSelection {
+            anchor: navigation::floor_boundary(&self.plain_text, range.start),
+            focus: navigation::floor_boundary(&self.plain_text, range.end),
+        });
+    }
+
+    pub fn select_all(&mut self) {
+        self.set_selection(Some(0..self.plain_text.len()));
+    }
+
+    /// Text selected by the user, `None` if nothing is selected.
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selection().map(|range| &self.plain_text[range])
+    }
+
+    /// Text laid out at `width`, kept until the text or styles change.
+    fn text_layout(&mut self, width: Pixel) -> &mut TextLayout {
+        let layout = self
+            .layout
+            .get_or_insert_with(|| TextLayout::new(&self.text, &self.styles, self.direction));
+        layout.layout(width.to_physical().max(0.0));
+        layout
+    }
+}
+
+fn parse_text_style(style: &TextBoxStyles) -> TextStyle {
+    let mut text_style = TextStyle::new();
+    let families = FontRegistry::resolve(style.font_family.0.as_deref());
+    if !families.is_empty() {
+        text_style.set_font_families(&families);
+    }
+    text_style
+        .set_font_style(FontStyle::new(style.weight.0, Width::NORMAL, style.slant.0))
+        .set_font_size(style.font_size.0.to_physical())
+        .set_color(match &style.color {
+            Some(c) => c.0,
+            None => Color::BLACK,
+        });
+    text_style
 }
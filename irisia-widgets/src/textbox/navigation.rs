@@ -0,0 +1,176 @@
+//! Moving positions in plain text. Positions are byte offsets, always
+//! on char boundaries.
+
+use std::ops::Range;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Space,
+    Word,
+    Punct,
+}
+
+pub(crate) fn prev_char(text: &str, pos: usize) -> usize {
+    text[..pos]
+        .char_indices()
+        .next_back()
+        .map_or(0, |(index, _)| index)
+}
+
+pub(crate) fn next_char(text: &str, pos: usize) -> usize {
+    text[pos..]
+        .chars()
+        .next()
+        .map_or(pos, |ch| pos + ch.len_utf8())
+}
+
+/// Start of the word before `pos`, skipping spaces between.
+pub(crate) fn prev_word(text: &str, pos: usize) -> usize {
+    let mut chars = text[..pos].char_indices().rev().peekable();
+    while chars
+        .next_if(|(_, ch)| class_of(*ch) == CharClass::Space)
+        .is_some()
+    {}
+
+    let Some(&(mut start, first)) = chars.peek() else {
+        return 0;
+    };
+    let class = class_of(first);
+    for (index, ch) in chars {
+        if class_of(ch) != class {
+            break;
+        }
+        start = index;
+    }
+    start
+}
+
+/// Start of the word after `pos`, skipping the rest of the current
+/// word and spaces after it.
+pub(crate) fn next_word(text: &str, pos: usize) -> usize {
+    let mut chars = text[pos..].char_indices().peekable();
+    if let Some(&(_, first)) = chars.peek() {
+        let class = class_of(first);
+        if class != CharClass::Space {
+            while chars.next_if(|(_, ch)| class_of(*ch) == class).is_some() {}
+        }
+    }
+    while chars
+        .next_if(|(_, ch)| class_of(*ch) == CharClass::Space)
+        .is_some()
+    {}
+
+    chars.peek().map_or(text.len(), |(index, _)| pos + index)
+}
+
+/// Word, run of spaces or run of punctuations containing the character
+/// after `pos`, or before `pos` at the end of a line.
+pub(crate) fn word_at(text: &str, pos: usize) -> Range<usize> {
+    let ch = match text[pos..].chars().next() {
+        Some(ch) if ch != '\n' => ch,
+        _ => match text[..pos].chars().next_back() {
+            Some(ch) if ch != '\n' => ch,
+            _ => return pos..pos,
+        },
+    };
+    let class = class_of(ch);
+    let same = |ch: char| ch != '\n' && class_of(ch) == class;
+
+    let start = text[..pos]
+        .char_indices()
+        .rev()
+        .take_while(|(_, ch)| same(*ch))
+        .last()
+        .map_or(pos, |(index, _)| index);
+    let end = text[pos..]
+        .char_indices()
+        .find(|(_, ch)| !same(*ch))
+        .map_or(text.len(), |(index, _)| pos + index);
+    start..end
+}
+
+pub(crate) fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map_or(0, |index| index + 1)
+}
+
+pub(crate) fn line_end(text: &str, pos: usize) -> usize {
+    text[pos..]
+        .find('\n')
+        .map_or(text.len(), |index| pos + index)
+}
+
+/// Largest char boundary not greater than `pos`.
+pub(crate) fn floor_boundary(text: &str, pos: usize) -> usize {
+    let mut pos = pos.min(text.len());
+    while !text.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    pos
+}
+
+fn class_of(ch: char) -> CharClass {
+    if ch.is_whitespace() {
+        CharClass::Space
+    } else if ch.is_alphanumeric() || ch == '_' {
+        CharClass::Word
+    } else {
+        CharClass::Punct
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `ö` takes 2 bytes
+    const TEXT: &str = "hello, wörld  foo";
+
+    #[test]
+    fn chars_keep_boundaries() {
+        // `é` takes 2 bytes and `😀` takes 4
+        let text = "aé😀b";
+        assert_eq!(next_char(text, 0), 1);
+        assert_eq!(next_char(text, 1), 3);
+        assert_eq!(next_char(text, 3), 7);
+        assert_eq!(next_char(text, 8), 8);
+        assert_eq!(prev_char(text, 7), 3);
+        assert_eq!(prev_char(text, 3), 1);
+        assert_eq!(prev_char(text, 0), 0);
+    }
+
+    #[test]
+    fn words() {
+        assert_eq!(next_word(TEXT, 0), 5);
+        assert_eq!(next_word(TEXT, 5), 7);
+        assert_eq!(next_word(TEXT, 7), 15);
+        assert_eq!(next_word(TEXT, 15), TEXT.len());
+
+        assert_eq!(prev_word(TEXT, TEXT.len()), 15);
+        assert_eq!(prev_word(TEXT, 15), 7);
+        assert_eq!(prev_word(TEXT, 7), 5);
+        assert_eq!(prev_word(TEXT, 5), 0);
+    }
+
+    #[test]
+    fn word_at_position() {
+        assert_eq!(word_at(TEXT, 8), 7..13);
+        assert_eq!(word_at(TEXT, 13), 13..15);
+        assert_eq!(word_at(TEXT, TEXT.len()), 15..TEXT.len());
+        assert_eq!(word_at("ab\n\ncd", 3), 3..3);
+    }
+
+    #[test]
+    fn lines() {
+        let text = "ab\ncd";
+        assert_eq!(line_start(text, 1), 0);
+        assert_eq!(line_end(text, 1), 2);
+        assert_eq!(line_start(text, 4), 3);
+        assert_eq!(line_end(text, 4), 5);
+    }
+
+    #[test]
+    fn floor_to_boundary() {
+        assert_eq!(floor_boundary("aé", 2), 1);
+        assert_eq!(floor_boundary("aé", 10), 3);
+    }
+}
@@ -0,0 +1,227 @@
+use std::sync::Arc;
+
+use irisia::{
+    primitive::Pixel,
+    skia_safe::{
+        font_style::{Slant, Weight, Width},
+        textlayout::{TextDecoration, TextStyle},
+        Color, Color4f, FontStyle, Paint,
+    },
+};
+
+use crate::font::FontRegistry;
+
+/// Compose a [`RichText`] from anything converting into [`Span`].
+///
+/// ```ignore
+/// rich_text!["Hello, ", Span::new("world").bold().color(Color::RED), "!"]
+/// ```
+#[macro_export]
+macro_rules! rich_text {
+    ($($span:expr),* $(,)?) => {
+        $crate::textbox::RichText::new() $(.push($span))*
+    };
+}
+
+/// Text made of spans with their own inline styles.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RichText {
+    spans: Vec<Span>,
+}
+
+impl RichText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(mut self, span: impl Into<Span>) -> Self {
+        self.spans.push(span.into());
+        self
+    }
+
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// Text of all spans without styles, in the order they are laid out.
+    pub fn plain_text(&self) -> String {
+        let mut text = String::new();
+        for span in &self.spans {
+            span.collect_text(&mut text);
+        }
+        text
+    }
+}
+
+impl From<Span> for RichText {
+    fn from(value: Span) -> Self {
+        RichText::new().push(value)
+    }
+}
+
+impl From<&str> for RichText {
+    fn from(value: &str) -> Self {
+        RichText::new().push(value)
+    }
+}
+
+impl From<String> for RichText {
+    fn from(value: String) -> Self {
+        RichText::new().push(value)
+    }
+}
+
+/// A piece of text with inline styles. Text of the span is laid out
+/// before its children, and children inherit the styles it sets.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub style: SpanStyle,
+    pub children: Vec<Span>,
+}
+
+/// Inline styles of a span. Fields not set are inherited from the parent
+/// span, or from styles of the text element for top level spans.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpanStyle {
+    pub font_family: Option<Arc<str>>,
+    pub font_size: Option<Pixel>,
+    pub weight: Option<Weight>,
+    pub slant: Option<Slant>,
+    pub color: Option<Color>,
+    pub background: Option<Color>,
+    pub underline: Option<bool>,
+    pub strikethrough: Option<bool>,
+    pub letter_spacing: Option<Pixel>,
+}
+
+impl Span {
+    pub fn new(text: impl Into<String>) -> Self {
+        Span {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn child(mut self, span: impl Into<Span>) -> Self {
+        self.children.push(span.into());
+        self
+    }
+
+    pub fn font_family(mut self, family: &str) -> Self {
+        self.style.font_family = Some(family.into());
+        self
+    }
+
+    pub fn font_size(mut self, size: Pixel) -> Self {
+        self.style.font_size = Some(size);
+        self
+    }
+
+    pub fn weight(mut self, weight: Weight) -> Self {
+        self.style.weight = Some(weight);
+        self
+    }
+
+    pub fn bold(self) -> Self {
+        self.weight(Weight::BOLD)
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.style.slant = Some(Slant::Italic);
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.style.color = Some(color);
+        self
+    }
+
+    pub fn background(mut self, color: Color) -> Self {
+        self.style.background = Some(color);
+        self
+    }
+
+    pub fn underline(mut self, underline: bool) -> Self {
+        self.style.underline = Some(underline);
+        self
+    }
+
+    pub fn strikethrough(mut self, strikethrough: bool) -> Self {
+        self.style.strikethrough = Some(strikethrough);
+        self
+    }
+
+    pub fn letter_spacing(mut self, spacing: Pixel) -> Self {
+        self.style.letter_spacing = Some(spacing);
+        self
+    }
+
+    fn collect_text(&self, text: &mut String) {
+        text.push_str(&self.text);
+        for child in &self.children {
+            child.collect_text(text);
+        }
+    }
+}
+
+impl From<&str> for Span {
+    fn from(value: &str) -> Self {
+        Span::new(value)
+    }
+}
+
+impl From<String> for Span {
+    fn from(value: String) -> Self {
+        Span::new(value)
+    }
+}
+
+impl SpanStyle {
+    /// Text style of a span whose parent has the style `parent`.
+    pub(super) fn apply(&self, parent: &TextStyle) -> TextStyle {
+        let mut style = parent.clone();
+
+        if let Some(family) = &self.font_family {
+            style.set_font_families(&FontRegistry::resolve(Some(&**family)));
+        }
+
+        if let Some(size) = self.font_size {
+            style.set_font_size(size.to_physical());
+        }
+
+        if self.weight.is_some() || self.slant.is_some() {
+            let font_style = parent.font_style();
+            style.set_font_style(FontStyle::new(
+                self.weight.unwrap_or_else(|| font_style.weight()),
+                Width::NORMAL,
+                self.slant.unwrap_or_else(|| font_style.slant()),
+            ));
+        }
+
+        if let Some(color) = self.color {
+            style.set_color(color);
+        }
+
+        if let Some(color) = self.background {
+            style.set_background_color(&Paint::new(Color4f::from(color), None));
+        }
+
+        if self.underline.is_some() || self.strikethrough.is_some() {
+            let mut decoration = parent.decoration_type();
+            if let Some(underline) = self.underline {
+                decoration.set(TextDecoration::UNDERLINE, underline);
+            }
+            if let Some(strikethrough) = self.strikethrough {
+                decoration.set(TextDecoration::LINE_THROUGH, strikethrough);
+            }
+            style.set_decoration_type(decoration);
+        }
+
+        if let Some(spacing) = self.letter_spacing {
+            style.set_letter_spacing(spacing.to_physical());
+        }
+
+        style
+    }
+}
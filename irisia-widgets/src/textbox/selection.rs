@@ -1,56 +1,71 @@
 use std::{
     ops::Range,
-    sync::{Arc, Mutex as SyncMutex},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex as SyncMutex, Weak,
+    },
+    time::{Duration, Instant},
 };
 
 use irisia::{
-    event::standard::{PointerEntered, PointerOut},
-    primitive::Point,
-    skia_safe::textlayout::Paragraph,
-    winit::window::CursorIcon,
-    WinitWindow,
-};
-use irisia_core::{
-    element::EventHandle,
+    application::GlobalContent,
+    clipboard::Clipboard,
+    element::ElementHandle,
     event::{
-        standard::Blured,
-        standard::{PointerDown, PointerMove, PointerUp},
+        standard::{
+            Blured, ElementAbandoned, Focused, PointerDown, PointerEntered, PointerMove,
+            PointerOut, PointerUp,
+        },
         EventDispatcher,
     },
+    primitive::{Direction, Pixel, Point},
     skia_safe::Point as SkiaPoint,
+    winit::{
+        event::{ElementState, KeyboardInput, ModifiersState, VirtualKeyCode},
+        window::CursorIcon,
+    },
+    StaticWindowEvent, WinitWindow,
 };
 use tokio::{sync::Mutex, task::JoinHandle};
 
-pub(super) struct SelectionRtMgr {
-    window: Arc<WinitWindow>,
-    win_ed: EventDispatcher,
-    eh: EventHandle,
-    sel: Arc<SyncMutex<Selection>>,
-    handle: Option<JoinHandle<Option<()>>>,
+use super::{navigation, TextBox};
+
+/// Clicks within the time and distance count as a double or triple click.
+const MULTI_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+const MULTI_CLICK_DISTANCE: Pixel = Pixel(4.0);
+
+/// Selection in byte offsets of the plain text. The anchor stays when
+/// the selection is extended, the focus moves.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) struct TextSelection {
+    pub anchor: usize,
+    pub focus: usize,
 }
 
-#[derive(Default)]
-struct Selection {
-    cursor: Option<(Point, Point)>,
+impl TextSelection {
+    pub fn range(&self) -> Range<usize> {
+        self.anchor.min(self.focus)..self.anchor.max(self.focus)
+    }
+}
+
+pub(super) struct SelectionRtMgr {
+    handle: Option<JoinHandle<()>>,
 }
 
 impl SelectionRtMgr {
-    pub fn new(window: Arc<WinitWindow>, win_ed: EventDispatcher, eh: EventHandle) -> Self {
-        Self {
-            window,
-            win_ed,
-            eh,
-            sel: Default::default(),
-            handle: None,
-        }
+    pub fn new() -> Self {
+        Self { handle: None }
     }
 
-    pub fn start_runtime(&mut self) {
-        self.handle = Some(self.eh.spawn(start(
-            self.eh.clone(),
-            self.sel.clone(),
-            self.window.clone(),
-            self.win_ed.clone(),
+    pub fn start_runtime(&mut self, eh: &Arc<ElementHandle<TextBox>>) {
+        if self.handle.is_some() {
+            return;
+        }
+
+        self.handle = Some(tokio::spawn(start(
+            Arc::downgrade(eh),
+            eh.event_dispatcher().clone(),
+            eh.global().clone(),
         )));
     }
 
@@ -58,45 +73,151 @@ impl SelectionRtMgr {
         if let Some(h) = self.handle.take() {
             h.abort();
         }
-        self.sel.lock().unwrap().cursor = None;
-    }
-
-    pub fn get_selection_range(
-        &self,
-        cursor_offset: Point,
-        paragraph: &Option<Paragraph>,
-        s: &str,
-    ) -> Option<Range<usize>> {
-        let two_point = {
-            let (start, end) = self.sel.lock().unwrap().cursor?;
-            let checked_sub = |point: Point| {
-                Point(
-                    (point.0 - cursor_offset.0).max(0.0),
-                    (point.1 - cursor_offset.1).max(0.0),
-                )
-            };
-            (checked_sub(start), checked_sub(end))
+    }
+}
+
+impl Drop for SelectionRtMgr {
+    fn drop(&mut self) {
+        self.stop_runtime();
+    }
+}
+
+/// Counts clicks at about the same place in quick succession, cycling
+/// through single, double and triple clicks.
+#[derive(Default)]
+struct ClickCounter {
+    last: Option<(Instant, Point)>,
+    count: u32,
+}
+
+impl ClickCounter {
+    fn click(&mut self, position: Point) -> u32 {
+        let now = Instant::now();
+        let continued = matches!(
+            self.last,
+            Some((time, last))
+                if now - time <= MULTI_CLICK_INTERVAL
+                    && (position.0 - last.0).0.abs() <= MULTI_CLICK_DISTANCE.0
+                    && (position.1 - last.1).0.abs() <= MULTI_CLICK_DISTANCE.0
+        );
+
+        self.count = if continued { self.count % 3 + 1 } else { 1 };
+        self.last = Some((now, position));
+        self.count
+    }
+}
+
+impl TextBox {
+    /// Byte offset in the plain text nearest to `point`, `None` if the
+    /// text is not laid out yet.
+    fn text_offset_at(&self, point: Point) -> Option<usize> {
+        let layout = self.layout.as_ref()?;
+        let pos = layout.position_at(SkiaPoint::new(
+            (point.0 - self.content_origin.0).to_physical(),
+            (point.1 - self.content_origin.1).to_physical(),
+        ));
+        Some(layout.to_text_offset(pos))
+    }
+
+    /// Range selected by clicking `clicks` times at `offset`: nothing,
+    /// a word or a visual line.
+    fn click_unit(&self, offset: usize, clicks: u32) -> Range<usize> {
+        match clicks {
+            1 => offset..offset,
+            2 => navigation::word_at(&self.plain_text, offset),
+            _ => self.visual_line(offset, true),
+        }
+    }
+
+    /// Visual line at `offset`, see [`TextLayout::line_range`]. Lines
+    /// between newlines are used if the text is not laid out yet.
+    ///
+    /// [`TextLayout::line_range`]: super::layout::TextLayout::line_range
+    fn visual_line(&self, offset: usize, with_spaces: bool) -> Range<usize> {
+        let Some(layout) = &self.layout else {
+            let text = &self.plain_text;
+            return navigation::line_start(text, offset)..navigation::line_end(text, offset);
+        };
+
+        let range = layout.line_range(layout.from_text_offset(offset), with_spaces);
+        layout.to_text_offset(range.start)..layout.to_text_offset(range.end)
+    }
+
+    /// Select from `origin` to `unit`, both of them are kept in the
+    /// selection entirely.
+    fn select_units(&mut self, origin: Range<usize>, unit: Range<usize>) {
+        self.selection = Some(if unit.start < origin.start {
+            TextSelection {
+                anchor: origin.end,
+                focus: unit.start,
+            }
+        } else {
+            TextSelection {
+                anchor: origin.start,
+                focus: unit.end.max(origin.end),
+            }
+        });
+    }
+
+    /// Move the focus of the selection by a key, keeps the anchor if
+    /// shift is pressed.
+    fn move_selection(&mut self, key: VirtualKeyCode, modifiers: ModifiersState) {
+        // arrows move visually, which is backward in right to left text
+        let key = match (key, self.direction) {
+            (VirtualKeyCode::Left, Direction::Rtl) => VirtualKeyCode::Right,
+            (VirtualKeyCode::Right, Direction::Rtl) => VirtualKeyCode::Left,
+            _ => key,
         };
-        let paragraph = paragraph.as_ref()?;
 
-        let get_word_b = |point: Point| {
-            let pos = paragraph.get_glyph_position_at_coordinate(SkiaPoint::new(point.0, point.1));
+        let text = &self.plain_text;
+        let TextSelection { anchor, focus } = self.selection.unwrap_or(TextSelection {
+            anchor: 0,
+            focus: 0,
+        });
+        let extend = modifiers.shift();
+        let word = modifiers.ctrl() || modifiers.alt();
+        let collapse = anchor != focus && !extend;
 
-            glyph_index_to_byte_index(s, pos.position as _)
+        let target = match key {
+            VirtualKeyCode::Left if collapse => anchor.min(focus),
+            VirtualKeyCode::Right if collapse => anchor.max(focus),
+            VirtualKeyCode::Left if word => navigation::prev_word(text, focus),
+            VirtualKeyCode::Right if word => navigation::next_word(text, focus),
+            VirtualKeyCode::Left => navigation::prev_char(text, focus),
+            VirtualKeyCode::Right => navigation::next_char(text, focus),
+            VirtualKeyCode::Home if modifiers.ctrl() => 0,
+            VirtualKeyCode::End if modifiers.ctrl() => text.len(),
+            VirtualKeyCode::Home => self.visual_line(focus, false).start,
+            VirtualKeyCode::End => self.visual_line(focus, false).end,
+            VirtualKeyCode::Up => self.vertical(focus, -1.0),
+            VirtualKeyCode::Down => self.vertical(focus, 1.0),
+            _ => return,
         };
-        let pos1 = get_word_b(two_point.0)?;
-        let pos2 = get_word_b(two_point.1)?;
 
-        Some(pos1.min(pos2)..pos1.max(pos2))
+        self.selection = Some(TextSelection {
+            anchor: if extend { anchor } else { target },
+            focus: target,
+        });
     }
-}
 
-fn glyph_index_to_byte_index(s: &str, index: usize) -> Option<usize> {
-    s.char_indices()
-        .map(|ch| ch.0)
-        .chain(std::iter::once(s.len()))
-        .take(index + 1)
-        .last()
+    /// Offset in the line above or below `offset`.
+    fn vertical(&self, offset: usize, direction: f32) -> usize {
+        let Some(layout) = &self.layout else {
+            return offset;
+        };
+
+        let caret = layout.caret_rect(layout.from_text_offset(offset));
+        let y = if direction < 0.0 {
+            caret.top - 1.0
+        } else {
+            caret.bottom + 1.0
+        };
+        if y < 0.0 {
+            return 0;
+        }
+
+        layout.to_text_offset(layout.position_at(SkiaPoint::new(caret.left, y)))
+    }
 }
 
 struct CursorIconSetter {
@@ -134,46 +255,56 @@ impl CursorIconSetter {
     }
 }
 
-async fn start(
-    eh: EventHandle,
-    sel: Arc<SyncMutex<Selection>>,
-    win: Arc<WinitWindow>,
-    win_ed: EventDispatcher,
-) {
+async fn start(eh: Weak<ElementHandle<TextBox>>, ed: EventDispatcher, global: Arc<GlobalContent>) {
+    let win = global.window();
+    let win_ed = global.global_event_dispatcher();
     let cursor_icon_setter = Mutex::new(CursorIconSetter {
         showing_text_cursor: false,
         text_selecting: false,
         cursor_entered: false,
     });
+    let focused = AtomicBool::new(false);
+    let modifiers = SyncMutex::new(ModifiersState::empty());
 
     let a = async {
+        let mut clicks = ClickCounter::default();
         loop {
             cursor_icon_setter
                 .lock()
                 .await
-                .set_text_selecting(&win, false);
-
-            let pd = tokio::select! {
-                pd = eh.recv_sys::<PointerDown>() => pd,
-                _ = eh.recv_sys::<Blured>() => {
-                    sel.lock().unwrap().cursor = None;
-                    continue;
-                }
-            };
+                .set_text_selecting(win, false);
 
+            let pd = ed.recv_sys::<PointerDown>().await;
             if !pd.is_current {
-                eh.recv_sys::<PointerUp>().await;
+                ed.recv_sys::<PointerUp>().await;
                 continue;
             }
 
-            eh.focus().await;
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+            eh.focus();
             cursor_icon_setter
                 .lock()
                 .await
-                .set_text_selecting(&win, true);
+                .set_text_selecting(win, true);
 
-            let mut range = (pd.position, pd.position);
-            sel.lock().unwrap().cursor = Some(range);
+            let count = clicks.click(pd.position);
+            let extend = modifiers.lock().unwrap().shift();
+            let origin = {
+                let mut el = eh.el_write().await;
+                let Some(offset) = el.text_offset_at(pd.position) else {
+                    continue;
+                };
+
+                let origin = match el.selection {
+                    Some(sel) if extend && count == 1 => sel.anchor..sel.anchor,
+                    _ => el.click_unit(offset, count),
+                };
+                let unit = el.click_unit(offset, count);
+                el.select_units(origin.clone(), unit);
+                origin
+            };
 
             loop {
                 let pm = tokio::select! {
@@ -181,33 +312,103 @@ async fn start(
                     _ = win_ed.recv_sys::<PointerUp>() => break
                 };
 
-                range.1 = pm.position;
-                sel.lock().unwrap().cursor = Some(range);
+                let mut el = eh.el_write().await;
+                if let Some(offset) = el.text_offset_at(pm.position) {
+                    let unit = el.click_unit(offset, count);
+                    el.select_units(origin.clone(), unit);
+                }
             }
         }
     };
 
     let b = async {
-        eh.recv_sys::<PointerMove>().await;
+        ed.recv_sys::<PointerMove>().await;
 
         loop {
             tokio::select! {
-                _ = eh.recv_sys::<PointerEntered>() => {
+                _ = ed.recv_sys::<PointerEntered>() => {
                     cursor_icon_setter
                         .lock()
                         .await
-                        .set_cursor_entered(&win, true);
+                        .set_cursor_entered(win, true);
                 }
 
-                _ = eh.recv_sys::<PointerOut>() => {
+                _ = ed.recv_sys::<PointerOut>() => {
                     cursor_icon_setter
                         .lock()
                         .await
-                        .set_cursor_entered(&win, false);
+                        .set_cursor_entered(win, false);
                 }
             }
         }
     };
 
-    tokio::join!(a, b);
+    let c = async {
+        loop {
+            ed.recv_sys::<Focused>().await;
+            focused.store(true, Ordering::Relaxed);
+            ed.recv_sys::<Blured>().await;
+            focused.store(false, Ordering::Relaxed);
+
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+            eh.el_write().await.selection = None;
+        }
+    };
+
+    let d = async {
+        let mut lock = win_ed.lock();
+        loop {
+            let key = match lock.recv_sys::<StaticWindowEvent>().await {
+                StaticWindowEvent::ModifiersChanged(state) => {
+                    *modifiers.lock().unwrap() = state;
+                    continue;
+                }
+                StaticWindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode: Some(key),
+                            ..
+                        },
+                    ..
+                } if focused.load(Ordering::Relaxed) => key,
+                _ => continue,
+            };
+
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+            let state = *modifiers.lock().unwrap();
+            let command = state.ctrl() || state.logo();
+
+            match key {
+                VirtualKeyCode::C if command => copy_selection(&eh).await,
+                VirtualKeyCode::A if command => eh.el_write().await.select_all(),
+                VirtualKeyCode::Left
+                | VirtualKeyCode::Right
+                | VirtualKeyCode::Up
+                | VirtualKeyCode::Down
+                | VirtualKeyCode::Home
+                | VirtualKeyCode::End => eh.el_write().await.move_selection(key, state),
+                _ => {}
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = ed.recv_sys::<ElementAbandoned>() => {}
+        _ = async { tokio::join!(a, b, c, d) } => {}
+    }
+}
+
+async fn copy_selection(eh: &ElementHandle<TextBox>) {
+    let Some(text) = eh.el_read().await.selected_text().map(str::to_owned) else {
+        return;
+    };
+
+    if let Err(err) = Clipboard::global().write_text(&text) {
+        irisia::warning!(target: irisia::log::CLIPBOARD, "cannot copy selected text: {err:#}");
+    }
 }
@@ -1,9 +1,35 @@
-use irisia_core::{
+use std::sync::Arc;
+
+use irisia::{
     primitive::Pixel,
-    skia_safe::font_style::{Slant, Weight},
+    skia_safe::{
+        font_style::{Slant, Weight},
+        textlayout::TextAlign,
+    },
     Style,
 };
 
+/// Font family of text, registered in [`FontRegistry`] or installed in
+/// the system. Text without a font family uses the default families of
+/// the registry.
+///
+/// [`FontRegistry`]: crate::font::FontRegistry
+#[derive(Style, Clone, PartialEq)]
+#[style(from = "", impl_default)]
+pub struct StyleFontFamily(#[style(default = "None")] pub Option<Arc<str>>);
+
+impl StyleFontFamily {
+    pub fn default_family(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl From<&str> for StyleFontFamily {
+    fn from(value: &str) -> Self {
+        StyleFontFamily(Some(value.into()))
+    }
+}
+
 #[derive(Style, Clone, Copy, PartialEq)]
 #[style(from, impl_default)]
 pub struct StyleFontSize(#[style(default = "Pixel(40.0)")] pub Pixel);
@@ -57,3 +83,160 @@ impl From<u32> for StyleFontWeight {
         StyleFontWeight(Weight::from(value as i32))
     }
 }
+
+/// Horizontal alignment of lines. `start` and `end` follow the direction
+/// of the element, see [`StyleDirection`](irisia::style::StyleDirection).
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from = "", impl_default)]
+pub struct StyleTextAlign(#[style(default = "TextAlign::Start")] pub TextAlign);
+
+impl StyleTextAlign {
+    pub fn start(&mut self) {
+        self.0 = TextAlign::Start;
+    }
+
+    pub fn center(&mut self) {
+        self.0 = TextAlign::Center;
+    }
+
+    pub fn end(&mut self) {
+        self.0 = TextAlign::End;
+    }
+
+    pub fn justify(&mut self) {
+        self.0 = TextAlign::Justify;
+    }
+
+    pub fn left(&mut self) {
+        self.0 = TextAlign::Left;
+    }
+
+    pub fn right(&mut self) {
+        self.0 = TextAlign::Right;
+    }
+}
+
+/// Height of a line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineHeight {
+    /// Decided by metrics of the font.
+    Normal,
+
+    /// Multiple of the font size.
+    Scale(f32),
+
+    /// Fixed height regardless of the font size.
+    Fixed(Pixel),
+}
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from = "", impl_default)]
+pub struct StyleLineHeight(#[style(default = "LineHeight::Normal")] pub LineHeight);
+
+impl StyleLineHeight {
+    pub fn normal(&mut self) {
+        self.0 = LineHeight::Normal;
+    }
+
+    pub fn scale(&mut self, scale: f32) {
+        self.0 = LineHeight::Scale(scale);
+    }
+
+    pub fn fixed(&mut self, height: Pixel) {
+        self.0 = LineHeight::Fixed(height);
+    }
+}
+
+impl From<f32> for StyleLineHeight {
+    fn from(value: f32) -> Self {
+        StyleLineHeight(LineHeight::Scale(value))
+    }
+}
+
+impl From<Pixel> for StyleLineHeight {
+    fn from(value: Pixel) -> Self {
+        StyleLineHeight(LineHeight::Fixed(value))
+    }
+}
+
+/// Extra space between paragraphs, which are separated by `\n`.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleParagraphSpacing(#[style(default = "Pixel(0.0)")] pub Pixel);
+
+/// Maximum count of lines shown, counted over all paragraphs.
+/// Lines after it are hidden.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from = "", impl_default)]
+pub struct StyleMaxLines(#[style(default = "None")] pub Option<usize>);
+
+impl StyleMaxLines {
+    pub fn unlimited(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl From<usize> for StyleMaxLines {
+    fn from(value: usize) -> Self {
+        StyleMaxLines(Some(value))
+    }
+}
+
+/// What to show at the end of the last line when text is truncated
+/// by `max_lines`.
+#[derive(Style, Clone, PartialEq)]
+#[style(from = "", impl_default)]
+pub struct StyleTextOverflow(#[style(default = "TextOverflow::Clip")] pub TextOverflow);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextOverflow {
+    Clip,
+    Ellipsis(String),
+}
+
+impl StyleTextOverflow {
+    pub fn clip(&mut self) {
+        self.0 = TextOverflow::Clip;
+    }
+
+    /// Ends the last line with `…`.
+    pub fn ellipsis(&mut self) {
+        self.0 = TextOverflow::Ellipsis("\u{2026}".into());
+    }
+
+    pub fn custom(&mut self, ellipsis: &str) {
+        self.0 = TextOverflow::Ellipsis(ellipsis.into());
+    }
+}
+
+/// Where lines can break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordBreak {
+    /// Break between words. A word longer than a line is broken
+    /// between its characters.
+    Normal,
+
+    /// Break between any two characters.
+    BreakAll,
+
+    /// Never wrap, lines only end at `\n`.
+    NoWrap,
+}
+
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from = "", impl_default)]
+pub struct StyleWordBreak(#[style(default = "WordBreak::Normal")] pub WordBreak);
+
+impl StyleWordBreak {
+    pub fn normal(&mut self) {
+        self.0 = WordBreak::Normal;
+    }
+
+    pub fn break_all(&mut self) {
+        self.0 = WordBreak::BreakAll;
+    }
+
+    pub fn no_wrap(&mut self) {
+        self.0 = WordBreak::NoWrap;
+    }
+}
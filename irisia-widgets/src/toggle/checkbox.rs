@@ -0,0 +1,248 @@
+use std::{sync::Arc, time::Duration};
+
+use irisia::{
+    element::{ChildrenNodes, ChildrenSetter, Element, RenderElement, UpdateElement},
+    primitive::Region,
+    skia_safe::{Path, Point as SkiaPoint},
+    style::StyleContainer,
+    Event, Result, UpdateWith,
+};
+use tokio::task::JoinHandle;
+
+use crate::{
+    indicator::{draw_state, fade_disabled, fill, rounded, stroke},
+    interaction::{run_pressable, InteractionState, Pressable},
+    textbox::RichText,
+};
+
+use super::{label::IndicatorLabel, read_styles, styles::ToggleStyles};
+
+/// Checkbox with a label. Clicking it, or pressing `Space` while it is
+/// focused, emits [`CheckboxChanged`] with the opposite of the current
+/// state, an indeterminate checkbox asks to be checked.
+pub struct Checkbox {
+    runtime: Option<JoinHandle<()>>,
+    checked: CheckState,
+    disabled: bool,
+    state: InteractionState,
+    label: IndicatorLabel,
+    styles: ToggleStyles,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CheckState {
+    #[default]
+    Unchecked,
+    Checked,
+
+    /// Neither checked nor unchecked, usually when only some of the
+    /// options controlled by the checkbox are checked.
+    Indeterminate,
+}
+
+impl CheckState {
+    pub fn is_checked(self) -> bool {
+        self == CheckState::Checked
+    }
+}
+
+impl From<bool> for CheckState {
+    fn from(checked: bool) -> Self {
+        if checked {
+            CheckState::Checked
+        } else {
+            CheckState::Unchecked
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CheckboxProps {
+    pub checked: CheckState,
+    pub label: RichText,
+    pub disabled: bool,
+}
+
+impl CheckboxProps {
+    pub fn checked(mut self, checked: impl Into<CheckState>) -> Self {
+        self.checked = checked.into();
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<RichText>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// The checkbox asks to be checked, or unchecked if `false`.
+#[derive(Event, Clone, Copy)]
+pub struct CheckboxChanged(pub bool);
+
+impl Element for Checkbox {
+    type BlankProps = CheckboxProps;
+
+    fn layout<'a, Ch>(&mut self, _: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        setter.set_children(());
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        let styles = &self.styles;
+        let canvas = renderer.canvas();
+
+        let count = canvas.save();
+        if self.disabled {
+            fade_disabled(canvas);
+        }
+
+        let content_region = styles.box_styles.draw_border_limited(canvas, draw_region);
+        let (start, end) = content_region;
+        styles.box_styles.clip_content(canvas, content_region);
+
+        let size = styles.indicator_size.0.to_physical();
+        let (rect, _) = self.label.paint(
+            canvas,
+            &styles.text,
+            (start.0.to_physical(), end.0.to_physical()),
+            start.1.to_physical(),
+            (size, size),
+        );
+
+        let rrect = rounded(rect, 0.15);
+        draw_state(canvas, &rrect, styles.accent.0, self.state, self.disabled);
+
+        let line = size * 0.12;
+        let at = |x: f32, y: f32| SkiaPoint::new(rect.left + size * x, rect.top + size * y);
+        match self.checked {
+            CheckState::Unchecked => {
+                let mut inner = rrect;
+                inner.inset((line / 2.0, line / 2.0));
+                canvas.draw_rrect(inner, &stroke(styles.track.0, line));
+            }
+            CheckState::Checked => {
+                canvas.draw_rrect(rrect, &fill(styles.accent.0));
+                let mut path = Path::new();
+                path.move_to(at(0.24, 0.52))
+                    .line_to(at(0.42, 0.7))
+                    .line_to(at(0.76, 0.32));
+                canvas.draw_path(&path, &stroke(styles.mark.0, line));
+            }
+            CheckState::Indeterminate => {
+                canvas.draw_rrect(rrect, &fill(styles.accent.0));
+                canvas.draw_line(at(0.26, 0.5), at(0.74, 0.5), &stroke(styles.mark.0, line));
+            }
+        }
+
+        canvas.restore_to_count(count);
+        renderer.set_interact_region(draw_region);
+        Ok(())
+    }
+}
+
+impl<'a, Sty> UpdateWith<UpdateElement<'a, Self, CheckboxProps, Sty>> for Checkbox
+where
+    Sty: StyleContainer,
+{
+    fn create_with(updater: UpdateElement<'a, Self, CheckboxProps, Sty>) -> Self {
+        let handle = updater.handle;
+        let mut checkbox = Checkbox {
+            runtime: Some(tokio::spawn(run_pressable(
+                Arc::downgrade(handle),
+                handle.event_dispatcher().clone(),
+                handle.global().clone(),
+            ))),
+            checked: CheckState::Unchecked,
+            disabled: false,
+            state: InteractionState::default(),
+            label: IndicatorLabel::new(RichText::new(), handle.direction()),
+            styles: read_styles(updater.styles, None, handle),
+        };
+
+        checkbox.update_with(updater, false);
+        checkbox
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, CheckboxProps, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        let UpdateElement {
+            props:
+                CheckboxProps {
+                    checked,
+                    label,
+                    disabled,
+                },
+            styles,
+            handle,
+        } = updater;
+
+        let direction = handle.direction();
+        self.styles = read_styles(styles, Some(&self.styles.box_styles), handle);
+        self.label.set(label, direction);
+        self.checked = checked;
+
+        if disabled && !self.disabled {
+            self.state.pressed = false;
+            handle.blur();
+        }
+        self.disabled = disabled;
+
+        // box styles cannot be compared
+        false
+    }
+}
+
+impl Drop for Checkbox {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            runtime.abort();
+        }
+    }
+}
+
+impl Pressable for Checkbox {
+    type Activated = CheckboxChanged;
+
+    const ACTIVATE_ON_ENTER: bool = false;
+
+    fn interaction(&mut self) -> &mut InteractionState {
+        &mut self.state
+    }
+
+    fn disabled(&self) -> bool {
+        self.disabled
+    }
+
+    fn activate(&mut self) -> Option<CheckboxChanged> {
+        Some(CheckboxChanged(!self.checked.is_checked()))
+    }
+}
+
+impl Checkbox {
+    pub fn checked(&self) -> CheckState {
+        self.checked
+    }
+
+    pub fn state(&self) -> InteractionState {
+        self.state
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+}
@@ -0,0 +1,69 @@
+use irisia::{
+    primitive::Direction,
+    skia_safe::{Canvas, Point as SkiaPoint, Rect},
+};
+
+use crate::textbox::{layout::TextLayout, RichText, TextBoxStyles};
+
+/// Label of a toggle, placed after an indicator at the start side.
+pub(super) struct IndicatorLabel {
+    text: RichText,
+    direction: Direction,
+    layout: Option<(TextBoxStyles, TextLayout)>,
+}
+
+impl IndicatorLabel {
+    pub fn new(text: RichText, direction: Direction) -> Self {
+        IndicatorLabel {
+            text,
+            direction,
+            layout: None,
+        }
+    }
+
+    pub fn set(&mut self, text: RichText, direction: Direction) {
+        if self.text != text || self.direction != direction {
+            self.text = text;
+            self.direction = direction;
+            self.layout = None;
+        }
+    }
+
+    /// Paint a row of an indicator of `indicator` size and the label,
+    /// between `left` and `right` from `top`. Returns the rect of the
+    /// indicator, to be drawn by the caller, and the height of the row.
+    pub fn paint(
+        &mut self,
+        canvas: &mut Canvas,
+        styles: &TextBoxStyles,
+        (left, right): (f32, f32),
+        top: f32,
+        (width, height): (f32, f32),
+    ) -> (Rect, f32) {
+        let gap = if self.text.spans().is_empty() {
+            0.0
+        } else {
+            height / 2.0
+        };
+
+        if !matches!(&self.layout, Some((old, _)) if old == styles) {
+            let layout = TextLayout::new(&self.text, styles, self.direction);
+            self.layout = Some((styles.clone(), layout));
+        }
+        let layout = &mut self.layout.as_mut().unwrap().1;
+        layout.layout((right - left - width - gap).max(0.0));
+
+        let row_height = height.max(layout.height());
+        let indicator_top = top + (row_height - height) / 2.0;
+        let label_top = top + (row_height - layout.height()) / 2.0;
+
+        let (indicator_left, label_left) = match self.direction {
+            Direction::Ltr => (left, left + width + gap),
+            Direction::Rtl => (right - width, left),
+        };
+
+        layout.paint(canvas, SkiaPoint::new(label_left, label_top));
+        let indicator = Rect::from_xywh(indicator_left, indicator_top, width, height);
+        (indicator, row_height)
+    }
+}
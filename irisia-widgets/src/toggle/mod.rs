@@ -0,0 +1,38 @@
+//! Boolean and choice controls. They do not change by themselves: the
+//! new value is emitted as an event, and the parent passes it back
+//! through the props.
+
+use std::sync::Arc;
+
+use irisia::{element::ElementHandle, style::StyleContainer, StyleReader};
+
+use crate::box_styles::BoxStyles;
+
+pub use self::{
+    checkbox::{CheckState, Checkbox, CheckboxChanged, CheckboxProps},
+    radio::{RadioChanged, RadioGroup, RadioGroupProps},
+    styles::*,
+    switch::{Switch, SwitchChanged, SwitchProps},
+};
+
+use self::styles::ToggleStyles;
+
+pub mod checkbox;
+mod label;
+pub mod radio;
+pub mod styles;
+pub mod switch;
+
+fn read_styles<El>(
+    styles: &impl StyleContainer,
+    previous: Option<&BoxStyles>,
+    handle: &Arc<ElementHandle<El>>,
+) -> ToggleStyles
+where
+    El: Send + Sync + 'static,
+{
+    let mut read = ToggleStyles::read_style(styles);
+    read.box_styles.apply_direction(handle.direction());
+    read.box_styles.preload_images(previous, handle);
+    read
+}
@@ -0,0 +1,414 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
+    time::Duration,
+};
+
+use irisia::{
+    application::GlobalContent,
+    element::{
+        ChildrenNodes, ChildrenSetter, Element, ElementHandle, RenderElement, UpdateElement,
+    },
+    event::{
+        standard::{
+            Blured, ElementAbandoned, Focused, PointerDown, PointerMove, PointerOut, PointerUp,
+        },
+        EventDispatcher,
+    },
+    primitive::{Direction, Region},
+    skia_safe::{RRect, Rect},
+    style::StyleContainer,
+    winit::event::{ElementState, KeyboardInput, VirtualKeyCode},
+    Event, Result, StaticWindowEvent, UpdateWith,
+};
+use tokio::task::JoinHandle;
+
+use crate::{
+    indicator::{draw_state, fade_disabled, fill, stroke},
+    interaction::InteractionState,
+    textbox::RichText,
+};
+
+use super::{label::IndicatorLabel, read_styles, styles::ToggleStyles};
+
+/// Group of mutually exclusive options, laid out from top to bottom.
+/// Clicking an option emits [`RadioChanged`] with its index. While the
+/// group is focused, arrow keys, `Home` and `End` move the selection.
+pub struct RadioGroup {
+    runtime: Option<JoinHandle<()>>,
+    options: Vec<IndicatorLabel>,
+    selected: Option<usize>,
+    disabled: bool,
+    focused: bool,
+    hovered: Option<usize>,
+    pressed: Option<usize>,
+    styles: ToggleStyles,
+    direction: Direction,
+
+    /// Top and bottom of options in the last frame, for hit-testing.
+    rows: Vec<(f32, f32)>,
+}
+
+#[derive(Default)]
+pub struct RadioGroupProps {
+    pub options: Vec<RichText>,
+    pub selected: Option<usize>,
+    pub disabled: bool,
+}
+
+impl RadioGroupProps {
+    /// Add an option after the existing ones.
+    pub fn option(mut self, label: impl Into<RichText>) -> Self {
+        self.options.push(label.into());
+        self
+    }
+
+    pub fn options<I>(mut self, options: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<RichText>,
+    {
+        self.options = options.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Index of the selected option, `None` if nothing is selected.
+    pub fn selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// The option at the index asks to be selected.
+#[derive(Event, Clone, Copy)]
+pub struct RadioChanged(pub usize);
+
+impl Element for RadioGroup {
+    type BlankProps = RadioGroupProps;
+
+    fn layout<'a, Ch>(&mut self, _: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        setter.set_children(());
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        let styles = &self.styles;
+        let canvas = renderer.canvas();
+
+        let count = canvas.save();
+        if self.disabled {
+            fade_disabled(canvas);
+        }
+
+        let content_region = styles.box_styles.draw_border_limited(canvas, draw_region);
+        let (start, end) = content_region;
+        styles.box_styles.clip_content(canvas, content_region);
+
+        let size = styles.indicator_size.0.to_physical();
+        let line = size * 0.1;
+        let horizontal = (start.0.to_physical(), end.0.to_physical());
+        let focus_index = self.focus_index();
+
+        self.rows.clear();
+        let mut top = start.1.to_physical();
+        for (index, label) in self.options.iter_mut().enumerate() {
+            let (rect, height) = label.paint(canvas, &styles.text, horizontal, top, (size, size));
+            self.rows.push((top, top + height));
+            top += height + size / 2.0;
+
+            let circle = RRect::new_oval(rect);
+            let state = InteractionState {
+                hovered: self.hovered == Some(index),
+                pressed: self.pressed == Some(index),
+                focused: self.focused && focus_index == Some(index),
+            };
+            draw_state(canvas, &circle, styles.accent.0, state, self.disabled);
+
+            let selected = self.selected == Some(index);
+            let color = if selected {
+                styles.accent.0
+            } else {
+                styles.track.0
+            };
+            let mut outline = rect;
+            outline.inset((line / 2.0, line / 2.0));
+            canvas.draw_oval(outline, &stroke(color, line));
+
+            if selected {
+                let dot = Rect::from_xywh(0.0, 0.0, size / 2.0, size / 2.0)
+                    .with_offset((rect.center_x() - size / 4.0, rect.center_y() - size / 4.0));
+                canvas.draw_oval(dot, &fill(styles.accent.0));
+            }
+        }
+
+        canvas.restore_to_count(count);
+        renderer.set_interact_region(draw_region);
+        Ok(())
+    }
+}
+
+impl<'a, Sty> UpdateWith<UpdateElement<'a, Self, RadioGroupProps, Sty>> for RadioGroup
+where
+    Sty: StyleContainer,
+{
+    fn create_with(updater: UpdateElement<'a, Self, RadioGroupProps, Sty>) -> Self {
+        let handle = updater.handle;
+        let mut group = RadioGroup {
+            runtime: Some(tokio::spawn(run(
+                Arc::downgrade(handle),
+                handle.event_dispatcher().clone(),
+                handle.global().clone(),
+            ))),
+            options: Vec::new(),
+            selected: None,
+            disabled: false,
+            focused: false,
+            hovered: None,
+            pressed: None,
+            styles: read_styles(updater.styles, None, handle),
+            direction: handle.direction(),
+            rows: Vec::new(),
+        };
+
+        group.update_with(updater, false);
+        group
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, RadioGroupProps, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        let UpdateElement {
+            props:
+                RadioGroupProps {
+                    options,
+                    selected,
+                    disabled,
+                },
+            styles,
+            handle,
+        } = updater;
+
+        let direction = handle.direction();
+        self.styles = read_styles(styles, Some(&self.styles.box_styles), handle);
+        self.direction = direction;
+
+        self.options.truncate(options.len());
+        let mut options = options.into_iter();
+        for (label, text) in self.options.iter_mut().zip(options.by_ref()) {
+            label.set(text, direction);
+        }
+        self.options
+            .extend(options.map(|text| IndicatorLabel::new(text, direction)));
+
+        let len = self.options.len();
+        self.selected = selected.filter(|&index| index < len);
+        self.hovered = self.hovered.filter(|&index| index < len);
+        self.pressed = self.pressed.filter(|&index| index < len);
+
+        if disabled && !self.disabled {
+            self.pressed = None;
+            handle.blur();
+        }
+        self.disabled = disabled;
+
+        // box styles cannot be compared
+        false
+    }
+}
+
+impl Drop for RadioGroup {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            runtime.abort();
+        }
+    }
+}
+
+impl RadioGroup {
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Index of the option under the physical vertical position `y`.
+    fn option_at(&self, y: f32) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|&(top, bottom)| (top..bottom).contains(&y))
+    }
+
+    /// Option showing the focus, the selected one or the first one.
+    fn focus_index(&self) -> Option<usize> {
+        self.selected
+            .or_else(|| (!self.options.is_empty()).then_some(0))
+    }
+
+    /// Option selected by pressing `key`, `None` if the key moves nothing.
+    fn navigate(&self, key: VirtualKeyCode) -> Option<usize> {
+        let len = self.options.len();
+        if len == 0 {
+            return None;
+        }
+
+        // `Left` and `Right` follow the reading direction
+        let forward = match key {
+            VirtualKeyCode::Home => return Some(0),
+            VirtualKeyCode::End => return Some(len - 1),
+            VirtualKeyCode::Space => return self.focus_index(),
+            VirtualKeyCode::Down => true,
+            VirtualKeyCode::Up => false,
+            VirtualKeyCode::Right => !self.direction.is_rtl(),
+            VirtualKeyCode::Left => self.direction.is_rtl(),
+            _ => return None,
+        };
+
+        // wraps around, starting from the first option if none is selected
+        Some(match self.selected {
+            None => 0,
+            Some(index) if forward => (index + 1) % len,
+            Some(index) => (index + len - 1) % len,
+        })
+    }
+}
+
+async fn run(eh: Weak<ElementHandle<RadioGroup>>, ed: EventDispatcher, global: Arc<GlobalContent>) {
+    let win_ed = global.global_event_dispatcher();
+    let focused = AtomicBool::new(false);
+
+    let select = |eh: &ElementHandle<RadioGroup>, index: usize, selected: Option<usize>| {
+        if selected != Some(index) {
+            eh.event_dispatcher().emit(RadioChanged(index));
+        }
+    };
+
+    let pointer = async {
+        loop {
+            let PointerDown { position, .. } = ed.recv_sys::<PointerDown>().await;
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            {
+                let mut el = eh.el_write().await;
+                if el.disabled {
+                    continue;
+                }
+                el.pressed = el.option_at(position.1.to_physical());
+            }
+            eh.focus();
+
+            // released anywhere, but only selects over the pressed option
+            let PointerUp { position, .. } = win_ed.recv_sys::<PointerUp>().await;
+            let (released, selected) = {
+                let mut el = eh.el_write().await;
+                let pressed = el.pressed.take();
+                let over =
+                    el.hovered.is_some() && el.option_at(position.1.to_physical()) == pressed;
+                (pressed.filter(|_| over && !el.disabled), el.selected)
+            };
+            if let Some(index) = released {
+                select(&eh, index, selected);
+            }
+        }
+    };
+
+    let hover = async {
+        loop {
+            let hovered = tokio::select! {
+                PointerMove { position, .. } = ed.recv_sys::<PointerMove>() => Some(position),
+                _ = ed.recv_sys::<PointerOut>() => None,
+            };
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            // only redraw when another option is hovered
+            let hovered = {
+                let el = eh.el_read().await;
+                let hovered = hovered.and_then(|position| el.option_at(position.1.to_physical()));
+                (el.hovered != hovered).then_some(hovered)
+            };
+            if let Some(hovered) = hovered {
+                eh.el_write().await.hovered = hovered;
+            }
+        }
+    };
+
+    let focus = async {
+        loop {
+            let value = tokio::select! {
+                _ = ed.recv_sys::<Focused>() => true,
+                _ = ed.recv_sys::<Blured>() => false,
+            };
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            let mut el = eh.el_write().await;
+            if value && el.disabled {
+                drop(el);
+                eh.blur();
+                continue;
+            }
+            el.focused = value;
+            focused.store(value, Ordering::Relaxed);
+        }
+    };
+
+    let keyboard = async {
+        let mut lock = win_ed.lock();
+        loop {
+            let key = match lock.recv_sys::<StaticWindowEvent>().await {
+                StaticWindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode: Some(key),
+                            ..
+                        },
+                    ..
+                } if focused.load(Ordering::Relaxed) => key,
+                _ => continue,
+            };
+
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            let (next, selected) = {
+                let el = eh.el_read().await;
+                if el.disabled {
+                    continue;
+                }
+                (el.navigate(key), el.selected)
+            };
+            if let Some(index) = next {
+                select(&eh, index, selected);
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = ed.recv_sys::<ElementAbandoned>() => {}
+        _ = async { tokio::join!(pointer, hover, focus, keyboard) } => {}
+    }
+}
@@ -0,0 +1,36 @@
+use irisia::{primitive::Pixel, skia_safe::Color, Style, StyleReader};
+
+use crate::{box_styles::BoxStyles, textbox::TextBoxStyles};
+
+/// Size of the box of a checkbox or the circle of a radio button. A
+/// switch is twice as wide as it is high.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleIndicatorSize(#[style(default = "Pixel(20.0)")] pub Pixel);
+
+/// Color of a checked indicator, and of the filled part and the
+/// thumbs of a slider.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleAccentColor(#[style(default = "Color::from_rgb(0x1e, 0x88, 0xe5)")] pub Color);
+
+/// Color of the check mark, the thumb of a switch and tick marks of
+/// a slider.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleMarkColor(#[style(default = "Color::WHITE")] pub Color);
+
+/// Color of an unchecked indicator, and of the track of a slider.
+#[derive(Style, Clone, Copy, PartialEq)]
+#[style(from, impl_default)]
+pub struct StyleTrackColor(#[style(default = "Color::from_rgb(0x75, 0x75, 0x75)")] pub Color);
+
+#[derive(StyleReader, Clone)]
+pub(super) struct ToggleStyles {
+    pub text: TextBoxStyles,
+    pub box_styles: BoxStyles,
+    pub indicator_size: StyleIndicatorSize,
+    pub accent: StyleAccentColor,
+    pub mark: StyleMarkColor,
+    pub track: StyleTrackColor,
+}
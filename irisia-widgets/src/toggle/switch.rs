@@ -0,0 +1,308 @@
+use std::{
+    sync::{Arc, Weak},
+    time::{Duration, Instant},
+};
+
+use irisia::{
+    application::GlobalContent,
+    element::{
+        ChildrenNodes, ChildrenSetter, Element, ElementHandle, RenderElement, UpdateElement,
+    },
+    event::EventDispatcher,
+    primitive::{Direction, Region},
+    skia_safe::{Color, Point as SkiaPoint},
+    style::StyleContainer,
+    Event, Result, UpdateWith,
+};
+use tokio::{sync::Notify, task::JoinHandle};
+
+use crate::{
+    indicator::{draw_state, fade_disabled, fill, rounded},
+    interaction::{run_pressable, InteractionState, Pressable},
+    textbox::RichText,
+};
+
+use super::{label::IndicatorLabel, read_styles, styles::ToggleStyles};
+
+const ANIMATION_DURATION: Duration = Duration::from_millis(150);
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Switch with a label, the thumb slides to the end side when it is on.
+/// Clicking it, or pressing `Enter` or `Space` while it is focused,
+/// emits [`SwitchChanged`] with the opposite of the current state.
+pub struct Switch {
+    runtime: Option<JoinHandle<()>>,
+    checked: bool,
+    disabled: bool,
+    state: InteractionState,
+    label: IndicatorLabel,
+    styles: ToggleStyles,
+    direction: Direction,
+    animation: Option<Animation>,
+    animate: Arc<Notify>,
+}
+
+/// Thumb moving from `from` to the position of the current state, where
+/// `0.0` is off and `1.0` is on.
+#[derive(Clone, Copy)]
+struct Animation {
+    from: f32,
+    start: Instant,
+}
+
+#[derive(Default)]
+pub struct SwitchProps {
+    pub checked: bool,
+    pub label: RichText,
+    pub disabled: bool,
+}
+
+impl SwitchProps {
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<RichText>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// The switch asks to be turned on, or off if `false`.
+#[derive(Event, Clone, Copy)]
+pub struct SwitchChanged(pub bool);
+
+impl Element for Switch {
+    type BlankProps = SwitchProps;
+
+    fn layout<'a, Ch>(&mut self, _: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        setter.set_children(());
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        let styles = &self.styles;
+        let canvas = renderer.canvas();
+
+        let count = canvas.save();
+        if self.disabled {
+            fade_disabled(canvas);
+        }
+
+        let content_region = styles.box_styles.draw_border_limited(canvas, draw_region);
+        let (start, end) = content_region;
+        styles.box_styles.clip_content(canvas, content_region);
+
+        let size = styles.indicator_size.0.to_physical();
+        let (rect, _) = self.label.paint(
+            canvas,
+            &styles.text,
+            (start.0.to_physical(), end.0.to_physical()),
+            start.1.to_physical(),
+            (size * 2.0, size),
+        );
+
+        let track = rounded(rect, 0.5);
+        draw_state(canvas, &track, styles.accent.0, self.state, self.disabled);
+
+        let progress = self.progress();
+        let track_color = mix(styles.track.0, styles.accent.0, progress);
+        canvas.draw_rrect(track, &fill(track_color));
+
+        // the thumb moves to the end side, which is the left when right to left
+        let position = if self.direction.is_rtl() {
+            1.0 - progress
+        } else {
+            progress
+        };
+        let radius = size / 2.0;
+        let center = SkiaPoint::new(rect.left + radius + size * position, rect.center_y());
+        canvas.draw_circle(center, radius * 0.75, &fill(styles.mark.0));
+
+        canvas.restore_to_count(count);
+        renderer.set_interact_region(draw_region);
+        Ok(())
+    }
+}
+
+impl<'a, Sty> UpdateWith<UpdateElement<'a, Self, SwitchProps, Sty>> for Switch
+where
+    Sty: StyleContainer,
+{
+    fn create_with(updater: UpdateElement<'a, Self, SwitchProps, Sty>) -> Self {
+        let handle = updater.handle;
+        let animate = Arc::new(Notify::new());
+        let mut switch = Switch {
+            runtime: Some(tokio::spawn(run(
+                Arc::downgrade(handle),
+                handle.event_dispatcher().clone(),
+                handle.global().clone(),
+                animate.clone(),
+            ))),
+            checked: updater.props.checked,
+            disabled: false,
+            state: InteractionState::default(),
+            label: IndicatorLabel::new(RichText::new(), handle.direction()),
+            styles: read_styles(updater.styles, None, handle),
+            direction: handle.direction(),
+            animation: None,
+            animate,
+        };
+
+        switch.update_with(updater, false);
+        switch
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, SwitchProps, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        let UpdateElement {
+            props:
+                SwitchProps {
+                    checked,
+                    label,
+                    disabled,
+                },
+            styles,
+            handle,
+        } = updater;
+
+        let direction = handle.direction();
+        self.styles = read_styles(styles, Some(&self.styles.box_styles), handle);
+        self.label.set(label, direction);
+        self.direction = direction;
+
+        if checked != self.checked {
+            self.animation = Some(Animation {
+                from: self.progress(),
+                start: Instant::now(),
+            });
+            self.checked = checked;
+            self.animate.notify_one();
+        }
+
+        if disabled && !self.disabled {
+            self.state.pressed = false;
+            handle.blur();
+        }
+        self.disabled = disabled;
+
+        // box styles cannot be compared
+        false
+    }
+}
+
+impl Drop for Switch {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            runtime.abort();
+        }
+    }
+}
+
+impl Pressable for Switch {
+    type Activated = SwitchChanged;
+
+    const ACTIVATE_ON_ENTER: bool = true;
+
+    fn interaction(&mut self) -> &mut InteractionState {
+        &mut self.state
+    }
+
+    fn disabled(&self) -> bool {
+        self.disabled
+    }
+
+    fn activate(&mut self) -> Option<SwitchChanged> {
+        Some(SwitchChanged(!self.checked))
+    }
+}
+
+impl Switch {
+    pub fn checked(&self) -> bool {
+        self.checked
+    }
+
+    pub fn state(&self) -> InteractionState {
+        self.state
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Position of the thumb now, from `0.0` for off to `1.0` for on.
+    fn progress(&self) -> f32 {
+        let to = if self.checked { 1.0 } else { 0.0 };
+        let Some(Animation { from, start }) = self.animation else {
+            return to;
+        };
+
+        let t = start.elapsed().as_secs_f32() / ANIMATION_DURATION.as_secs_f32();
+        if t >= 1.0 {
+            return to;
+        }
+
+        // ease out
+        let t = 1.0 - (1.0 - t).powi(3);
+        from + (to - from) * t
+    }
+
+    fn animating(&self) -> bool {
+        matches!(self.animation, Some(Animation { start, .. }) if start.elapsed() < ANIMATION_DURATION)
+    }
+}
+
+async fn run(
+    eh: Weak<ElementHandle<Switch>>,
+    ed: EventDispatcher,
+    global: Arc<GlobalContent>,
+    animate: Arc<Notify>,
+) {
+    // redraw every frame until the thumb stops
+    let animation = async {
+        loop {
+            animate.notified().await;
+            loop {
+                tokio::time::sleep(FRAME_INTERVAL).await;
+                let Some(eh) = eh.upgrade() else {
+                    return;
+                };
+                eh.set_dirty();
+                if !eh.el_read().await.animating() {
+                    break;
+                }
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = run_pressable(eh.clone(), ed, global) => {}
+        _ = animation => {}
+    }
+}
+
+fn mix(from: Color, to: Color, t: f32) -> Color {
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Color::from_argb(
+        channel(from.a(), to.a()),
+        channel(from.r(), to.r()),
+        channel(from.g(), to.g()),
+        channel(from.b(), to.b()),
+    )
+}
@@ -0,0 +1,144 @@
+use std::{collections::HashMap, ops::Range};
+
+/// Heights of items in logical pixels. Measured heights are cached by
+/// key, the others are estimated.
+pub(super) struct Heights {
+    estimated: f32,
+    measured: HashMap<u64, f32>,
+    heights: Vec<f32>,
+
+    /// Offset of the top of every item, and the total height at last.
+    offsets: Vec<f32>,
+}
+
+impl Heights {
+    pub fn new() -> Self {
+        Heights {
+            estimated: 0.0,
+            measured: HashMap::new(),
+            heights: Vec::new(),
+            offsets: vec![0.0],
+        }
+    }
+
+    /// Rebuild heights of `len` items keyed by `key`, measured heights
+    /// of keys no longer exist are dropped.
+    pub fn reset(&mut self, len: usize, key: &dyn Fn(usize) -> u64, estimated: f32) {
+        let mut measured = HashMap::with_capacity(self.measured.len().min(len));
+        self.estimated = estimated;
+        self.heights.clear();
+
+        for index in 0..len {
+            let key = key(index);
+            let height = match self.measured.remove(&key) {
+                Some(height) => {
+                    measured.insert(key, height);
+                    height
+                }
+                None => estimated,
+            };
+            self.heights.push(height);
+        }
+
+        self.measured = measured;
+        self.update_offsets();
+    }
+
+    /// Cache the height measured for `key`, call [`Self::reset`] to
+    /// apply it.
+    pub fn remember(&mut self, key: u64, height: f32) {
+        self.measured.insert(key, height);
+    }
+
+    /// Cache the height measured for item `index` with `key`, returns
+    /// how much it grows. Call [`Self::update_offsets`] after all set.
+    pub fn set(&mut self, index: usize, key: u64, height: f32) -> f32 {
+        self.measured.insert(key, height);
+        height - std::mem::replace(&mut self.heights[index], height)
+    }
+
+    pub fn update_offsets(&mut self) {
+        self.offsets.clear();
+        self.offsets.push(0.0);
+
+        let mut offset = 0.0;
+        for height in &self.heights {
+            offset += height;
+            self.offsets.push(offset);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heights.len()
+    }
+
+    pub fn height(&self, index: usize) -> f32 {
+        self.heights[index]
+    }
+
+    /// Offset of the top of item `index`, the total height for `len`.
+    pub fn offset(&self, index: usize) -> f32 {
+        self.offsets[index]
+    }
+
+    pub fn total(&self) -> f32 {
+        self.offsets[self.len()]
+    }
+
+    /// Items overlapping `top..bottom`.
+    pub fn range(&self, top: f32, bottom: f32) -> Range<usize> {
+        let start = self.offsets[1..].partition_point(|&end| end <= top);
+        let end = self.offsets[..self.len()].partition_point(|&start| start < bottom);
+        start..end.max(start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(index: usize) -> u64 {
+        index as u64
+    }
+
+    #[test]
+    fn range_of_items() {
+        let mut heights = Heights::new();
+        heights.reset(4, &key, 10.0);
+        assert_eq!(heights.total(), 40.0);
+
+        assert_eq!(heights.range(0.0, 10.0), 0..1);
+        assert_eq!(heights.range(15.0, 25.0), 1..3);
+        assert_eq!(heights.range(10.0, 20.0), 1..2);
+        assert_eq!(heights.range(35.0, 100.0), 3..4);
+        assert_eq!(heights.range(50.0, 60.0), 4..4);
+    }
+
+    #[test]
+    fn reset_keeps_measured_keys() {
+        let mut heights = Heights::new();
+        heights.remember(2, 30.0);
+        heights.reset(4, &key, 10.0);
+        assert_eq!(heights.height(2), 30.0);
+        assert_eq!(heights.offset(3), 50.0);
+        assert_eq!(heights.total(), 60.0);
+
+        // key 2 is gone, so is its height
+        heights.reset(2, &key, 10.0);
+        heights.reset(4, &key, 10.0);
+        assert_eq!(heights.height(2), 10.0);
+        assert_eq!(heights.total(), 40.0);
+    }
+
+    #[test]
+    fn set_returns_growth() {
+        let mut heights = Heights::new();
+        heights.reset(3, &key, 10.0);
+        assert_eq!(heights.set(1, key(1), 25.0), 15.0);
+
+        heights.update_offsets();
+        assert_eq!(heights.offset(2), 35.0);
+        assert_eq!(heights.total(), 45.0);
+        assert_eq!(heights.range(12.0, 13.0), 1..2);
+    }
+}
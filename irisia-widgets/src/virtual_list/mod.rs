@@ -0,0 +1,376 @@
+//! List creating elements only for items in view, for lists too long
+//! to create all of them.
+
+use std::{
+    ops::Range,
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use irisia::{
+    element::{
+        ChildrenNodes, ChildrenSetter, Element, ElementHandle, RenderElement, UpdateElement,
+    },
+    primitive::{Pixel, Point, Region},
+    skia_safe::Rect,
+    structure::{add_one, once::Once, recycle::Recycle},
+    style::StyleContainer,
+    Result, UpdateWith,
+};
+use tokio::task::JoinHandle;
+
+use self::heights::Heights;
+
+mod heights;
+mod runtime;
+
+type KeyFn = Arc<dyn Fn(usize) -> u64 + Send + Sync>;
+type ItemFn<Pr> = Arc<dyn Fn(usize) -> Pr + Send + Sync>;
+
+/// Vertical list of `len` items of element `El`, scrolled by the mouse
+/// wheel. Only items in view, and `overscan` items before and after
+/// them, have elements, whose props are given by `item`.
+///
+/// Elements are keyed by `key`, and elements of items scrolled out are
+/// reused for items scrolled in, so `El` should take all of its states
+/// from props. Items are `estimated_height` high until their elements
+/// report their preferred heights, see
+/// [`ElementHandle::set_preferred_height`], which are cached by key.
+/// Keys are read again only when `len` or `version` changes.
+pub struct VirtualList<El: Element> {
+    runtime: Option<JoinHandle<()>>,
+    handle: Weak<ElementHandle<Self>>,
+    len: usize,
+    key: KeyFn,
+    item: ItemFn<El::BlankProps>,
+    estimated_height: Pixel,
+    overscan: usize,
+    version: u64,
+    heights: Heights,
+
+    /// Whether keys may have changed since the last layout.
+    keys_changed: bool,
+
+    /// Offset of the top of the view from the top of the first item.
+    scroll: f32,
+    scroll_to: Option<usize>,
+    visible: Range<usize>,
+
+    /// Keys, indexes and handles of items having elements.
+    rows: Vec<(u64, usize, Arc<ElementHandle<El>>)>,
+}
+
+pub struct VirtualListProps<Pr> {
+    pub len: usize,
+
+    /// Key of the item at an index, the index by default.
+    pub key: KeyFn,
+
+    /// Props of the item at an index, the default props by default.
+    pub item: ItemFn<Pr>,
+
+    /// Height of items not measured yet, `40px` by default.
+    pub estimated_height: Pixel,
+
+    /// Count of items having elements before and after items in view,
+    /// `3` by default.
+    pub overscan: usize,
+
+    /// Change it when keys change but `len` does not, e.g. items are
+    /// moved. `0` by default.
+    pub version: u64,
+}
+
+impl<Pr: Default + 'static> Default for VirtualListProps<Pr> {
+    fn default() -> Self {
+        VirtualListProps {
+            len: 0,
+            key: Arc::new(|index| index as u64),
+            item: Arc::new(|_| Pr::default()),
+            estimated_height: Pixel(40.0),
+            overscan: 3,
+            version: 0,
+        }
+    }
+}
+
+impl<Pr> VirtualListProps<Pr> {
+    pub fn len(mut self, len: usize) -> Self {
+        self.len = len;
+        self
+    }
+
+    /// Keys should be unique and stay the same for the same item when
+    /// items are inserted, removed or moved.
+    pub fn key<F>(mut self, key: F) -> Self
+    where
+        F: Fn(usize) -> u64 + Send + Sync + 'static,
+    {
+        self.key = Arc::new(key);
+        self
+    }
+
+    pub fn item<F>(mut self, item: F) -> Self
+    where
+        F: Fn(usize) -> Pr + Send + Sync + 'static,
+    {
+        self.item = Arc::new(item);
+        self
+    }
+
+    pub fn estimated_height(mut self, height: Pixel) -> Self {
+        self.estimated_height = height;
+        self
+    }
+
+    pub fn overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    pub fn version(mut self, version: u64) -> Self {
+        self.version = version;
+        self
+    }
+}
+
+impl<El> Element for VirtualList<El>
+where
+    El: Element + Send + Sync + for<'sty> UpdateWith<UpdateElement<'sty, El, El::BlankProps, ()>>,
+    El::BlankProps: 'static,
+{
+    type BlankProps = VirtualListProps<El::BlankProps>;
+
+    fn layout<'a, Ch>(&mut self, draw_region: Region, _: Ch, setter: ChildrenSetter<'a>)
+    where
+        Ch: ChildrenNodes,
+    {
+        self.apply_measured();
+
+        let view_height = (draw_region.1 .1 - draw_region.0 .1).0.max(0.0);
+        if let Some(index) = self.scroll_to.take() {
+            self.scroll = self.heights.offset(index.min(self.heights.len()));
+        }
+        let max_scroll = (self.heights.total() - view_height).max(0.0);
+        self.scroll = self.scroll.clamp(0.0, max_scroll);
+
+        self.visible = self.heights.range(self.scroll, self.scroll + view_height);
+        let created = self.visible.start.saturating_sub(self.overscan)
+            ..(self.visible.end + self.overscan).min(self.heights.len());
+
+        let (key, item) = (&self.key, &self.item);
+        let mut children = setter.set_children(Recycle::new(created.clone().map(|index| {
+            (
+                key(index),
+                Once(add_one::<El, _, _, _, _>(item(index), (), (), |_: &_| {})),
+            )
+        })));
+
+        let (left, right) = (draw_region.0 .0, draw_region.1 .0);
+        let top = draw_region.0 .1 - Pixel(self.scroll);
+        let heights = &self.heights;
+
+        // one region for every child, never fails
+        let _ = children.layout(created.clone().map(|index| {
+            let start = top + Pixel(heights.offset(index));
+            (
+                Point(left, start),
+                Point(right, start + Pixel(heights.height(index))),
+            )
+        }));
+
+        self.rows = created
+            .zip(children.handles::<El>())
+            .map(|(index, handle)| (key(index), index, handle))
+            .collect();
+
+        if let Some(handle) = self.handle.upgrade() {
+            handle.set_dirty();
+        }
+    }
+
+    fn render(
+        &mut self,
+        mut renderer: RenderElement,
+        _: Duration,
+        draw_region: Region,
+    ) -> Result<()> {
+        let (start, end) = draw_region;
+        let canvas = renderer.canvas();
+        let count = canvas.save();
+        canvas.clip_rect(
+            Rect::new(
+                start.0.to_physical(),
+                start.1.to_physical(),
+                end.0.to_physical(),
+                end.1.to_physical(),
+            ),
+            None,
+            true,
+        );
+
+        let result = renderer.render_children().map(|_| ());
+        renderer.canvas().restore_to_count(count);
+        renderer.set_interact_region(draw_region);
+        result
+    }
+}
+
+impl<'a, El, Sty> UpdateWith<UpdateElement<'a, Self, VirtualListProps<El::BlankProps>, Sty>>
+    for VirtualList<El>
+where
+    El: Element + Send + Sync,
+    El::BlankProps: 'static,
+    Sty: StyleContainer,
+{
+    fn create_with(
+        updater: UpdateElement<'a, Self, VirtualListProps<El::BlankProps>, Sty>,
+    ) -> Self {
+        let handle = updater.handle;
+        let mut list = VirtualList {
+            runtime: Some(tokio::spawn(runtime::run(
+                Arc::downgrade(handle),
+                handle.event_dispatcher().clone(),
+                handle.global().clone(),
+            ))),
+            handle: Arc::downgrade(handle),
+            len: 0,
+            key: updater.props.key.clone(),
+            item: updater.props.item.clone(),
+            estimated_height: updater.props.estimated_height,
+            overscan: 0,
+            version: updater.props.version,
+            heights: Heights::new(),
+            keys_changed: true,
+            scroll: 0.0,
+            scroll_to: None,
+            visible: 0..0,
+            rows: Vec::new(),
+        };
+
+        list.update_with(updater, false);
+        list
+    }
+
+    fn update_with(
+        &mut self,
+        updater: UpdateElement<'a, Self, VirtualListProps<El::BlankProps>, Sty>,
+        _equality_matters: bool,
+    ) -> bool {
+        let VirtualListProps {
+            len,
+            key,
+            item,
+            estimated_height,
+            overscan,
+            version,
+        } = updater.props;
+
+        // keys cannot be compared and rebuilding heights calls them `len`
+        // times, so only rebuilt after `len`, `version` or the estimate
+        // changes
+        if len != self.len || version != self.version || estimated_height != self.estimated_height {
+            self.keys_changed = true;
+        }
+        self.len = len;
+        self.version = version;
+        self.key = key;
+        self.item = item;
+        self.estimated_height = estimated_height;
+        self.overscan = overscan;
+
+        false
+    }
+}
+
+impl<El: Element> Drop for VirtualList<El> {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            runtime.abort();
+        }
+    }
+}
+
+impl<El: Element> VirtualList<El> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Items in view in the last layout, without overscanned items.
+    pub fn visible_range(&self) -> Range<usize> {
+        self.visible.clone()
+    }
+
+    /// Offset of the top of the view from the top of the first item.
+    pub fn scroll_offset(&self) -> Pixel {
+        Pixel(self.scroll)
+    }
+
+    /// Scroll to `offset`, clamped in the next layout.
+    pub fn set_scroll_offset(&mut self, offset: Pixel) {
+        self.scroll = offset.0;
+        self.scroll_to = None;
+        self.request_layout();
+    }
+
+    pub fn scroll_by(&mut self, delta: Pixel) {
+        let offset = self.scroll_offset() + delta;
+        self.set_scroll_offset(offset);
+    }
+
+    /// Scroll to put the top of item `index` at the top of the view.
+    /// Items before it not measured yet are estimated, so it may not be
+    /// exactly at the top after they are measured.
+    pub fn scroll_to_index(&mut self, index: usize) {
+        self.scroll_to = Some(index);
+        self.request_layout();
+    }
+
+    fn request_layout(&self) {
+        if let Some(handle) = self.handle.upgrade() {
+            handle.request_layout();
+        }
+    }
+
+    /// Cache preferred heights of items having elements. Items above
+    /// the view changing their heights scroll the view by the same
+    /// distance, to keep items in view still.
+    fn apply_measured(&mut self) {
+        let measured = self
+            .rows
+            .iter()
+            .filter_map(|(key, index, handle)| Some((*key, *index, handle.preferred_height()?.0)));
+
+        if self.keys_changed {
+            // indexes of the rows are outdated
+            for (key, _, height) in measured {
+                self.heights.remember(key, height);
+            }
+            self.heights
+                .reset(self.len, &*self.key, self.estimated_height.0.max(0.0));
+            self.keys_changed = false;
+            return;
+        }
+
+        let anchor = self.visible.start;
+        let mut changed = false;
+        for (key, index, height) in measured {
+            let grown = self.heights.set(index, key, height);
+            if grown == 0.0 {
+                continue;
+            }
+            changed = true;
+            if index < anchor {
+                self.scroll += grown;
+            }
+        }
+
+        if changed {
+            self.heights.update_offsets();
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Weak,
+};
+
+use irisia::{
+    application::GlobalContent,
+    element::{Element, ElementHandle},
+    event::{
+        standard::{ElementAbandoned, PointerEntered, PointerOut},
+        EventDispatcher,
+    },
+    primitive::Pixel,
+    winit::event::MouseScrollDelta,
+    StaticWindowEvent,
+};
+
+use super::VirtualList;
+
+/// Distance scrolled by a line of the mouse wheel.
+const LINE_HEIGHT: Pixel = Pixel(40.0);
+
+pub(super) async fn run<El: Element>(
+    eh: Weak<ElementHandle<VirtualList<El>>>,
+    ed: EventDispatcher,
+    global: Arc<GlobalContent>,
+) {
+    let win_ed = global.global_event_dispatcher();
+    let hovered = AtomicBool::new(false);
+
+    let hover = async {
+        loop {
+            let value = tokio::select! {
+                _ = ed.recv_sys::<PointerEntered>() => true,
+                _ = ed.recv_sys::<PointerOut>() => false,
+            };
+            hovered.store(value, Ordering::Relaxed);
+        }
+    };
+
+    let wheel = async {
+        let mut lock = win_ed.lock();
+        loop {
+            let delta = match lock.recv_sys::<StaticWindowEvent>().await {
+                StaticWindowEvent::MouseWheel { delta, .. } if hovered.load(Ordering::Relaxed) => {
+                    delta
+                }
+                _ => continue,
+            };
+
+            let Some(eh) = eh.upgrade() else {
+                return;
+            };
+
+            // positive deltas scroll up
+            let delta = match delta {
+                MouseScrollDelta::LineDelta(_, lines) => LINE_HEIGHT * -lines,
+                MouseScrollDelta::PixelDelta(position) => -Pixel::from_physical(position.y as f32),
+            };
+            eh.el_write().await.scroll_by(delta);
+        }
+    };
+
+    tokio::select! {
+        _ = ed.recv_sys::<ElementAbandoned>() => {}
+        _ = async { tokio::join!(hover, wheel) } => {}
+    }
+}
@@ -1,35 +1,45 @@
 use std::{
-    sync::{Arc, Mutex as StdMutex},
-    time::Duration,
+    any::type_name,
+    sync::{atomic::AtomicBool, Arc, Mutex as StdMutex},
+    time::{Duration, Instant},
 };
 
 use irisia_backend::{
     skia_safe::{colors::TRANSPARENT, Canvas},
     window_handle::{RawWindowHandle, WindowBuilder},
     winit::dpi::PhysicalSize,
-    AppWindow, StaticWindowEvent, WinitWindow,
+    AppWindow, BackendError, FrameTiming, Recovery, StaticWindowEvent, WinitWindow,
 };
 
 use crate::{
     dom::{add_one, update::ElementModelUpdater, EMUpdateContent, ElementModel},
     element::Element,
     event::EventDispatcher,
-    primitive::{Pixel, Point, Region},
+    log,
+    primitive::{Direction, Pixel, Point, Region},
     Result, UpdateWith,
 };
 
 use super::{
-    content::GlobalContent,
+    content::{GlobalContent, LayerCapture},
+    error::{ErrorPolicy, RenderErrorAction, WindowError, WindowErrorKind},
     event_comp::{global::focusing::Focusing, GlobalEventMgr},
+    frame_stats::{FrameRecord, FrameStatsRecorder},
     redraw_scheduler::{RedrawScheduler, ROOT_LAYER_ID},
     EmptyUpdateOptions, Window,
 };
 
+/// Layouts in one frame at most, elements changing their preferred
+/// heights every pass are laid out again in the next frame.
+const MAX_LAYOUT_PASSES: usize = 3;
+
 pub(super) struct BackendRuntime<El: Element> {
     gem: GlobalEventMgr,
     gc: Arc<GlobalContent>,
     root_element: ElementModel<El, (), ()>,
     redraw_scheduler: RedrawScheduler,
+    frame_stats: Arc<StdMutex<FrameStatsRecorder>>,
+    renderer_failures: u32,
 }
 
 impl<El> AppWindow for BackendRuntime<El>
@@ -37,30 +47,114 @@ where
     El: Element + for<'a> UpdateWith<EmptyUpdateOptions<'a, El>>,
 {
     fn on_redraw(&mut self, canvas: &mut Canvas, interval: Duration) -> Result<()> {
-        canvas.clear(TRANSPARENT);
-        self.redraw_scheduler.redraw(
+        // elements measured in layout may change their preferred heights,
+        // which containers read in the next pass
+        for _ in 0..MAX_LAYOUT_PASSES {
+            if !self.gc.take_layout_request() {
+                break;
+            }
+            self.layout(self.gc.window().inner_size());
+        }
+
+        // recorded locally, `Window::frame_stats` should not wait for the frame
+        let mut frame = FrameRecord::default();
+
+        let errors = self.redraw_scheduler.redraw(
             canvas,
             |lr, reg, interval| self.root_element.render(lr, reg, interval),
             interval,
             &mut self.gc.redraw_list.lock().unwrap(),
-        )?;
+            &mut frame,
+        );
+
+        let action = self.gc.error_policy().on_render_error;
+        for err in errors {
+            tracing::error!(target: log::RENDER, "{err}");
+            match (action, err.layer()) {
+                (RenderErrorAction::SkipLayer, Some(layer)) => {
+                    self.redraw_scheduler.skip_layer(layer)
+                }
+                (RenderErrorAction::CloseWindow, _) => self.gc.close_window(),
+                _ => {}
+            }
+            self.gc.global_ed.emit(err);
+        }
 
         // composite
+        let _span = tracing::trace_span!(target: log::RENDER, "composite").entered();
+        let start = Instant::now();
         canvas.clear(TRANSPARENT);
-        self.redraw_scheduler.composite(canvas)
+        let result = self.redraw_scheduler.composite(canvas);
+        frame.record_composite(start.elapsed(), self.redraw_scheduler.layer_count());
+        self.frame_stats.lock().unwrap().record_redraw(frame);
+
+        for LayerCapture { id, giver } in self.gc.layer_captures.lock().unwrap().drain(..) {
+            let _ = giver.send(self.redraw_scheduler.capture_layer(id));
+        }
+
+        result
     }
 
     fn on_window_event(&mut self, event: StaticWindowEvent) {
+        let _span = tracing::trace_span!(target: log::EVENT, "window event").entered();
+        tracing::trace!(target: log::EVENT, ?event);
+
         if let StaticWindowEvent::Resized(size) = &event {
-            self.root_element.layout(window_size_to_draw_region(*size));
+            self.layout(*size);
         }
 
         if let Some(npe) = self.gem.emit_event(event, &self.gc) {
-            if !self.root_element.emit_event(&npe) {
+            // portals are hit tested before the root element, the topmost first
+            for overlay in self.redraw_scheduler.overlays().iter().rev() {
+                if overlay.emit_event(&npe) {
+                    npe.occlude();
+                }
+            }
+
+            if !self.root_element.emit_event(&npe) && !npe.is_occluded() {
                 npe.focus_on(None);
             }
         }
     }
+
+    fn on_backend_error(&mut self, error: BackendError) -> Recovery {
+        tracing::error!(target: log::BACKEND, "{error}");
+        let error = WindowError::from_backend(error);
+        let policy = self.gc.error_policy();
+
+        let recovery = match error.kind {
+            WindowErrorKind::RendererInit if self.renderer_failures < policy.renderer_retries => {
+                self.renderer_failures += 1;
+                Recovery::RetryRenderer
+            }
+            WindowErrorKind::RendererInit => Recovery::CloseWindow,
+            _ if policy.on_render_error == RenderErrorAction::CloseWindow => Recovery::CloseWindow,
+            _ => Recovery::Ignore,
+        };
+
+        self.gc.global_ed.emit(error);
+        recovery
+    }
+
+    fn on_frame_presented(&mut self, timing: FrameTiming) {
+        self.renderer_failures = 0;
+        self.frame_stats
+            .lock()
+            .unwrap()
+            .frame_presented(timing, &self.gc.global_ed);
+    }
+}
+
+impl<El: Element> BackendRuntime<El> {
+    fn layout(&mut self, size: PhysicalSize<u32>) {
+        let _span = tracing::debug_span!(target: log::LAYOUT, "layout", ?size).entered();
+        let start = Instant::now();
+        self.root_element.layout(window_size_to_draw_region(size));
+        self.frame_stats
+            .lock()
+            .unwrap()
+            .record_layout(start.elapsed());
+    }
 }
 
 fn window_size_to_draw_region(size: PhysicalSize<u32>) -> Region {
@@ -79,18 +173,27 @@ where
     F: FnOnce(WindowBuilder) -> WindowBuilder + Send + 'static,
 {
     let ev_disp = EventDispatcher::new();
+    let frame_stats = Arc::new(StdMutex::new(FrameStatsRecorder::new()));
+    let error_policy = Arc::new(StdMutex::new(ErrorPolicy::default()));
 
     let create_app = {
         let ev_disp = ev_disp.clone();
+        let frame_stats = frame_stats.clone();
+        let error_policy = error_policy.clone();
 
         move |window: Arc<WinitWindow>, close_handle| {
-            let (redraw_scheduler, redraw_list) = RedrawScheduler::new(window.clone());
+            let (redraw_scheduler, redraw_list) =
+                RedrawScheduler::new(window.clone(), type_name::<El>());
 
             let gc = Arc::new(GlobalContent {
                 global_ed: ev_disp,
-                focusing: Focusing::new(),
+                focusing: Focusing::new(window.clone()),
+                pointer_capture: Default::default(),
                 window,
                 redraw_list: StdMutex::new(redraw_list),
+                layout_requested: AtomicBool::new(false),
+                layer_captures: StdMutex::new(Vec::new()),
+                error_policy,
                 close_handle,
             });
 
@@ -99,6 +202,7 @@ where
                 content: EMUpdateContent {
                     global_content: &gc,
                     dep_layer_id: ROOT_LAYER_ID,
+                    direction: Direction::Ltr,
                 },
             });
 
@@ -111,6 +215,8 @@ where
                 gem: GlobalEventMgr::new(),
                 gc,
                 redraw_scheduler,
+                frame_stats,
+                renderer_failures: 0,
             }
         }
     };
@@ -124,5 +230,7 @@ where
         winit_window: raw_window,
         close_handle,
         event_dispatcher: ev_disp,
+        frame_stats,
+        error_policy,
     })
 }
@@ -1,20 +1,37 @@
-use std::sync::{Arc, Mutex as StdMutex};
+use std::{
+    any::Any,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex as StdMutex,
+    },
+};
 
-use irisia_backend::{window_handle::CloseHandle, WinitWindow};
+use irisia_backend::{window_handle::CloseHandle, CapturedFrame, WinitWindow};
+use tokio::sync::oneshot;
 
-use crate::event::EventDispatcher;
+use crate::{event::EventDispatcher, log, Result};
 
 use super::{
-    event_comp::global::focusing::Focusing,
+    error::{ErrorPolicy, WindowError},
+    event_comp::global::{capture::PointerCapture, focusing::Focusing},
     redraw_scheduler::{LayerId, RedrawList},
 };
 
 pub struct GlobalContent {
     pub(super) focusing: Focusing,
+    pub(super) pointer_capture: PointerCapture,
     pub(super) global_ed: EventDispatcher,
     pub(super) window: Arc<WinitWindow>,
     pub(super) close_handle: CloseHandle,
     pub(super) redraw_list: StdMutex<RedrawList>,
+    pub(super) layout_requested: AtomicBool,
+    pub(super) layer_captures: StdMutex<Vec<LayerCapture>>,
+    pub(super) error_policy: Arc<StdMutex<ErrorPolicy>>,
+}
+
+pub(super) struct LayerCapture {
+    pub id: LayerId,
+    pub giver: oneshot::Sender<Result<CapturedFrame>>,
 }
 
 impl GlobalContent {
@@ -26,6 +43,10 @@ impl GlobalContent {
         &self.focusing
     }
 
+    pub(crate) fn pointer_capture(&self) -> &PointerCapture {
+        &self.pointer_capture
+    }
+
     pub fn global_event_dispatcher(&self) -> &EventDispatcher {
         &self.global_ed
     }
@@ -45,4 +66,46 @@ impl GlobalContent {
     pub(crate) fn request_redraw(&self, id: LayerId) {
         self.redraw_list.lock().unwrap().request_redraw(id);
     }
+
+    pub(crate) fn request_composite(&self) {
+        self.redraw_list.lock().unwrap().request_composite();
+    }
+
+    /// Lay out the window before the next redraw. Only requests a redraw
+    /// of the window, not of any layer, so it can be called when rendering.
+    pub(crate) fn request_layout(&self) {
+        if !self.layout_requested.swap(true, Ordering::Relaxed) {
+            self.window.request_redraw();
+        }
+    }
+
+    pub(super) fn take_layout_request(&self) -> bool {
+        self.layout_requested.swap(false, Ordering::Relaxed)
+    }
+
+    pub(crate) fn error_policy(&self) -> ErrorPolicy {
+        *self.error_policy.lock().unwrap()
+    }
+
+    pub(crate) fn report_panic(
+        &self,
+        layer: LayerId,
+        element: &'static str,
+        payload: Box<dyn Any + Send>,
+    ) {
+        let error = WindowError::element_panic(layer, element, payload);
+        tracing::error!(target: log::RENDER, "{error}");
+        self.global_ed.emit(error);
+    }
+
+    /// Capture the independent layer after the next frame composited.
+    pub(crate) async fn capture_layer(&self, id: LayerId) -> Result<CapturedFrame> {
+        let (giver, receiver) = oneshot::channel();
+        self.layer_captures
+            .lock()
+            .unwrap()
+            .push(LayerCapture { id, giver });
+        self.request_composite();
+        receiver.await?
+    }
 }
@@ -0,0 +1,255 @@
+use std::{any::Any, fmt::Display, sync::Arc};
+
+use anyhow::anyhow;
+use irisia_backend::{
+    skia_safe::{Canvas, Color, Paint, PaintStyle, Rect},
+    BackendError,
+};
+
+use crate as irisia;
+use crate::{primitive::Region, Event};
+
+use super::redraw_scheduler::LayerId;
+
+/// Emitted on the window's event dispatcher when something went wrong
+/// while rendering the window.
+#[derive(Event, Clone)]
+pub struct WindowError {
+    pub kind: WindowErrorKind,
+    pub error: Arc<anyhow::Error>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum WindowErrorKind {
+    /// The renderer of the window cannot be created.
+    RendererInit,
+
+    /// The renderer cannot be resized to the window size.
+    Resize,
+
+    /// The frame cannot be drawn or presented.
+    Present,
+
+    /// Recorded content of a layer cannot be flushed.
+    LayerFlush { layer: LayerId },
+
+    /// An element returned an error on rendering.
+    ElementRender {
+        layer: LayerId,
+        element: &'static str,
+    },
+
+    /// An element panicked on rendering or layout. The element is replaced
+    /// by the panic placeholder from then on.
+    ElementPanic {
+        layer: LayerId,
+        element: &'static str,
+    },
+}
+
+impl WindowError {
+    pub(super) fn from_backend(error: BackendError) -> Self {
+        let (kind, error) = match error {
+            BackendError::RendererInit(e) => (WindowErrorKind::RendererInit, e),
+            BackendError::Resize(e) => (WindowErrorKind::Resize, e),
+            BackendError::Render(e) => (WindowErrorKind::Present, e),
+        };
+
+        WindowError {
+            kind,
+            error: Arc::new(error),
+        }
+    }
+
+    /// Classify an error returned by redrawing the layer `layer`, which is
+    /// owned by `element`.
+    pub(super) fn from_redraw(error: anyhow::Error, layer: LayerId, element: &'static str) -> Self {
+        let kind = if let Some(e) = error.downcast_ref::<ElementRenderError>() {
+            WindowErrorKind::ElementRender {
+                layer: e.layer,
+                element: e.element,
+            }
+        } else if let Some(e) = error.downcast_ref::<LayerFlushError>() {
+            WindowErrorKind::LayerFlush { layer: e.layer }
+        } else {
+            WindowErrorKind::ElementRender { layer, element }
+        };
+
+        WindowError {
+            kind,
+            error: Arc::new(error),
+        }
+    }
+
+    pub(crate) fn element_panic(
+        layer: LayerId,
+        element: &'static str,
+        payload: Box<dyn Any + Send>,
+    ) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(msg) => *msg,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(msg) => msg.to_string(),
+                Err(_) => "unknown panic payload".to_string(),
+            },
+        };
+
+        WindowError {
+            kind: WindowErrorKind::ElementPanic { layer, element },
+            error: Arc::new(anyhow!("{message}")),
+        }
+    }
+
+    /// The layer failed to render, if any.
+    pub fn layer(&self) -> Option<LayerId> {
+        match self.kind {
+            WindowErrorKind::LayerFlush { layer }
+            | WindowErrorKind::ElementRender { layer, .. }
+            | WindowErrorKind::ElementPanic { layer, .. } => Some(layer),
+            _ => None,
+        }
+    }
+}
+
+impl Display for WindowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            WindowErrorKind::RendererInit => write!(f, "renderer creation error: {}", self.error),
+            WindowErrorKind::Resize => write!(f, "cannot resize window: {}", self.error),
+            WindowErrorKind::Present => write!(f, "cannot present frame: {}", self.error),
+            WindowErrorKind::LayerFlush { layer } => {
+                write!(f, "cannot flush layer {layer:?}: {}", self.error)
+            }
+            WindowErrorKind::ElementRender { layer, element } => {
+                write!(
+                    f,
+                    "element `{element}` on layer {layer:?} failed to render: {}",
+                    self.error
+                )
+            }
+            WindowErrorKind::ElementPanic { layer, element } => {
+                write!(
+                    f,
+                    "element `{element}` on layer {layer:?} panicked: {}",
+                    self.error
+                )
+            }
+        }
+    }
+}
+
+/// How a window recovers from errors.
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorPolicy {
+    /// Times to retry creating the renderer before closing the window.
+    pub renderer_retries: u32,
+
+    pub on_render_error: RenderErrorAction,
+
+    /// Drawn in place of elements which have panicked.
+    pub panic_placeholder: PanicPlaceholder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderErrorAction {
+    /// Keep whatever the broken layer recorded before the error.
+    KeepLayer,
+
+    /// Clear the broken layer until it is redrawn successfully.
+    SkipLayer,
+
+    CloseWindow,
+}
+
+impl Default for ErrorPolicy {
+    fn default() -> Self {
+        ErrorPolicy {
+            renderer_retries: 3,
+            on_render_error: RenderErrorAction::KeepLayer,
+            panic_placeholder: PanicPlaceholder::Default,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum PanicPlaceholder {
+    /// Draw nothing.
+    Empty,
+
+    /// A red crossed box over the drawing region.
+    Default,
+
+    Custom(fn(&mut Canvas, Region)),
+}
+
+impl PanicPlaceholder {
+    pub(crate) fn draw(&self, canvas: &mut Canvas, region: Region) {
+        match self {
+            Self::Empty => {}
+            Self::Default => draw_default_placeholder(canvas, region),
+            Self::Custom(f) => f(canvas, region),
+        }
+    }
+}
+
+fn draw_default_placeholder(canvas: &mut Canvas, (start, end): Region) {
+    let rect = Rect::new(
+        start.0.to_physical(),
+        start.1.to_physical(),
+        end.0.to_physical(),
+        end.1.to_physical(),
+    );
+
+    let mut paint = Paint::default();
+    paint
+        .set_anti_alias(true)
+        .set_color(Color::from_argb(48, 255, 0, 0));
+    canvas.draw_rect(rect, &paint);
+
+    paint
+        .set_style(PaintStyle::Stroke)
+        .set_stroke_width(2.0)
+        .set_color(Color::RED);
+    canvas.draw_rect(rect, &paint);
+    canvas.draw_line((rect.left, rect.top), (rect.right, rect.bottom), &paint);
+    canvas.draw_line((rect.right, rect.top), (rect.left, rect.bottom), &paint);
+}
+
+/// An element returned an error on rendering.
+#[derive(Debug)]
+pub(crate) struct ElementRenderError {
+    pub layer: LayerId,
+    pub element: &'static str,
+    pub source: anyhow::Error,
+}
+
+impl Display for ElementRenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`: {}", self.element, self.source)
+    }
+}
+
+impl std::error::Error for ElementRenderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// Recorded content of a layer cannot be flushed.
+#[derive(Debug)]
+pub(crate) struct LayerFlushError {
+    pub layer: LayerId,
+    pub source: anyhow::Error,
+}
+
+impl Display for LayerFlushError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl std::error::Error for LayerFlushError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&*self.source)
+    }
+}
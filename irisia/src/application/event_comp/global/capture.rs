@@ -0,0 +1,69 @@
+use std::sync::Mutex as StdMutex;
+
+use crate::event::EventDispatcher;
+
+/// Element capturing the pointer. It receives [`PointerMove`] and
+/// [`PointerUp`] from the window even when the pointer is outside of
+/// it, until the pointer is released or leaves the window.
+///
+/// [`PointerMove`]: crate::event::standard::PointerMove
+/// [`PointerUp`]: crate::event::standard::PointerUp
+#[derive(Default)]
+pub struct PointerCapture {
+    captured: StdMutex<Option<Captured>>,
+}
+
+struct Captured {
+    ed: EventDispatcher,
+
+    /// The release is being emitted, the capture is dropped after it.
+    ending: bool,
+}
+
+impl PointerCapture {
+    pub fn capture(&self, ed: EventDispatcher) {
+        *self.captured.lock().unwrap() = Some(Captured { ed, ending: false });
+    }
+
+    /// Release the capture if `ed` is capturing the pointer.
+    pub fn release_checked(&self, ed: &EventDispatcher) {
+        let mut guard = self.captured.lock().unwrap();
+        if matches!(&*guard, Some(captured) if captured.ed.is_same(ed)) {
+            *guard = None;
+        }
+    }
+
+    pub fn release(&self) {
+        *self.captured.lock().unwrap() = None;
+    }
+
+    pub fn is_captured_by(&self, ed: &EventDispatcher) -> bool {
+        matches!(&*self.captured.lock().unwrap(), Some(captured) if captured.ed.is_same(ed))
+    }
+
+    /// Like [`Self::is_captured_by`], but `false` once the release is
+    /// being emitted.
+    pub fn is_held_by(&self, ed: &EventDispatcher) -> bool {
+        matches!(
+            &*self.captured.lock().unwrap(),
+            Some(captured) if !captured.ending && captured.ed.is_same(ed)
+        )
+    }
+
+    pub fn captured(&self) -> Option<EventDispatcher> {
+        self.captured
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|captured| captured.ed.clone())
+    }
+
+    /// Mark the capture ending before emitting the release, returns the
+    /// capturing element.
+    pub fn end(&self) -> Option<EventDispatcher> {
+        let mut guard = self.captured.lock().unwrap();
+        let captured = guard.as_mut()?;
+        captured.ending = true;
+        Some(captured.ed.clone())
+    }
+}
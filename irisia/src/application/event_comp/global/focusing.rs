@@ -1,46 +1,118 @@
 use std::sync::{Arc, Mutex as StdMutex};
 
-use crate::event::{
-    standard::{Blured, Focused},
-    EventDispatcher,
+use irisia_backend::{
+    winit::{dpi::PhysicalPosition, event::Ime},
+    WinitWindow,
 };
 
-pub struct Focusing(Arc<StdMutex<Option<EventDispatcher>>>);
+use crate::{
+    event::{
+        standard::{Blured, Focused, ImeCommit, ImePreedit},
+        EventDispatcher,
+    },
+    primitive::Region,
+};
+
+pub struct Focusing {
+    window: Arc<WinitWindow>,
+    state: StdMutex<FocusState>,
+}
+
+#[derive(Default)]
+struct FocusState {
+    focused: Option<EventDispatcher>,
+
+    /// Caret of the focused element, `Some` if it accepts IME.
+    ime_caret: Option<Region>,
+}
 
 impl Focusing {
-    pub fn new() -> Self {
-        Focusing(Default::default())
+    pub fn new(window: Arc<WinitWindow>) -> Self {
+        Focusing {
+            window,
+            state: Default::default(),
+        }
     }
 
     pub fn focus(&self, ed: EventDispatcher) {
-        let mut guard = self.0.lock().unwrap();
+        let mut guard = self.state.lock().unwrap();
 
-        match &*guard {
+        match &guard.focused {
             Some(old_ed) if ed.ptr_eq(old_ed) => return,
             _ => {
-                blur(&mut guard);
+                self.blur_locked(&mut guard);
                 ed.emit_sys(Focused);
-                *guard = Some(ed);
+                guard.focused = Some(ed);
             }
         }
     }
 
     pub fn blur(&self) {
-        blur(&mut self.0.lock().unwrap())
+        self.blur_locked(&mut self.state.lock().unwrap())
     }
 
     pub fn blur_checked(&self, ed: &EventDispatcher) {
-        let mut guard = self.0.lock().unwrap();
-        if let Some(focused) = &mut *guard {
+        let mut guard = self.state.lock().unwrap();
+        if let Some(focused) = &guard.focused {
             if focused.is_same(ed) {
-                blur(&mut guard);
+                self.blur_locked(&mut guard);
             }
         }
     }
-}
 
-fn blur(ed: &mut Option<EventDispatcher>) {
-    if let Some(ed) = ed.take() {
-        ed.emit_sys(Blured);
+    /// Set the IME caret of `ed`, ignored if `ed` is not focused. IME is
+    /// allowed while the caret is `Some`, and the candidate window is
+    /// placed below the caret.
+    pub fn set_ime_caret(&self, ed: &EventDispatcher, caret: Option<Region>) {
+        let mut guard = self.state.lock().unwrap();
+        match &guard.focused {
+            Some(focused) if focused.is_same(ed) => {}
+            _ => return,
+        }
+
+        if guard.ime_caret == caret {
+            return;
+        }
+
+        if guard.ime_caret.is_some() != caret.is_some() {
+            self.window.set_ime_allowed(caret.is_some());
+        }
+        if let Some((start, end)) = caret {
+            self.window.set_ime_position(PhysicalPosition::new(
+                start.0.to_physical(),
+                end.1.to_physical(),
+            ));
+        }
+        guard.ime_caret = caret;
+    }
+
+    /// Deliver the IME event to the focused element, if it accepts IME.
+    pub fn emit_ime(&self, ime: &Ime) {
+        let guard = self.state.lock().unwrap();
+        let (Some(ed), Some(_)) = (&guard.focused, guard.ime_caret) else {
+            return;
+        };
+
+        match ime {
+            Ime::Preedit(text, cursor) => ed.emit_sys(ImePreedit {
+                text: text.clone(),
+                cursor: *cursor,
+            }),
+            Ime::Commit(text) => ed.emit_sys(ImeCommit(text.clone())),
+            Ime::Disabled => ed.emit_sys(ImePreedit {
+                text: String::new(),
+                cursor: None,
+            }),
+            Ime::Enabled => {}
+        }
+    }
+
+    fn blur_locked(&self, state: &mut FocusState) {
+        if state.ime_caret.take().is_some() {
+            self.window.set_ime_allowed(false);
+        }
+        if let Some(ed) = state.focused.take() {
+            ed.emit_sys(Blured);
+        }
     }
 }
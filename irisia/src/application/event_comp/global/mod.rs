@@ -14,6 +14,7 @@ use crate::{
 
 use self::new_event::{NewPointerEvent, PointerStateChange};
 
+pub(crate) mod capture;
 pub(crate) mod focusing;
 pub(crate) mod new_event;
 
@@ -45,16 +46,39 @@ impl GlobalEventMgr {
     ) -> Option<NewPointerEvent<'a>> {
         match cursor_behavior(&event, self.pointer_state, self.last_cursor_position) {
             Some((new_position, new_pointer_state)) => {
+                let last_position = self.last_cursor_position;
                 let npe = NewPointerEvent::new(event, self, gc, new_position, new_pointer_state);
+
+                // marked ending before any release is emitted, so the element
+                // can tell whether it missed one. released when `npe` drops.
+                let captured = match npe.pointer_state_change {
+                    PointerStateChange::Release | PointerStateChange::LeaveViewport => {
+                        gc.pointer_capture.end()
+                    }
+                    _ => gc.pointer_capture.captured(),
+                };
+
                 emit_physical_pointer_event(
                     &gc.global_ed,
                     new_position,
                     npe.cursor_delta,
                     npe.pointer_state_change,
                 );
+                if let Some(captured) = captured {
+                    // the pointer has no position once it left the window
+                    emit_captured_pointer_event(
+                        &captured,
+                        new_position.or(last_position),
+                        npe.cursor_delta,
+                        npe.pointer_state_change,
+                    );
+                }
                 Some(npe)
             }
             None => {
+                if let StaticWindowEvent::Ime(ime) = &event {
+                    gc.focusing.emit_ime(ime);
+                }
                 gc.global_ed.emit_sys(event);
                 None
             }
@@ -88,6 +112,37 @@ fn emit_physical_pointer_event(
     }
 }
 
+/// The capturing element receives pointer events wherever the pointer
+/// is, and a release at the last known position when the pointer leaves
+/// the window.
+fn emit_captured_pointer_event(
+    ed: &EventDispatcher,
+    position: Option<Point>,
+    delta: Option<(Pixel, Pixel)>,
+    new_pointer_state: PointerStateChange,
+) {
+    let Some(position) = position else {
+        return;
+    };
+
+    match new_pointer_state {
+        PointerStateChange::Unchange => {
+            if let Some(delta) = delta {
+                ed.emit_sys(PointerMove {
+                    is_current: true,
+                    delta,
+                    position,
+                });
+            }
+        }
+        PointerStateChange::Release | PointerStateChange::LeaveViewport => ed.emit_sys(PointerUp {
+            is_current: true,
+            position,
+        }),
+        PointerStateChange::Press | PointerStateChange::EnterViewport => {}
+    }
+}
+
 fn cursor_behavior(
     event: &StaticWindowEvent,
     old_state: PointerState,
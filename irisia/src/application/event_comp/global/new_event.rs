@@ -17,6 +17,7 @@ pub struct NewPointerEvent<'a> {
     pub(crate) new_position: Option<Point>,
     pub(crate) cursor_delta: Option<(Pixel, Pixel)>,
     new_focused: Cell<NewFocused>,
+    occluded: Cell<bool>,
     pub(crate) new_pointer_state: PointerState,
     pub(crate) pointer_state_change: PointerStateChange,
 }
@@ -54,6 +55,7 @@ impl<'a> NewPointerEvent<'a> {
             new_position,
             cursor_delta,
             new_focused: Cell::new(NewFocused::Unchanged),
+            occluded: Cell::new(false),
             new_pointer_state,
             pointer_state_change: PointerStateChange::difference_between(
                 gem.pointer_state,
@@ -64,6 +66,26 @@ impl<'a> NewPointerEvent<'a> {
         }
     }
 
+    /// Position of the pointer for hit testing, `None` if it is out of
+    /// the window or over an overlay above.
+    pub(crate) fn hit_position(&self) -> Option<Point> {
+        if self.occluded.get() {
+            None
+        } else {
+            self.new_position
+        }
+    }
+
+    /// Hide the pointer from elements receiving this event later, which
+    /// are below the one occluding.
+    pub(crate) fn occlude(&self) {
+        self.occluded.set(true);
+    }
+
+    pub(crate) fn is_occluded(&self) -> bool {
+        self.occluded.get()
+    }
+
     pub(crate) fn focus_on(&self, ed: Option<EventDispatcher>) {
         self.new_focused.set(match ed {
             Some(ed) => NewFocused::ChangeTo(ed),
@@ -98,6 +120,12 @@ impl Drop for NewPointerEvent<'_> {
             NewFocused::Blur => self.global_content.focusing.blur(),
         }
 
+        if let PointerStateChange::Release | PointerStateChange::LeaveViewport =
+            self.pointer_state_change
+        {
+            self.global_content.pointer_capture.release();
+        }
+
         self.gem.pointer_state = self.new_pointer_state;
     }
 }
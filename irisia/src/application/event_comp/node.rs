@@ -24,9 +24,9 @@ enum State {
 }
 
 impl NodeEventMgr {
-    pub fn new() -> Self {
+    pub fn new(ed: EventDispatcher) -> Self {
         Self {
-            ed: EventDispatcher::new(),
+            ed,
             current_state: State::Untracked,
         }
     }
@@ -36,13 +36,14 @@ impl NodeEventMgr {
         update: &NewPointerEvent,
         region: Option<Region>,
         logically_entered: bool,
+        capture_on_press: bool,
     ) -> bool {
         let Some(region) = region
         else {
             return false;
         };
 
-        let position = match update.new_position {
+        let position = match update.hit_position() {
             Some(p) if p.abs_ge(region.0) && p.abs_le(region.1) => {
                 self.update_state(State::PhysicallyEnter);
                 p
@@ -57,12 +58,19 @@ impl NodeEventMgr {
             }
         };
 
-        self.emit_physical_pointer_event(
-            update.pointer_state_change,
-            position,
-            update.cursor_delta,
-            logically_entered,
-        );
+        // the element capturing the pointer received it from the window
+        if !update
+            .global_content
+            .pointer_capture
+            .is_captured_by(&self.ed)
+        {
+            self.emit_physical_pointer_event(
+                update.pointer_state_change,
+                position,
+                update.cursor_delta,
+                logically_entered,
+            );
+        }
 
         self.ed.emit_sys(update.event.clone());
 
@@ -70,6 +78,15 @@ impl NodeEventMgr {
         {
             // TODO: the element may cannot be focused on, set `None` instead.
             update.focus_on(Some(self.ed.clone()));
+
+            // captured before the element handles the press, so a quick
+            // release is not missed
+            if capture_on_press {
+                update
+                    .global_content
+                    .pointer_capture
+                    .capture(self.ed.clone());
+            }
         }
 
         true
@@ -0,0 +1,254 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use irisia_backend::FrameTiming;
+
+use crate as irisia;
+use crate::{event::EventDispatcher, Event};
+
+const HISTORY_CAPACITY: usize = 240;
+
+/// Timings of one presented frame.
+#[derive(Debug, Clone, Default)]
+pub struct FrameRecord {
+    /// Time elapsed since the previous frame.
+    pub delta: Duration,
+
+    /// Time spent on the whole frame, from layout to upload.
+    pub total: Duration,
+
+    /// Time spent on layout since the previous frame.
+    pub layout: Duration,
+
+    /// Time spent on rendering every redrawn layer.
+    pub render: Duration,
+
+    /// Time spent on each layer redrawn in this frame. Independent
+    /// layers nested in a redrawn layer are included in its parent's.
+    pub layers: Vec<LayerTiming>,
+
+    /// Time spent on compositing layers into the window canvas.
+    pub composite: Duration,
+
+    /// Time spent on copying the frame to the window and presenting it.
+    pub upload: Duration,
+
+    /// Number of independent layers in the window, including the root and
+    /// overlay layers.
+    pub layer_count: usize,
+
+    /// Number of frames missed because this frame is over budget, always
+    /// `0` if frames are drawn on demand.
+    pub dropped: u32,
+}
+
+impl FrameRecord {
+    pub(super) fn record_layer(&mut self, element: &'static str, duration: Duration) {
+        self.render += duration;
+        self.layers.push(LayerTiming { element, duration });
+    }
+
+    pub(super) fn record_composite(&mut self, duration: Duration, layer_count: usize) {
+        self.composite = duration;
+        self.layer_count = layer_count;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LayerTiming {
+    /// Type name of the element owning the layer.
+    pub element: &'static str,
+    pub duration: Duration,
+}
+
+/// Summary of recent frames.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrameStatsSummary {
+    /// Frames presented since the window opened.
+    pub frame_count: u64,
+
+    /// Frames dropped since the window opened.
+    pub dropped_frames: u64,
+
+    /// Number of recent frames the percentiles are computed from.
+    pub sample_count: usize,
+
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+
+    /// Average frames per second of recent frames.
+    pub fps: f32,
+}
+
+/// Emitted periodically on the window's event dispatcher,
+/// see [`Window::report_frame_stats`](super::Window::report_frame_stats).
+#[derive(Event, Clone, Copy)]
+pub struct FrameStatsReport(pub FrameStatsSummary);
+
+/// Frame statistics of a window, keeping recent frames only.
+#[derive(Debug, Clone)]
+pub struct FrameStats {
+    history: VecDeque<FrameRecord>,
+    frame_count: u64,
+    dropped_frames: u64,
+}
+
+impl FrameStats {
+    pub(super) fn new() -> Self {
+        FrameStats {
+            history: VecDeque::with_capacity(HISTORY_CAPACITY),
+            frame_count: 0,
+            dropped_frames: 0,
+        }
+    }
+
+    fn push(&mut self, record: FrameRecord) {
+        if self.history.len() == HISTORY_CAPACITY {
+            self.history.pop_front();
+        }
+
+        self.frame_count += 1;
+        self.dropped_frames += record.dropped as u64;
+        self.history.push_back(record);
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped_frames
+    }
+
+    pub fn last(&self) -> Option<&FrameRecord> {
+        self.history.back()
+    }
+
+    /// Recent frames, from the oldest to the latest.
+    pub fn records(&self) -> impl Iterator<Item = &FrameRecord> {
+        self.history.iter()
+    }
+
+    /// Percentile of recent frame times, `p` in `0.0..=1.0`.
+    pub fn percentile(&self, p: f32) -> Option<Duration> {
+        let mut totals: Vec<Duration> = self.history.iter().map(|r| r.total).collect();
+        totals.sort_unstable();
+        percentile_of_sorted(&totals, p)
+    }
+
+    pub fn summary(&self) -> FrameStatsSummary {
+        let mut totals: Vec<Duration> = self.history.iter().map(|r| r.total).collect();
+        totals.sort_unstable();
+
+        let elapsed: Duration = self.history.iter().skip(1).map(|r| r.delta).sum();
+        let fps = if elapsed.is_zero() {
+            0.0
+        } else {
+            (self.history.len() - 1) as f32 / elapsed.as_secs_f32()
+        };
+
+        let pct = |p| percentile_of_sorted(&totals, p).unwrap_or_default();
+
+        FrameStatsSummary {
+            frame_count: self.frame_count,
+            dropped_frames: self.dropped_frames,
+            sample_count: totals.len(),
+            p50: pct(0.5),
+            p90: pct(0.9),
+            p99: pct(0.99),
+            max: totals.last().copied().unwrap_or_default(),
+            fps,
+        }
+    }
+}
+
+fn percentile_of_sorted(sorted: &[Duration], p: f32) -> Option<Duration> {
+    if sorted.is_empty() {
+        return None;
+    }
+
+    let index = ((sorted.len() - 1) as f32 * p.clamp(0.0, 1.0)).round() as usize;
+    Some(sorted[index])
+}
+
+/// Collects timings of the frame in progress, owned by the backend runtime.
+pub(super) struct FrameStatsRecorder {
+    current: FrameRecord,
+
+    // layout since the last redraw, which belongs to the next frame
+    layout: Duration,
+    stats: FrameStats,
+    report_interval: Option<Duration>,
+    last_report: Option<Instant>,
+}
+
+impl FrameStatsRecorder {
+    pub fn new() -> Self {
+        FrameStatsRecorder {
+            current: FrameRecord::default(),
+            layout: Duration::ZERO,
+            stats: FrameStats::new(),
+            report_interval: None,
+            last_report: None,
+        }
+    }
+
+    pub fn stats(&self) -> &FrameStats {
+        &self.stats
+    }
+
+    pub fn set_report_interval(&mut self, interval: Option<Duration>) {
+        self.report_interval = interval;
+        self.last_report = None;
+    }
+
+    pub fn record_layout(&mut self, duration: Duration) {
+        self.layout += duration;
+    }
+
+    /// Start the record of a frame with timings of a redraw, recorded
+    /// without holding the lock of this recorder. Timings of the last
+    /// frame are dropped if it was not presented.
+    pub fn record_redraw(&mut self, redraw: FrameRecord) {
+        self.current = FrameRecord {
+            layout: std::mem::take(&mut self.layout),
+            ..redraw
+        };
+    }
+
+    pub fn frame_presented(&mut self, timing: FrameTiming, ed: &EventDispatcher) {
+        let mut record = std::mem::take(&mut self.current);
+        record.delta = timing.delta;
+        record.upload = timing.upload;
+        record.total = record.layout + timing.draw + timing.upload;
+        record.dropped = dropped_frames(record.total, timing.budget);
+        self.stats.push(record);
+
+        if let Some(interval) = self.report_interval {
+            let now = Instant::now();
+            match self.last_report {
+                Some(last) if now.duration_since(last) < interval => {}
+                _ => {
+                    self.last_report = Some(now);
+                    ed.emit(FrameStatsReport(self.stats.summary()));
+                }
+            }
+        }
+    }
+}
+
+fn dropped_frames(total: Duration, budget: Option<Duration>) -> u32 {
+    let Some(budget) = budget.filter(|budget| !budget.is_zero()) else {
+        return 0;
+    };
+
+    if total <= budget {
+        return 0;
+    }
+
+    (total.as_secs_f64() / budget.as_secs_f64()).ceil() as u32 - 1
+}
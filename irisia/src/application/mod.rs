@@ -1,19 +1,34 @@
-use std::sync::Arc;
+use std::{
+    sync::{Arc, Mutex as StdMutex},
+    time::Duration,
+};
 
-use irisia_backend::{window_handle::WindowBuilder, WinitWindow};
+use irisia_backend::{
+    runtime::{capture_frame, set_window_pacing, FramePacing},
+    window_handle::WindowBuilder,
+    CapturedFrame, WinitWindow,
+};
 
 use crate::{
     element::{Element, UpdateElement},
     event::{standard::window_event::WindowDestroyed, EventDispatcher},
+    primitive::{region_to_irect, Region},
     Result, UpdateWith,
 };
 
 mod backend;
 pub(crate) mod content;
+pub mod error;
 pub(crate) mod event_comp;
+pub mod frame_stats;
 pub(crate) mod redraw_scheduler;
 
 use backend::new_window;
+use error::ErrorPolicy;
+use frame_stats::{FrameStats, FrameStatsRecorder, FrameStatsSummary};
+
+pub use content::GlobalContent;
+pub use redraw_scheduler::LayerId;
 
 pub use irisia_backend::window_handle::CloseHandle;
 
@@ -22,6 +37,8 @@ pub struct Window {
     winit_window: Arc<WinitWindow>,
     close_handle: CloseHandle,
     event_dispatcher: EventDispatcher,
+    frame_stats: Arc<StdMutex<FrameStatsRecorder>>,
+    error_policy: Arc<StdMutex<ErrorPolicy>>,
 }
 
 type EmptyUpdateOptions<'a, El> = UpdateElement<'a, El, (), ()>;
@@ -55,10 +72,55 @@ impl Window {
         self.close_handle.close();
     }
 
+    /// Set frame pacing of this window. `None` to follow the default pacing
+    /// of the app, which can be set by [`crate::set_default_pacing`].
+    pub async fn set_frame_pacing(&self, pacing: Option<FramePacing>) {
+        set_window_pacing(self.winit_window.id(), pacing).await;
+    }
+
     pub fn event_dispatcher(&self) -> &EventDispatcher {
         &self.event_dispatcher
     }
 
+    /// Set how this window recovers from errors. Errors are emitted as
+    /// [`WindowError`](error::WindowError) on the event dispatcher of this window
+    /// whatever the policy is.
+    pub fn set_error_policy(&self, policy: ErrorPolicy) {
+        *self.error_policy.lock().unwrap() = policy;
+    }
+
+    pub fn error_policy(&self) -> ErrorPolicy {
+        *self.error_policy.lock().unwrap()
+    }
+
+    /// Capture the last frame presented by this window.
+    pub async fn capture_frame(&self) -> Result<CapturedFrame> {
+        capture_frame(self.winit_window.id(), None).await
+    }
+
+    /// Capture a region of the last frame presented by this window.
+    pub async fn capture_region(&self, region: Region) -> Result<CapturedFrame> {
+        capture_frame(self.winit_window.id(), Some(region_to_irect(region))).await
+    }
+
+    /// Statistics of recently presented frames.
+    pub fn frame_stats(&self) -> FrameStats {
+        self.frame_stats.lock().unwrap().stats().clone()
+    }
+
+    pub fn frame_stats_summary(&self) -> FrameStatsSummary {
+        self.frame_stats.lock().unwrap().stats().summary()
+    }
+
+    /// Emit [`FrameStatsReport`](frame_stats::FrameStatsReport) on the event
+    /// dispatcher of this window at most once per `interval`. `None` to stop.
+    pub fn report_frame_stats(&self, interval: Option<Duration>) {
+        self.frame_stats
+            .lock()
+            .unwrap()
+            .set_report_interval(interval);
+    }
+
     pub async fn join(&self) {
         self.event_dispatcher.recv_sys::<WindowDestroyed>().await;
     }
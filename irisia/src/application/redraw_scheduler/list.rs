@@ -23,11 +23,16 @@ impl RedrawList {
     }
 
     pub fn request_redraw(&mut self, id: LayerId) {
+        self.request_composite();
+        self.list.insert(id);
+    }
+
+    /// Request a new frame without rebuilding any layer.
+    pub fn request_composite(&mut self) {
         if !self.redraw_req_sent {
             self.redraw_req_sent = true;
             self.window.request_redraw();
         }
-        self.list.insert(id);
     }
 
     pub fn drain(&mut self) -> Drain<LayerId> {
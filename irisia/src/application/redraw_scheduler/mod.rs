@@ -1,33 +1,53 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::anyhow;
-use irisia_backend::{skia_safe::Canvas, WinitWindow};
+use irisia_backend::{
+    skia_safe::{Canvas, Rect},
+    CapturedFrame, WinitWindow,
+};
 
 use crate::{
+    application::event_comp::NewPointerEvent,
     dom::layer::{LayerCompositer, LayerRebuilder},
-    Result,
+    log, Result,
 };
 
+use super::{error::WindowError, frame_stats::FrameRecord};
+
 pub(crate) use self::{list::RedrawList, register::IndepLayerRegister};
 
 mod list;
 mod register;
 pub(crate) const ROOT_LAYER_ID: LayerId = LayerId(0);
 
+/// Layer of portals, composited above the root layer. Ids of other
+/// layers are addresses, which are never `1`.
+pub(crate) const OVERLAY_LAYER_ID: LayerId = LayerId(1);
+const OVERLAY_ELEMENT: &str = "<overlay>";
+
 pub(super) struct RedrawScheduler {
     root_layer_compositer: LayerCompositer,
+    overlay_layer_compositer: LayerCompositer,
     register: IndepLayerRegister,
+    root_element: &'static str,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) struct LayerId(usize);
+/// Identifies an independent layer of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LayerId(usize);
 
 impl RedrawScheduler {
-    pub fn new(window: Arc<WinitWindow>) -> (Self, RedrawList) {
+    pub fn new(window: Arc<WinitWindow>, root_element: &'static str) -> (Self, RedrawList) {
         (
             RedrawScheduler {
                 register: IndepLayerRegister::new(),
                 root_layer_compositer: LayerCompositer::new(),
+                overlay_layer_compositer: LayerCompositer::new(),
+                root_element,
             },
             RedrawList::new(window),
         )
@@ -43,61 +63,149 @@ impl RedrawScheduler {
         ) -> Result<()>,
         interval: Duration,
         list: &mut RedrawList,
-    ) -> Result<()> {
+        stats: &mut FrameRecord,
+    ) -> Vec<WindowError> {
         let mut errors = Vec::new();
+        let bounds = Rect::from_isize(canvas.base_layer_size());
 
         for ptr in list.drain() {
-            let result = if ptr == ROOT_LAYER_ID {
-                root_element_renderer(
-                    &mut self.root_layer_compositer.rebuild(canvas),
-                    &mut self.register,
-                    interval,
+            let _span = tracing::trace_span!(target: log::RENDER, "layer", layer = ?ptr).entered();
+            let start = Instant::now();
+            let (element, result) = if ptr == ROOT_LAYER_ID {
+                let mut lr = self.root_layer_compositer.rebuild(bounds);
+                let result = root_element_renderer(&mut lr, &mut self.register, interval);
+                (self.root_element, result.and(lr.finish(ROOT_LAYER_ID)))
+            } else if ptr == OVERLAY_LAYER_ID {
+                (
+                    OVERLAY_ELEMENT,
+                    self.redraw_overlay(bounds, interval, &mut errors),
                 )
             } else {
                 match self.register.get(ptr) {
-                    Some(ro) => ro.clone().redraw(canvas, &mut self.register, interval),
-                    None => Err(anyhow!("redraw object not registered")),
+                    Some(ro) => {
+                        let ro = ro.clone();
+                        (
+                            ro.element_name(),
+                            ro.redraw(bounds, &mut self.register, interval),
+                        )
+                    }
+                    None => {
+                        errors.push(WindowError::from_redraw(
+                            anyhow!("redraw object not registered"),
+                            ptr,
+                            "<unregistered>",
+                        ));
+                        continue;
+                    }
                 }
             };
+            stats.record_layer(element, start.elapsed());
+
+            if let Err(err) = result {
+                errors.push(WindowError::from_redraw(err, ptr, element));
+            }
+        }
+
+        // portals register when first rendered by their parents, draw
+        // them in the same frame
+        while self.register.take_overlays_changed() {
+            let start = Instant::now();
+            let result = self.redraw_overlay(bounds, interval, &mut errors);
+            stats.record_layer(OVERLAY_ELEMENT, start.elapsed());
 
             if let Err(err) = result {
-                errors.push(err);
+                errors.push(WindowError::from_redraw(
+                    err,
+                    OVERLAY_LAYER_ID,
+                    OVERLAY_ELEMENT,
+                ));
+                break;
             }
         }
 
-        fmt_errors(&errors)
+        errors
     }
 
-    pub fn composite(&self, canvas: &mut Canvas) -> Result<()> {
-        self.root_layer_compositer.composite(canvas)
+    /// Errors of portals are pushed to `errors`, other portals are still
+    /// drawn. Returns the error finishing the layer.
+    fn redraw_overlay(
+        &mut self,
+        bounds: Rect,
+        interval: Duration,
+        errors: &mut Vec<WindowError>,
+    ) -> Result<()> {
+        let overlays = self.register.overlays();
+        let mut lr = self.overlay_layer_compositer.rebuild(bounds);
+        for overlay in overlays {
+            if let Err(err) = overlay.render(&mut lr, &mut self.register, interval) {
+                errors.push(WindowError::from_redraw(
+                    err,
+                    OVERLAY_LAYER_ID,
+                    OVERLAY_ELEMENT,
+                ));
+            }
+        }
+        lr.finish(OVERLAY_LAYER_ID)
+    }
+
+    /// Clear content of the layer, it will be empty until redrawn.
+    pub fn skip_layer(&mut self, id: LayerId) {
+        if id == ROOT_LAYER_ID {
+            self.root_layer_compositer.clear();
+        } else if id == OVERLAY_LAYER_ID {
+            self.overlay_layer_compositer.clear();
+        } else if let Some(ro) = self.register.get(id) {
+            ro.clear();
+        }
     }
-}
 
-fn fmt_errors(errors: &[anyhow::Error]) -> Result<()> {
-    if errors.is_empty() {
-        return Ok(());
+    pub fn composite(&self, canvas: &mut Canvas) -> Result<()> {
+        self.root_layer_compositer.composite(canvas)?;
+        self.overlay_layer_compositer.composite(canvas)
     }
 
-    let mut msg = String::new();
-    for (index, err) in errors.iter().enumerate() {
-        msg += &format!("`{err}`");
-        if index != errors.len() - 1 {
-            msg += ", ";
+    pub fn capture_layer(&self, id: LayerId) -> Result<CapturedFrame> {
+        match self.register.get(id) {
+            Some(ro) => ro.capture(),
+            None => Err(anyhow!("redraw object not registered")),
         }
     }
 
-    Err(anyhow!(
-        "{} error(s) occurred on redraw: {}",
-        errors.len(),
-        msg
-    ))
+    /// Number of independent layers, including the root layer and the
+    /// overlay layer.
+    pub fn layer_count(&self) -> usize {
+        self.register.len() + 2
+    }
+
+    /// Portals in the overlay layer, from the bottom to the top.
+    pub fn overlays(&mut self) -> Vec<Rc<dyn OverlayObject>> {
+        self.register.overlays()
+    }
 }
 
 pub(crate) trait RedrawObject {
-    fn redraw(
+    fn redraw(&self, bounds: Rect, reg: &mut IndepLayerRegister, interval: Duration) -> Result<()>;
+
+    /// Type name of the element owning this layer.
+    fn element_name(&self) -> &'static str;
+
+    /// Drop recorded content of this layer.
+    fn clear(&self);
+
+    /// Composite this layer alone onto a transparent frame of its drawing region.
+    fn capture(&self) -> Result<CapturedFrame>;
+}
+
+/// Content of a portal, drawn in the overlay layer.
+pub(crate) trait OverlayObject {
+    fn render(
         &self,
-        canvas: &mut Canvas,
+        lr: &mut LayerRebuilder,
         reg: &mut IndepLayerRegister,
         interval: Duration,
     ) -> Result<()>;
+
+    /// Returns whether the pointer is over this overlay, elements below
+    /// it will not receive the pointer then.
+    fn emit_event(&self, npe: &NewPointerEvent) -> bool;
 }
@@ -1,30 +1,62 @@
-use std::{collections::HashMap, rc::Rc};
+use std::{
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
 
 use crate::application::redraw_scheduler::LayerId;
 
-use super::RedrawObject;
+use super::{OverlayObject, RedrawObject};
 
-pub struct IndepLayerRegister(HashMap<usize, Rc<dyn RedrawObject>>);
+pub struct IndepLayerRegister {
+    layers: HashMap<usize, Rc<dyn RedrawObject>>,
+
+    /// Portals from the bottom to the top, removed when dropped.
+    overlays: Vec<Weak<dyn OverlayObject>>,
+    overlays_changed: bool,
+}
 
 impl IndepLayerRegister {
     pub(super) fn new() -> Self {
-        Self(HashMap::new())
+        Self {
+            layers: HashMap::new(),
+            overlays: Vec::new(),
+            overlays_changed: false,
+        }
     }
 
     pub(crate) fn reg(&mut self, value: Rc<dyn RedrawObject>) -> LayerId {
         let key = Rc::as_ptr(&value).cast::<()>() as usize;
         assert_ne!(key, 0);
-        let not_exists = self.0.insert(key, value).is_none();
+        let not_exists = self.layers.insert(key, value).is_none();
         debug_assert!(not_exists);
         LayerId(key)
     }
 
     pub(crate) fn del(&mut self, key: LayerId) {
-        let already_exists = self.0.remove(&key.0).is_some();
+        let already_exists = self.layers.remove(&key.0).is_some();
         debug_assert!(already_exists);
     }
 
+    /// Put a portal above all others in the overlay layer.
+    pub(crate) fn reg_overlay(&mut self, value: Weak<dyn OverlayObject>) {
+        self.overlays.push(value);
+        self.overlays_changed = true;
+    }
+
+    pub(super) fn overlays(&mut self) -> Vec<Rc<dyn OverlayObject>> {
+        self.overlays.retain(|overlay| overlay.strong_count() > 0);
+        self.overlays.iter().filter_map(Weak::upgrade).collect()
+    }
+
+    pub(super) fn take_overlays_changed(&mut self) -> bool {
+        std::mem::take(&mut self.overlays_changed)
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.layers.len()
+    }
+
     pub(super) fn get(&self, key: LayerId) -> Option<&Rc<dyn RedrawObject>> {
-        self.0.get(&key.0)
+        self.layers.get(&key.0)
     }
 }
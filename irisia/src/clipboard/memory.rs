@@ -0,0 +1,71 @@
+use crate::Result;
+
+use super::{ClipboardBackend, ClipboardImage};
+
+/// Clipboard keeping data in memory, invisible to other apps.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryClipboard {
+    content: Content,
+}
+
+#[derive(Debug, Default, Clone)]
+enum Content {
+    #[default]
+    Empty,
+    Text(String),
+    Image(ClipboardImage),
+    Data {
+        mime: String,
+        data: Vec<u8>,
+    },
+}
+
+impl MemoryClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ClipboardBackend for MemoryClipboard {
+    fn read_text(&mut self) -> Result<Option<String>> {
+        Ok(match &self.content {
+            Content::Text(text) => Some(text.clone()),
+            _ => None,
+        })
+    }
+
+    fn write_text(&mut self, text: &str) -> Result<()> {
+        self.content = Content::Text(text.to_owned());
+        Ok(())
+    }
+
+    fn read_image(&mut self) -> Result<Option<ClipboardImage>> {
+        Ok(match &self.content {
+            Content::Image(image) => Some(image.clone()),
+            _ => None,
+        })
+    }
+
+    fn write_image(&mut self, image: &ClipboardImage) -> Result<()> {
+        self.content = Content::Image(image.clone());
+        Ok(())
+    }
+
+    fn read_data(&mut self, mime: &str) -> Result<Option<Vec<u8>>> {
+        Ok(match &self.content {
+            Content::Data { mime: m, data } if m == mime => Some(data.clone()),
+            Content::Text(text) if mime.starts_with("text/plain") => {
+                Some(text.clone().into_bytes())
+            }
+            _ => None,
+        })
+    }
+
+    fn write_data(&mut self, mime: &str, data: &[u8]) -> Result<()> {
+        self.content = Content::Data {
+            mime: mime.to_owned(),
+            data: data.to_vec(),
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+//! Clipboard of the system, or of the app only.
+//!
+//! Text elements read and write the clipboard returned by
+//! [`Clipboard::global`], which uses the platform clipboard by default.
+//! Replace it with [`Clipboard::set_global`], e.g. with a
+//! [`MemoryClipboard`] in tests.
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::anyhow;
+use lazy_static::lazy_static;
+
+use crate::{log, Result};
+
+pub use self::memory::MemoryClipboard;
+
+#[cfg(all(feature = "platform-clipboard", not(target_os = "android")))]
+pub use self::platform::PlatformClipboard;
+
+mod memory;
+#[cfg(all(feature = "platform-clipboard", not(target_os = "android")))]
+mod platform;
+
+lazy_static! {
+    static ref GLOBAL: Mutex<Option<Clipboard>> = Mutex::new(None);
+}
+
+/// Storage of a clipboard. Writing any kind of data replaces all the
+/// data in the clipboard. Backends support text at least, other kinds
+/// are unsupported by default.
+pub trait ClipboardBackend: Send + 'static {
+    /// Text in the clipboard, `None` if there is no text.
+    fn read_text(&mut self) -> Result<Option<String>>;
+
+    fn write_text(&mut self, text: &str) -> Result<()>;
+
+    /// Image in the clipboard, `None` if there is no image.
+    fn read_image(&mut self) -> Result<Option<ClipboardImage>> {
+        Err(anyhow!("images are unsupported by this clipboard"))
+    }
+
+    fn write_image(&mut self, _image: &ClipboardImage) -> Result<()> {
+        Err(anyhow!("images are unsupported by this clipboard"))
+    }
+
+    /// Data of the MIME type in the clipboard, `None` if there is no
+    /// data of the type.
+    fn read_data(&mut self, _mime: &str) -> Result<Option<Vec<u8>>> {
+        Err(anyhow!("custom data is unsupported by this clipboard"))
+    }
+
+    fn write_data(&mut self, _mime: &str, _data: &[u8]) -> Result<()> {
+        Err(anyhow!("custom data is unsupported by this clipboard"))
+    }
+}
+
+/// Image in RGBA8, without premultiplied alpha.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// Shared handle of a clipboard backend.
+#[derive(Clone)]
+pub struct Clipboard(Arc<Mutex<dyn ClipboardBackend>>);
+
+impl Clipboard {
+    pub fn new(backend: impl ClipboardBackend) -> Self {
+        Clipboard(Arc::new(Mutex::new(backend)))
+    }
+
+    /// Clipboard used by elements. If not set, it is the platform
+    /// clipboard, or a memory clipboard if the platform clipboard
+    /// is unavailable.
+    pub fn global() -> Clipboard {
+        GLOBAL
+            .lock()
+            .unwrap()
+            .get_or_insert_with(Self::default_backend)
+            .clone()
+    }
+
+    pub fn set_global(clipboard: Clipboard) {
+        *GLOBAL.lock().unwrap() = Some(clipboard);
+    }
+
+    pub fn read_text(&self) -> Result<Option<String>> {
+        self.0.lock().unwrap().read_text()
+    }
+
+    pub fn write_text(&self, text: &str) -> Result<()> {
+        self.0.lock().unwrap().write_text(text)
+    }
+
+    pub fn read_image(&self) -> Result<Option<ClipboardImage>> {
+        self.0.lock().unwrap().read_image()
+    }
+
+    pub fn write_image(&self, image: &ClipboardImage) -> Result<()> {
+        self.0.lock().unwrap().write_image(image)
+    }
+
+    pub fn read_data(&self, mime: &str) -> Result<Option<Vec<u8>>> {
+        self.0.lock().unwrap().read_data(mime)
+    }
+
+    pub fn write_data(&self, mime: &str, data: &[u8]) -> Result<()> {
+        self.0.lock().unwrap().write_data(mime, data)
+    }
+
+    #[cfg(all(feature = "platform-clipboard", not(target_os = "android")))]
+    fn default_backend() -> Clipboard {
+        match PlatformClipboard::new() {
+            Ok(platform) => Clipboard::new(platform),
+            Err(err) => {
+                tracing::warn!(
+                    target: log::CLIPBOARD,
+                    "platform clipboard unavailable, fallback to memory clipboard: {err:#}"
+                );
+                Clipboard::new(MemoryClipboard::new())
+            }
+        }
+    }
+
+    #[cfg(not(all(feature = "platform-clipboard", not(target_os = "android"))))]
+    fn default_backend() -> Clipboard {
+        tracing::debug!(target: log::CLIPBOARD, "no platform clipboard, use memory clipboard");
+        Clipboard::new(MemoryClipboard::new())
+    }
+}
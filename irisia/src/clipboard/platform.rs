@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+
+use anyhow::Context;
+use arboard::{Error, ImageData};
+
+use crate::Result;
+
+use super::{ClipboardBackend, ClipboardImage};
+
+/// Clipboard of the system. Custom MIME data is unsupported.
+pub struct PlatformClipboard(arboard::Clipboard);
+
+impl PlatformClipboard {
+    pub fn new() -> Result<Self> {
+        arboard::Clipboard::new()
+            .map(PlatformClipboard)
+            .context("cannot open the platform clipboard")
+    }
+}
+
+impl ClipboardBackend for PlatformClipboard {
+    fn read_text(&mut self) -> Result<Option<String>> {
+        match self.0.get_text() {
+            Ok(text) => Ok(Some(text)),
+            Err(Error::ContentNotAvailable) => Ok(None),
+            Err(err) => Err(err).context("cannot read text from the clipboard"),
+        }
+    }
+
+    fn write_text(&mut self, text: &str) -> Result<()> {
+        self.0
+            .set_text(text)
+            .context("cannot write text to the clipboard")
+    }
+
+    fn read_image(&mut self) -> Result<Option<ClipboardImage>> {
+        match self.0.get_image() {
+            Ok(image) => Ok(Some(ClipboardImage {
+                width: image.width as _,
+                height: image.height as _,
+                rgba: image.bytes.into_owned(),
+            })),
+            Err(Error::ContentNotAvailable) => Ok(None),
+            Err(err) => Err(err).context("cannot read image from the clipboard"),
+        }
+    }
+
+    fn write_image(&mut self, image: &ClipboardImage) -> Result<()> {
+        self.0
+            .set_image(ImageData {
+                width: image.width as _,
+                height: image.height as _,
+                bytes: Cow::Borrowed(&image.rgba),
+            })
+            .context("cannot write image to the clipboard")
+    }
+}
@@ -1,4 +1,5 @@
-pub(crate) use self::{children_node::ChildrenNodes, render_multiple::RenderMultiple};
+pub use self::children_node::ChildrenNodes;
+pub(crate) use self::render_multiple::RenderMultiple;
 
 mod children_node;
 mod render_multiple;
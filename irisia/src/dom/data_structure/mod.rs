@@ -1,17 +1,21 @@
-use std::sync::{Arc, RwLock as StdRwLock};
+use std::sync::{atomic::AtomicBool, Arc, RwLock as StdRwLock};
 
+use irisia_backend::skia_safe::{BlendMode, M44};
 use tokio::sync::RwLock;
 
 use crate::{
     application::{content::GlobalContent, event_comp::NodeEventMgr, redraw_scheduler::LayerId},
     event::EventDispatcher,
-    primitive::Region,
+    primitive::{Direction, Pixel, Region},
     structure::slot::Slot,
 };
 
 use self::maybe_shared::MaybeShared;
 
-use super::{children::ChildrenBox, layer::LayerCompositer};
+use super::{
+    children::ChildrenBox,
+    layer::{LayerAttrs, LayerCompositer},
+};
 
 pub(super) mod maybe_shared;
 
@@ -30,6 +34,7 @@ pub(super) struct LayerSharedPart<El> {
     pub(super) expanded_children: Option<ChildrenBox>,
     pub(super) draw_region: Region,
     pub(super) interact_region: Option<Region>,
+    pub(super) panicked: bool,
 }
 
 pub struct ElementHandle<El> {
@@ -37,15 +42,44 @@ pub struct ElementHandle<El> {
     pub(super) ed: EventDispatcher,
     pub(super) global_content: Arc<GlobalContent>,
     pub(super) layer_info: StdRwLock<LayerInfo>,
+    pub(super) direction: StdRwLock<Direction>,
+    pub(super) preferred_height: StdRwLock<Option<Pixel>>,
+    pub(super) capture_pointer_on_press: AtomicBool,
 }
 
 pub(super) struct LayerInfo {
     pub acquire_independent_layer: bool,
     pub parent_layer_id: LayerId,
     pub indep_layer_id: Option<LayerId>,
+
+    /// Attributes read from styles, default ones for missing styles.
+    pub styled_attrs: LayerAttrs,
+
+    /// Attributes set through the handle, which take precedence over styles.
+    pub handle_attrs: LayerAttrsOverride,
+}
+
+#[derive(Clone, Default, PartialEq)]
+pub(super) struct LayerAttrsOverride {
+    pub opacity: Option<f32>,
+    pub transform: Option<M44>,
+    pub blend_mode: Option<BlendMode>,
 }
 
 impl LayerInfo {
+    pub fn attrs(&self) -> LayerAttrs {
+        let styled = &self.styled_attrs;
+        let handle = &self.handle_attrs;
+        LayerAttrs {
+            opacity: handle.opacity.unwrap_or(styled.opacity),
+            transform: handle
+                .transform
+                .clone()
+                .unwrap_or_else(|| styled.transform.clone()),
+            blend_mode: handle.blend_mode.unwrap_or(styled.blend_mode),
+        }
+    }
+
     pub fn render_layer_id(&self) -> LayerId {
         self.indep_layer_id.unwrap_or(self.parent_layer_id)
     }
@@ -1,8 +1,4 @@
-use anyhow::anyhow;
-use irisia_backend::skia_safe::{
-    canvas::{SaveLayerFlags, SaveLayerRec},
-    BlendMode, Canvas, Paint,
-};
+use irisia_backend::skia_safe::{canvas::SaveLayerRec, BlendMode, Canvas, Paint, Rect, M44};
 
 use self::queue::{Layer, Queue};
 use crate::Result;
@@ -11,6 +7,9 @@ pub(crate) use rebuild::LayerRebuilder;
 mod queue;
 pub(crate) mod rebuild;
 
+/// Layers are recorded as skia `Picture`s rather than rasterized, so
+/// rebuilding a layer costs only the recorded commands and compositing
+/// replays them onto the window surface.
 pub(crate) struct LayerCompositer {
     layers: Queue,
 }
@@ -22,41 +21,81 @@ impl LayerCompositer {
         }
     }
 
-    pub fn rebuild<'a>(&'a mut self, canvas: &'a mut Canvas) -> LayerRebuilder<'a> {
+    pub fn clear(&mut self) {
         self.layers.clear();
-        LayerRebuilder::new(self, canvas)
     }
 
-    pub fn composite(&self, canvas: &mut Canvas) -> Result<()> {
-        let mut paint = Paint::default();
-        paint.set_blend_mode(BlendMode::DstOver);
-        let rec = SaveLayerRec::default()
-            .flags(SaveLayerFlags::INIT_WITH_PREVIOUS)
-            .paint(&paint);
-        canvas.save_layer(&rec);
+    pub fn rebuild(&mut self, bounds: Rect) -> LayerRebuilder {
+        self.layers.clear();
+        LayerRebuilder::new(self, bounds)
+    }
 
+    pub fn composite(&self, canvas: &mut Canvas) -> Result<()> {
         for layer in self.layers.iter() {
             match layer {
-                Layer::Normal(bitmap) => {
-                    if !canvas.write_pixels_from_bitmap(bitmap, (0, 0)) {
-                        return Err(anyhow!("cannot write bitmap to canvas"));
-                    }
-                }
-                Layer::Extern { layer, matrix } => {
-                    canvas.save();
-                    canvas.set_matrix(matrix);
-                    let result = layer.composite(canvas)?;
-                    canvas.restore();
-                    result
+                Layer::Normal(picture) => {
+                    canvas.draw_picture(picture, None, None);
                 }
+                Layer::Extern { layer, matrix } => composite_extern(&**layer, matrix, canvas)?,
             }
         }
 
-        canvas.restore();
         Ok(())
     }
 }
 
+fn composite_extern(layer: &dyn CustomLayer, matrix: &M44, canvas: &mut Canvas) -> Result<()> {
+    let attrs = layer.attrs();
+    if attrs.opacity <= 0.0 {
+        return Ok(());
+    }
+
+    let count = canvas.save();
+    canvas.concat_44(matrix);
+    canvas.concat_44(&attrs.transform);
+
+    if attrs.needs_save_layer() {
+        let mut paint = Paint::default();
+        paint
+            .set_alpha_f(attrs.opacity)
+            .set_blend_mode(attrs.blend_mode);
+        canvas.save_layer(&SaveLayerRec::default().paint(&paint));
+    }
+
+    let result = layer.composite(canvas);
+    canvas.restore_to_count(count);
+    result
+}
+
+/// Attributes applied when an independent layer is composited onto
+/// its parent, changing them never re-renders the subtree.
+#[derive(Clone, PartialEq)]
+pub(crate) struct LayerAttrs {
+    pub opacity: f32,
+    pub transform: M44,
+    pub blend_mode: BlendMode,
+}
+
+impl LayerAttrs {
+    fn needs_save_layer(&self) -> bool {
+        self.opacity < 1.0 || self.blend_mode != BlendMode::SrcOver
+    }
+}
+
+impl Default for LayerAttrs {
+    fn default() -> Self {
+        Self {
+            opacity: 1.0,
+            transform: M44::new_identity(),
+            blend_mode: BlendMode::SrcOver,
+        }
+    }
+}
+
 pub(crate) trait CustomLayer {
     fn composite(&self, canvas: &mut Canvas) -> Result<()>;
+
+    /// Attributes to apply while compositing, transform is already
+    /// relative to the window.
+    fn attrs(&self) -> LayerAttrs;
 }
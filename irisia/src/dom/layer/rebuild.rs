@@ -1,46 +1,71 @@
 use std::rc::Rc;
 
 use anyhow::anyhow;
-use irisia_backend::skia_safe::{colors::TRANSPARENT, Canvas};
+use irisia_backend::skia_safe::{Canvas, PictureRecorder, Rect, M44};
 
 use super::{CustomLayer, LayerCompositer};
-use crate::Result;
+use crate::{
+    application::{error::LayerFlushError, redraw_scheduler::LayerId},
+    log, Result,
+};
 
 pub struct LayerRebuilder<'a> {
     pub(super) lc: &'a mut LayerCompositer,
-    pub(super) canvas: &'a mut Canvas,
+    recorder: PictureRecorder,
+    bounds: Rect,
     dirty: bool,
 }
 
 impl<'a> LayerRebuilder<'a> {
-    pub(super) fn new(lc: &'a mut LayerCompositer, canvas: &'a mut Canvas) -> Self {
-        canvas.save();
-        canvas.reset_matrix();
+    pub(super) fn new(lc: &'a mut LayerCompositer, bounds: Rect) -> Self {
         Self {
             lc,
-            canvas,
+            recorder: PictureRecorder::new(),
+            bounds,
             dirty: false,
         }
     }
 
     pub(crate) fn draw_in_place(&mut self) -> &mut Canvas {
-        if self.dirty {
-            self.canvas.restore();
-        }
-        self.dirty = true;
-        self.canvas.save();
-        self.canvas
+        let canvas = if self.dirty {
+            let canvas = self
+                .recorder
+                .recording_canvas()
+                .unwrap_or_else(|| unreachable!("recording canvas expected to be exists"));
+            canvas.restore();
+            canvas
+        } else {
+            self.dirty = true;
+            self.recorder.begin_recording(self.bounds, None)
+        };
+
+        canvas.save();
+        canvas
     }
 
-    pub(crate) fn new_layer<T>(&mut self, custom_layer: Rc<T>) -> Result<&mut Canvas>
+    /// Finish the picture currently recording and push `custom_layer` after it,
+    /// composited with the matrix the parent had when it started. Returns the
+    /// bounds of this layer, the new layer bounds itself to its region in it.
+    pub(crate) fn new_layer<T>(&mut self, custom_layer: Rc<T>) -> Result<Rect>
     where
         T: CustomLayer + 'static,
     {
+        let matrix = match self.recorder.recording_canvas() {
+            Some(canvas) if self.dirty => canvas.local_to_device(),
+            _ => M44::new_identity(),
+        };
+
         self.flush()?;
-        let matrix = self.canvas.local_to_device();
         self.lc.layers.add_layer(custom_layer, matrix);
-        self.canvas.clear(TRANSPARENT);
-        Ok(self.canvas)
+        Ok(self.bounds)
+    }
+
+    /// Finish the picture currently recording, errors are tagged with
+    /// `layer`. Must be called after rendering, a failure when dropped is
+    /// only logged.
+    pub(crate) fn finish(&mut self, layer: LayerId) -> Result<()> {
+        self.flush()
+            .map_err(|source| LayerFlushError { layer, source }.into())
     }
 
     fn flush(&mut self) -> Result<()> {
@@ -48,13 +73,12 @@ impl<'a> LayerRebuilder<'a> {
             return Ok(());
         }
 
-        let bitmap = self.lc.layers.add_bitmap(&self.canvas.image_info());
-        if !self.canvas.read_pixels_to_bitmap(bitmap, (0, 0)) {
-            self.lc.layers.pop();
-            return Err(anyhow!("cannot flush canvas content"));
-        }
-        self.canvas.restore();
         self.dirty = false;
+        let picture = self
+            .recorder
+            .finish_recording_as_picture(None)
+            .ok_or_else(|| anyhow!("cannot flush recorded layer content"))?;
+        self.lc.layers.add_picture(picture);
 
         Ok(())
     }
@@ -62,8 +86,8 @@ impl<'a> LayerRebuilder<'a> {
 
 impl Drop for LayerRebuilder<'_> {
     fn drop(&mut self) {
-        self.flush().expect("flush at drop time failed");
-        self.canvas.clear(TRANSPARENT);
-        self.canvas.restore();
+        if let Err(err) = self.flush() {
+            tracing::error!(target: log::RENDER, "cannot flush layer when dropped: {err}");
+        }
     }
 }
@@ -1,11 +1,17 @@
-use std::time::Duration;
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{atomic::Ordering, Arc},
+    time::Duration,
+};
 
 use crate::{
     application::{
+        error::LayerFlushError,
         event_comp::NewPointerEvent,
         redraw_scheduler::{IndepLayerRegister, RedrawObject},
     },
     element::{ChildrenSetter, Element},
+    log,
     primitive::Region,
     Result,
 };
@@ -41,8 +47,13 @@ impl<El, Sty, Sc> ElementModel<El, Sty, Sc> {
         match &mut self.shared {
             MaybeShared::Unique(unique) => unique.redraw(lr, reg, interval),
             MaybeShared::Shared(shared) => {
-                let canvas = lr.new_layer(shared.clone())?;
-                shared.redraw(canvas, reg, interval)
+                let bounds = lr
+                    .new_layer(shared.clone())
+                    .map_err(|source| LayerFlushError {
+                        layer: self.pub_shared.layer_info.read().unwrap().parent_layer_id,
+                        source,
+                    })?;
+                shared.redraw(bounds, reg, interval)
             }
         }
     }
@@ -52,18 +63,39 @@ impl<El, Sty, Sc> ElementModel<El, Sty, Sc> {
         El: Element,
         Sc: RenderMultiple + 'static,
     {
+        let _span = tracing::trace_span!(
+            target: log::LAYOUT,
+            "layout",
+            element = std::any::type_name::<El>()
+        )
+        .entered();
+
         let mut shared = self.shared.borrow_mut();
         shared.draw_region = draw_region;
 
-        self.pub_shared.el_write_clean().layout(
-            draw_region,
-            &self.slot_cache,
-            ChildrenSetter::new(
-                &mut shared.expanded_children,
-                &self.pub_shared.global(),
-                self.pub_shared.layer_info.read().unwrap().render_layer_id(),
-            ),
-        )
+        if shared.panicked {
+            return;
+        }
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            self.pub_shared.el_write_clean().layout(
+                draw_region,
+                &self.slot_cache,
+                ChildrenSetter::new(
+                    &mut shared.expanded_children,
+                    &self.pub_shared.global(),
+                    self.pub_shared.layer_info.read().unwrap().render_layer_id(),
+                    self.pub_shared.direction(),
+                ),
+            )
+        }));
+
+        if let Err(payload) = result {
+            shared.on_panic(payload);
+
+            let layer_id = self.pub_shared.layer_info.read().unwrap().render_layer_id();
+            self.pub_shared.global().request_redraw(layer_id);
+        }
     }
 
     /// returns whether this element is logically entered
@@ -76,14 +108,24 @@ impl<El, Sty, Sc> ElementModel<El, Sty, Sc> {
         };
 
         let children_logically_entered = children_box.as_render_multiple().emit_event(npe);
-        self.event_mgr
-            .update_and_emit(npe, shared.interact_region, children_logically_entered)
+        self.event_mgr.update_and_emit(
+            npe,
+            shared.interact_region,
+            children_logically_entered,
+            self.pub_shared
+                .capture_pointer_on_press
+                .load(Ordering::Relaxed),
+        )
     }
 
     pub fn styles(&self) -> &Sty {
         &self.styles
     }
 
+    pub fn handle(&self) -> &Arc<ElementHandle<El>> {
+        &self.pub_shared
+    }
+
     fn update_independent_layer(&mut self, reg: &mut IndepLayerRegister)
     where
         El: Element,
@@ -1,15 +1,28 @@
-use irisia_backend::WinitWindow;
+use anyhow::anyhow;
+use irisia_backend::{
+    skia_safe::{BlendMode, M44},
+    CapturedFrame, WinitWindow,
+};
 use std::{
     ops::{Deref, DerefMut},
-    sync::Arc,
+    sync::{atomic::Ordering, Arc},
 };
 use tokio::sync::{RwLockMappedWriteGuard, RwLockReadGuard, RwLockWriteGuard};
 
-use crate::{application::content::GlobalContent, event::EventDispatcher};
+use crate::{
+    application::content::GlobalContent,
+    event::EventDispatcher,
+    primitive::{Direction, Pixel, Region},
+    style::{StyleBlendMode, StyleContainer, StyleOpacity, StyleTransform},
+    Result,
+};
 
 use self::listen::Listen;
 
-use super::data_structure::ElementHandle;
+use super::{
+    data_structure::{ElementHandle, LayerInfo},
+    layer::LayerAttrs,
+};
 
 pub mod listen;
 
@@ -48,6 +61,94 @@ impl<El> ElementHandle<El> {
         self.global_content.focusing().blur_checked(&self.ed);
     }
 
+    /// Capture the pointer, this element receives [`PointerMove`] and
+    /// [`PointerUp`] even when the pointer is outside of it, usually
+    /// called when pressed to keep tracking a drag. The capture ends
+    /// when the pointer is released or leaves the window, and a
+    /// [`PointerUp`] is emitted for both.
+    ///
+    /// [`PointerMove`]: crate::event::standard::PointerMove
+    /// [`PointerUp`]: crate::event::standard::PointerUp
+    pub fn capture_pointer(&self) {
+        self.global_content
+            .pointer_capture()
+            .capture(self.ed.clone());
+    }
+
+    /// Capture the pointer as soon as this element is pressed, before
+    /// [`PointerDown`] is handled, see [`Self::capture_pointer`].
+    ///
+    /// [`PointerDown`]: crate::event::standard::PointerDown
+    pub fn set_capture_pointer_on_press(&self, capture: bool) {
+        self.capture_pointer_on_press
+            .store(capture, Ordering::Relaxed);
+    }
+
+    /// Whether this element is capturing the pointer, `false` once the
+    /// [`PointerUp`] ending the capture is emitted. Register a receiver
+    /// of [`PointerUp`] before checking it to never miss the release.
+    ///
+    /// [`PointerUp`]: crate::event::standard::PointerUp
+    pub fn is_capturing_pointer(&self) -> bool {
+        self.global_content.pointer_capture().is_held_by(&self.ed)
+    }
+
+    /// Release the pointer, does nothing if this element is not
+    /// capturing it.
+    pub fn release_pointer(&self) {
+        self.global_content
+            .pointer_capture()
+            .release_checked(&self.ed);
+    }
+
+    /// Declare this element accepts text from the input method, with
+    /// the caret at `caret`, or no longer accepts it with `None`.
+    ///
+    /// Only takes effect while this element is focused, and is reset
+    /// when it is blured. IME is enabled while the focused element
+    /// accepts it, which receives [`ImePreedit`] and [`ImeCommit`].
+    ///
+    /// [`ImePreedit`]: crate::event::standard::ImePreedit
+    /// [`ImeCommit`]: crate::event::standard::ImeCommit
+    pub fn set_ime_caret(&self, caret: Option<Region>) {
+        self.global_content
+            .focusing()
+            .set_ime_caret(&self.ed, caret);
+    }
+
+    /// Direction of this element, decided by [`StyleDirection`] of itself
+    /// and its ancestors.
+    ///
+    /// [`StyleDirection`]: crate::style::StyleDirection
+    pub fn direction(&self) -> Direction {
+        *self.direction.read().unwrap()
+    }
+
+    /// Height this element prefers at the width it was laid out with,
+    /// `None` if it has no preference.
+    pub fn preferred_height(&self) -> Option<Pixel> {
+        *self.preferred_height.read().unwrap()
+    }
+
+    /// Report the height this element prefers at the width it was laid
+    /// out with, usually after measuring its content in layout.
+    /// Containers sizing children by content read it in layout, so the
+    /// window is laid out again, before drawing, if the height changed.
+    pub fn set_preferred_height(&self, height: Option<Pixel>) {
+        let mut write = self.preferred_height.write().unwrap();
+        if *write != height {
+            *write = height;
+            self.global_content.request_layout();
+        }
+    }
+
+    /// Lay out the window again before the next redraw, for elements
+    /// whose children's regions changed, and set dirty flag to `true`.
+    pub fn request_layout(&self) {
+        self.global_content.request_layout();
+        self.set_dirty();
+    }
+
     /// Get global content of the window.
     pub fn global(&self) -> &Arc<GlobalContent> {
         &self.global_content
@@ -95,6 +196,89 @@ impl<El> ElementHandle<El> {
     pub fn independent_layer_acquired(&self) -> bool {
         self.layer_info.read().unwrap().acquire_independent_layer
     }
+
+    /// Set opacity of the independent layer, clamped into `0.0..=1.0`.
+    ///
+    /// Like other layer attributes, it is applied when compositing, so
+    /// changing it will not re-render this element and its children.
+    /// It only takes effect while an independent layer exists, see
+    /// [`Self::acquire_independent_layer`]. Attributes set through the
+    /// handle take precedence over layer styles until
+    /// [`Self::reset_layer_attrs`] is called.
+    pub fn set_layer_opacity(&self, opacity: f32) {
+        self.update_layer_info(|info| info.handle_attrs.opacity = Some(opacity.clamp(0.0, 1.0)));
+    }
+
+    /// Set transform of the independent layer. The transform is
+    /// applied around the center of the drawing region.
+    pub fn set_layer_transform(&self, transform: M44) {
+        self.update_layer_info(|info| info.handle_attrs.transform = Some(transform));
+    }
+
+    /// Set the blend mode used to composite the independent layer
+    /// onto its parent's.
+    pub fn set_layer_blend_mode(&self, blend_mode: BlendMode) {
+        self.update_layer_info(|info| info.handle_attrs.blend_mode = Some(blend_mode));
+    }
+
+    /// Drop layer attributes set through the handle, so that layer
+    /// styles apply again.
+    pub fn reset_layer_attrs(&self) {
+        self.update_layer_info(|info| info.handle_attrs = Default::default());
+    }
+
+    pub fn layer_opacity(&self) -> f32 {
+        self.layer_info.read().unwrap().attrs().opacity
+    }
+
+    pub fn layer_transform(&self) -> M44 {
+        self.layer_info.read().unwrap().attrs().transform
+    }
+
+    pub fn layer_blend_mode(&self) -> BlendMode {
+        self.layer_info.read().unwrap().attrs().blend_mode
+    }
+
+    /// Capture the independent layer of this element alone, without layer
+    /// attributes applied. The frame covers the drawing region of this element.
+    pub async fn capture_layer(&self) -> Result<CapturedFrame> {
+        let id = self.layer_info.read().unwrap().indep_layer_id;
+        match id {
+            Some(id) => self.global_content.capture_layer(id).await,
+            None => Err(anyhow!("element has no independent layer")),
+        }
+    }
+
+    pub(crate) fn apply_layer_styles(&self, styles: &impl StyleContainer) {
+        crate::read_style!(styles => {
+            opacity: Option<StyleOpacity>,
+            transform: Option<StyleTransform>,
+            blend_mode: Option<StyleBlendMode>,
+        });
+
+        // removed styles fall back to the defaults
+        let default = LayerAttrs::default();
+        let styled = LayerAttrs {
+            opacity: opacity.map_or(default.opacity, |StyleOpacity(o)| o.clamp(0.0, 1.0)),
+            transform: transform.map_or(default.transform, |StyleTransform(t)| t),
+            blend_mode: blend_mode.map_or(default.blend_mode, |StyleBlendMode(b)| b),
+        };
+
+        self.update_layer_info(|info| info.styled_attrs = styled);
+    }
+
+    fn update_layer_info<F>(&self, f: F)
+    where
+        F: FnOnce(&mut LayerInfo),
+    {
+        let mut write = self.layer_info.write().unwrap();
+        let old = write.attrs();
+        f(&mut write);
+
+        if write.attrs() != old && write.indep_layer_id.is_some() {
+            self.global_content.request_composite();
+        }
+    }
 }
 
 pub struct ElWriteGuard<'a, T> {
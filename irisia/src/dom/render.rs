@@ -1,17 +1,29 @@
-use std::{cell::RefCell, time::Duration};
+use std::{
+    cell::RefCell,
+    panic::{catch_unwind, AssertUnwindSafe},
+    time::Duration,
+};
 
-use irisia_backend::skia_safe::Canvas;
+use anyhow::anyhow;
+use irisia_backend::{
+    skia_safe::{Canvas, Color, IRect, Rect, Surface, M44},
+    CapturedFrame,
+};
 
 use crate::{
-    application::redraw_scheduler::{IndepLayerRegister, RedrawObject},
+    application::{
+        error::{ElementRenderError, LayerFlushError},
+        redraw_scheduler::{IndepLayerRegister, RedrawObject},
+    },
     element::RenderElement,
+    primitive::region_to_irect,
     Element, Result,
 };
 
 use super::{
     children::ChildrenBox,
     data_structure::{LayerSharedPart, RcIndepLayer},
-    layer::{CustomLayer, LayerRebuilder},
+    layer::{CustomLayer, LayerAttrs, LayerRebuilder},
 };
 
 impl<El> LayerSharedPart<El>
@@ -24,17 +36,71 @@ where
         reg: &mut IndepLayerRegister,
         interval: Duration,
     ) -> Result<()> {
-        self.pub_shared.el_write_clean().render(
-            RenderElement::new(
-                lr,
-                reg,
-                unwrap_children(&mut self.expanded_children).as_render_multiple(),
-                &mut self.interact_region,
+        if self.panicked {
+            self.draw_panic_placeholder(lr);
+            return Ok(());
+        }
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            self.pub_shared.el_write_clean().render(
+                RenderElement::new(
+                    lr,
+                    reg,
+                    unwrap_children(&mut self.expanded_children).as_render_multiple(),
+                    &mut self.interact_region,
+                    interval,
+                ),
                 interval,
-            ),
-            interval,
-            self.draw_region,
-        )
+                self.draw_region,
+            )
+        }));
+
+        let result = match result {
+            Ok(result) => result,
+            Err(payload) => {
+                self.on_panic(payload);
+                self.draw_panic_placeholder(lr);
+                return Ok(());
+            }
+        };
+
+        result.map_err(|err| {
+            // errors from children have been tagged already
+            if err.is::<ElementRenderError>() || err.is::<LayerFlushError>() {
+                return err;
+            }
+
+            ElementRenderError {
+                layer: self.pub_shared.layer_info.read().unwrap().render_layer_id(),
+                element: std::any::type_name::<El>(),
+                source: err,
+            }
+            .into()
+        })
+    }
+
+    /// Mark this element as panicked, it will not be rendered or laid out anymore.
+    pub(super) fn on_panic(&mut self, payload: Box<dyn std::any::Any + Send>) {
+        self.panicked = true;
+        self.pub_shared.global().report_panic(
+            self.pub_shared.layer_info.read().unwrap().render_layer_id(),
+            std::any::type_name::<El>(),
+            payload,
+        );
+    }
+
+    fn draw_panic_placeholder(&self, lr: &mut LayerRebuilder) {
+        let canvas = lr.draw_in_place();
+
+        // drop saves left by the panicked element
+        canvas.restore_to_count(1);
+        canvas.save();
+
+        self.pub_shared
+            .global()
+            .error_policy()
+            .panic_placeholder
+            .draw(canvas, self.draw_region);
     }
 }
 
@@ -47,18 +113,52 @@ impl<El> RedrawObject for RefCell<RcIndepLayer<El>>
 where
     El: Element,
 {
-    fn redraw(
-        &self,
-        canvas: &mut Canvas,
-        reg: &mut IndepLayerRegister,
-        interval: Duration,
-    ) -> Result<()> {
+    fn redraw(&self, bounds: Rect, reg: &mut IndepLayerRegister, interval: Duration) -> Result<()> {
         let mut this = self.borrow_mut();
         let inner = &mut *this;
-        let ret = inner
+
+        // only record what the element covers
+        let mut bounds = bounds;
+        if !bounds.intersect(Rect::from_irect(region_to_irect(inner.main.draw_region))) {
+            bounds = Rect::new_empty();
+        }
+
+        let layer = inner
             .main
-            .redraw(&mut inner.extra.rebuild(canvas), reg, interval);
-        ret
+            .pub_shared
+            .layer_info
+            .read()
+            .unwrap()
+            .render_layer_id();
+        let mut lr = inner.extra.rebuild(bounds);
+        let result = inner.main.redraw(&mut lr, reg, interval);
+        result.and(lr.finish(layer))
+    }
+
+    fn element_name(&self) -> &'static str {
+        std::any::type_name::<El>()
+    }
+
+    fn clear(&self) {
+        self.borrow_mut().extra.clear();
+    }
+
+    fn capture(&self) -> Result<CapturedFrame> {
+        let this = self.borrow();
+        let rect = region_to_irect(this.main.draw_region);
+        if rect.is_empty() {
+            return Err(anyhow!("element has an empty drawing region"));
+        }
+
+        let mut surface = Surface::new_raster_n32_premul((rect.width(), rect.height()))
+            .ok_or_else(|| anyhow!("cannot create surface for capturing"))?;
+
+        let canvas = surface.canvas();
+        canvas.clear(Color::TRANSPARENT);
+        canvas.translate((-rect.left, -rect.top));
+        this.extra.composite(canvas)?;
+
+        CapturedFrame::read_from(canvas, IRect::from_wh(rect.width(), rect.height()))
     }
 }
 
@@ -66,4 +166,19 @@ impl<El> CustomLayer for RefCell<RcIndepLayer<El>> {
     fn composite(&self, canvas: &mut Canvas) -> Result<()> {
         self.borrow().extra.composite(canvas)
     }
+
+    fn attrs(&self) -> LayerAttrs {
+        let this = self.borrow();
+        let mut attrs = this.main.pub_shared.layer_info.read().unwrap().attrs();
+
+        // transform around the center of the element
+        let (start, end) = this.main.draw_region;
+        let (cx, cy) = (
+            (start.0 + end.0).to_physical() / 2.0,
+            (start.1 + end.1).to_physical() / 2.0,
+        );
+        attrs.transform =
+            &(&M44::translate(cx, cy, 0.0) * &attrs.transform) * &M44::translate(-cx, -cy, 0.0);
+        attrs
+    }
 }
@@ -1,6 +1,6 @@
 use std::{
     marker::PhantomData,
-    sync::{Arc, RwLock as StdRwLock},
+    sync::{atomic::AtomicBool, Arc, RwLock as StdRwLock},
 };
 
 use tokio::sync::RwLock;
@@ -9,8 +9,9 @@ use crate::{
     application::{content::GlobalContent, event_comp::NodeEventMgr, redraw_scheduler::LayerId},
     element::{Element, UpdateElement},
     event::EventDispatcher,
+    primitive::Direction,
     structure::{slot::Slot, MapVisitor},
-    style::StyleContainer,
+    style::{StyleContainer, StyleDirection},
     update_with::SpecificUpdate,
     UpdateWith,
 };
@@ -48,6 +49,9 @@ pub fn add_one<El, Pr, Sty, Ch, Oc>(
 pub struct EMUpdateContent<'a> {
     pub(crate) global_content: &'a Arc<GlobalContent>,
     pub(crate) dep_layer_id: LayerId,
+
+    /// Direction of the parent element.
+    pub(crate) direction: Direction,
 }
 
 impl<'a, El, Pr, Sty, Ch, Oc> MapVisitor<AddOne<El, Pr, Sty, Ch, Oc>> for EMUpdateContent<'a> {
@@ -88,9 +92,11 @@ where
                 EMUpdateContent {
                     global_content,
                     dep_layer_id,
+                    direction,
                 },
         } = updater;
 
+        let direction = resolve_direction(&styles, direction);
         let element_handle = {
             let eh = Arc::new(ElementHandle {
                 el: RwLock::new(None),
@@ -100,7 +106,12 @@ where
                     acquire_independent_layer: false,
                     parent_layer_id: dep_layer_id,
                     indep_layer_id: None,
+                    styled_attrs: Default::default(),
+                    handle_attrs: Default::default(),
                 }),
+                direction: StdRwLock::new(direction),
+                preferred_height: StdRwLock::new(None),
+                capture_pointer_on_press: AtomicBool::new(false),
             });
 
             // hold the lock prevent from being accessed
@@ -114,6 +125,7 @@ where
 
             *write = Some(el);
             drop(write);
+            eh.apply_layer_styles(&styles);
             eh
         };
 
@@ -124,13 +136,15 @@ where
             expanded_children: None,
             draw_region: Default::default(),
             interact_region: None,
+            panicked: false,
         });
 
         ElementModel {
-            event_mgr: NodeEventMgr::new(),
+            event_mgr: NodeEventMgr::new(element_handle.ed.clone()),
             slot_cache: Slot::new(children.create_model(&EMUpdateContent {
                 global_content,
                 dep_layer_id,
+                direction,
             })),
             styles,
             shared,
@@ -156,16 +170,21 @@ where
                 EMUpdateContent {
                     global_content: _,
                     dep_layer_id,
+                    direction,
                 },
         } = updater;
 
+        let direction = resolve_direction(&styles, direction);
+        *self.pub_shared.direction.write().unwrap() = direction;
         self.pub_shared.layer_info.write().unwrap().parent_layer_id = dep_layer_id;
+        self.pub_shared.apply_layer_styles(&styles);
 
         children.update_model(
             &mut self.slot_cache.borrow_mut(),
             &EMUpdateContent {
                 global_content: self.pub_shared.global(),
                 dep_layer_id,
+                direction,
             },
             &mut equality_matters,
         );
@@ -182,6 +201,12 @@ where
     }
 }
 
+fn resolve_direction(styles: &impl StyleContainer, parent: Direction) -> Direction {
+    styles
+        .get_style::<StyleDirection>()
+        .map_or(parent, |style| style.resolve(parent))
+}
+
 impl<'a, El, Pr, Sty, Ch, Oc> SpecificUpdate for ElementModelUpdater<'a, El, Pr, Sty, Ch, Oc>
 where
     El: Element,
@@ -6,6 +6,7 @@ use crate::{
         children::{ChildrenBox, ChildrenNodes},
         EMUpdateContent,
     },
+    primitive::Direction,
 };
 
 pub use peek_styles::PeekStyles;
@@ -16,6 +17,7 @@ pub struct ChildrenSetter<'a> {
     set_children: &'a mut Option<ChildrenBox>,
     global_content: &'a Arc<GlobalContent>,
     dep_layer_id: LayerId,
+    direction: Direction,
 }
 
 impl<'a> ChildrenSetter<'a> {
@@ -23,11 +25,13 @@ impl<'a> ChildrenSetter<'a> {
         set_children: &'a mut Option<ChildrenBox>,
         gc: &'a Arc<GlobalContent>,
         dep_layer_id: LayerId,
+        direction: Direction,
     ) -> Self {
         Self {
             set_children,
             global_content: gc,
             dep_layer_id,
+            direction,
         }
     }
 
@@ -38,6 +42,7 @@ impl<'a> ChildrenSetter<'a> {
         let updater = EMUpdateContent {
             global_content: self.global_content,
             dep_layer_id: self.dep_layer_id,
+            direction: self.direction,
         };
 
         let model = match self.set_children {
@@ -56,7 +61,7 @@ impl<'a> ChildrenSetter<'a> {
                     .unwrap()
                     .as_render_multiple()
                     .as_any()
-                    .downcast_ref()
+                    .downcast_mut()
                     .unwrap()
             }
         };
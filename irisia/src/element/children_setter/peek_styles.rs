@@ -1,16 +1,18 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
 use crate::{
-    dom::ElementModel,
+    dom::{children::RenderMultiple, ElementHandle, ElementModel},
+    primitive::Region,
     structure::{Visit, Visitor},
     style::StyleContainer,
     Result, StyleReader,
 };
 
-pub struct PeekStyles<'a, T>(&'a T);
+/// Children just set, to peek their styles and lay them out.
+pub struct PeekStyles<'a, T>(&'a mut T);
 
 impl<'a, T> PeekStyles<'a, T> {
-    pub(super) fn new(t: &'a T) -> Self {
+    pub(super) fn new(t: &'a mut T) -> Self {
         PeekStyles(t)
     }
 
@@ -25,6 +27,27 @@ impl<'a, T> PeekStyles<'a, T> {
             _sr: PhantomData,
         });
     }
+
+    /// Lay out the children in order, one region for each. Returns
+    /// error if there are fewer regions than children.
+    pub fn layout<I>(&mut self, regions: I) -> Result<()>
+    where
+        I: IntoIterator<Item = Region>,
+        T: RenderMultiple,
+    {
+        self.0.layout(&mut regions.into_iter())
+    }
+
+    /// Handles of the children in order, only available if all of them
+    /// are `El`.
+    pub fn handles<El>(&self) -> Vec<Arc<ElementHandle<El>>>
+    where
+        T: Visit<HandleVis<El>>,
+    {
+        let mut vis = HandleVis(Vec::new());
+        let _ = self.0.visit(&mut vis);
+        vis.0
+    }
 }
 
 pub struct Vis<F, Sr> {
@@ -43,3 +66,12 @@ where
         Ok(())
     }
 }
+
+pub struct HandleVis<El>(Vec<Arc<ElementHandle<El>>>);
+
+impl<El, Sty, Sc> Visitor<ElementModel<El, Sty, Sc>> for HandleVis<El> {
+    fn visit(&mut self, data: &ElementModel<El, Sty, Sc>) -> Result<()> {
+        self.0.push(data.handle().clone());
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
 use std::{sync::Arc, time::Duration};
 
-use crate::{dom::children::ChildrenNodes, primitive::Region, Result};
+use crate::{primitive::Region, Result};
 
 pub use self::{children_setter::ChildrenSetter, render_element::RenderElement};
-pub use crate::dom::ElementHandle;
+pub use crate::dom::{children::ChildrenNodes, ElementHandle};
 
 mod children_setter;
 pub mod props;
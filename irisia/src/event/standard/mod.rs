@@ -52,3 +52,28 @@ pub struct PointerLeft;
 pub struct Click {
     pub is_current: bool,
 }
+
+/// Pressed outside of a portal, emitted to the dispatcher given by
+/// [`Portal::on_dismiss`]. The portal is not removed by itself.
+///
+/// [`Portal::on_dismiss`]: crate::structure::portal::Portal::on_dismiss
+#[derive(Event, Clone, Copy)]
+pub struct Dismissed;
+
+/// Text being composed in the input method, emitted to the focused
+/// element accepting IME, see [`ElementHandle::set_ime_caret`]. Empty
+/// text means the composition is cleared.
+///
+/// [`ElementHandle::set_ime_caret`]: crate::element::ElementHandle::set_ime_caret
+#[derive(Event, Clone)]
+pub struct ImePreedit {
+    pub text: String,
+
+    /// Byte range of the cursor in `text`, `None` to hide the cursor.
+    pub cursor: Option<(usize, usize)>,
+}
+
+/// Text committed by the input method, emitted to the focused element
+/// accepting IME.
+#[derive(Event, Clone)]
+pub struct ImeCommit(pub String);
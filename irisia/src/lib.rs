@@ -14,6 +14,7 @@ macro_rules! inner_error {
 }
 
 pub mod application;
+pub mod clipboard;
 pub(crate) mod dom;
 pub mod element;
 pub mod event;
@@ -30,7 +31,8 @@ pub use application::Window;
 pub use element::Element;
 pub use event::Event;
 pub use irisia_backend::{
-    runtime::exit_app, skia_safe, start_runtime, winit, StaticWindowEvent, WinitWindow,
+    runtime::{exit_app, set_default_pacing, ControlFlowMode, FramePacing},
+    skia_safe, start_runtime, winit, CapturedFrame, StaticWindowEvent, WinitWindow,
 };
 pub use irisia_macros::{build, main, props, style, Event, Style, StyleReader};
 pub use style::{reader::StyleReader, Style};
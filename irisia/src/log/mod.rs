@@ -1,28 +1,70 @@
+//! Logging of irisia is based on [`tracing`]. Nothing will be printed until a
+//! subscriber is installed, by [`set_subscriber`] or any other way `tracing`
+//! supports.
+//!
+//! Events and spans of the framework use the targets below, so they can
+//! be filtered per subsystem.
+
+use anyhow::anyhow;
+pub use tracing;
+
+use crate::Result;
+
+pub use irisia_backend::log::BACKEND;
+
+/// Reading and writing the clipboard.
+pub const CLIPBOARD: &str = "irisia::clipboard";
+
+/// Window event emitting and dispatching.
+pub const EVENT: &str = "irisia::event";
+
+/// Layout of elements.
+pub const LAYOUT: &str = "irisia::layout";
+
+/// Rendering and compositing of layers.
+pub const RENDER: &str = "irisia::render";
+
+/// Install `subscriber` as the global default subscriber.
+/// This can only be done once in the whole app.
+pub fn set_subscriber<S>(subscriber: S) -> Result<()>
+where
+    S: tracing::Subscriber + Send + Sync + 'static,
+{
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|_| anyhow!("a global subscriber has been installed"))
+}
+
 #[macro_export]
-macro_rules! info {
+macro_rules! trace {
     ($($tt:tt)+) => {
-        $crate::log::println!("INFO", $($tt)*);
+        $crate::log::tracing::trace!($($tt)+)
     };
 }
 
 #[macro_export]
-macro_rules! warning {
+macro_rules! debug {
     ($($tt:tt)+) => {
-        $crate::log::println!("WARNING", $($tt)*);
+        $crate::log::tracing::debug!($($tt)+)
     };
 }
 
 #[macro_export]
-macro_rules! error {
+macro_rules! info {
     ($($tt:tt)+) => {
-        $crate::log::println!("ERROR", $($tt)*);
+        $crate::log::tracing::info!($($tt)+)
     };
 }
 
-#[doc(hidden)]
 #[macro_export]
-macro_rules! println {
-    ($prefix:literal, $fmt:literal $($tt:tt)*) => {
-        println!(::std::concat!("[{} {}: {}] ", $fmt), $prefix, ::std::file!(), ::std::line!() $($tt)*)
+macro_rules! warning {
+    ($($tt:tt)+) => {
+        $crate::log::tracing::warn!($($tt)+)
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($tt:tt)+) => {
+        $crate::log::tracing::error!($($tt)+)
     };
 }
@@ -0,0 +1,19 @@
+/// Direction of text and layout, decided by [`StyleDirection`] of an
+/// element and its ancestors.
+///
+/// [`StyleDirection`]: crate::style::StyleDirection
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Left to right, the default.
+    #[default]
+    Ltr,
+
+    /// Right to left, for scripts like Arabic and Hebrew.
+    Rtl,
+}
+
+impl Direction {
+    pub fn is_rtl(self) -> bool {
+        self == Direction::Rtl
+    }
+}
@@ -1,7 +1,20 @@
-pub use self::{pixel::Pixel, point::Point};
+use irisia_backend::skia_safe::IRect;
 
+pub use self::{direction::Direction, pixel::Pixel, point::Point};
+
+pub mod direction;
 pub mod pixel;
 pub mod point;
 
 pub type Result<T> = anyhow::Result<T>;
 pub type Region = (Point, Point);
+
+/// Smallest integer rect in physical pixels containing the region.
+pub(crate) fn region_to_irect((start, end): Region) -> IRect {
+    IRect::from_ltrb(
+        start.0.to_physical().floor() as _,
+        start.1.to_physical().floor() as _,
+        end.0.to_physical().ceil() as _,
+        end.1.to_physical().ceil() as _,
+    )
+}
@@ -0,0 +1,112 @@
+use std::{any::Any, time::Duration};
+
+use crate::{
+    application::{event_comp::NewPointerEvent, redraw_scheduler::IndepLayerRegister},
+    dom::{
+        children::{ChildrenNodes, RenderMultiple},
+        layer::LayerRebuilder,
+        EMUpdateContent,
+    },
+    primitive::Region,
+    update_with::SpecificUpdate,
+    Result,
+};
+
+use super::{MapVisit, UpdateWith};
+
+type Build = Box<
+    dyn for<'a> FnOnce(
+        Option<&mut dyn RenderMultiple>,
+        &EMUpdateContent<'a>,
+    ) -> Option<Box<dyn RenderMultiple>>,
+>;
+
+/// Children structure with its type erased, for structures whose types
+/// cannot be named, like ones returned by a function stored in props.
+///
+/// The model is updated if the structure has the same type as the last
+/// one, otherwise it is recreated.
+pub struct Boxed(Build);
+
+impl Boxed {
+    pub fn new<T>(children: T) -> Self
+    where
+        T: ChildrenNodes + 'static,
+    {
+        let build = move |model: Option<&mut dyn RenderMultiple>,
+                          updater: &EMUpdateContent|
+              -> Option<Box<dyn RenderMultiple>> {
+            let model = model.and_then(|model| model.as_any().downcast_mut::<T::Model>());
+            match model {
+                Some(model) => {
+                    children.update_model(model, updater, &mut false);
+                    None
+                }
+                None => Some(Box::new(children.create_model(updater))),
+            }
+        };
+
+        Boxed(Box::new(build))
+    }
+}
+
+#[doc(hidden)]
+pub struct BoxedUpdater<'a> {
+    boxed: Boxed,
+    content: EMUpdateContent<'a>,
+}
+
+pub struct BoxedModel(Box<dyn RenderMultiple>);
+
+impl<'a> MapVisit<EMUpdateContent<'a>> for Boxed {
+    type Output = BoxedUpdater<'a>;
+    fn map(self, visitor: &EMUpdateContent<'a>) -> Self::Output {
+        BoxedUpdater {
+            boxed: self,
+            content: *visitor,
+        }
+    }
+}
+
+impl UpdateWith<BoxedUpdater<'_>> for BoxedModel {
+    fn create_with(updater: BoxedUpdater) -> Self {
+        let model = (updater.boxed.0)(None, &updater.content);
+        BoxedModel(model.unwrap_or_else(|| unreachable!("model not created")))
+    }
+
+    fn update_with(&mut self, updater: BoxedUpdater, _: bool) -> bool {
+        if let Some(model) = (updater.boxed.0)(Some(&mut *self.0), &updater.content) {
+            self.0 = model;
+        }
+
+        // structures cannot be compared
+        false
+    }
+}
+
+impl SpecificUpdate for BoxedUpdater<'_> {
+    type UpdateTo = BoxedModel;
+}
+
+impl RenderMultiple for BoxedModel {
+    fn render(
+        &mut self,
+        lr: &mut LayerRebuilder,
+        reg: &mut IndepLayerRegister,
+        interval: Duration,
+    ) -> Result<()> {
+        self.0.render(lr, reg, interval)
+    }
+
+    fn layout(&mut self, iter: &mut dyn Iterator<Item = Region>) -> Result<()> {
+        self.0.layout(iter)
+    }
+
+    fn emit_event(&mut self, npe: &NewPointerEvent) -> bool {
+        self.0.emit_event(npe)
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
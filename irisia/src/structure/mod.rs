@@ -1,9 +1,14 @@
 use crate::{update_with::UpdateWith, Result};
 
+pub use crate::dom::add_one;
+
+pub mod boxed;
 pub mod branch;
 pub mod chain;
 pub mod empty;
 pub mod once;
+pub mod portal;
+pub mod recycle;
 pub mod repeating;
 pub(crate) mod slot;
 
@@ -0,0 +1,343 @@
+use std::{any::Any, cell::RefCell, rc::Rc, sync::Arc, time::Duration};
+
+use anyhow::anyhow;
+
+use crate::{
+    application::{
+        content::GlobalContent,
+        event_comp::{global::new_event::PointerStateChange, NewPointerEvent},
+        redraw_scheduler::{IndepLayerRegister, OverlayObject, OVERLAY_LAYER_ID},
+    },
+    dom::{
+        children::{ChildrenNodes, RenderMultiple},
+        layer::LayerRebuilder,
+        EMUpdateContent,
+    },
+    event::{standard::Dismissed, EventDispatcher},
+    primitive::{Direction, Pixel, Point, Region},
+    update_with::SpecificUpdate,
+    Result,
+};
+
+use super::{MapVisit, UpdateWith};
+
+/// Side of the anchor to place a portal at. `Start` and `End` are
+/// mirrored in RTL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Placement {
+    #[default]
+    Below,
+    Above,
+    Start,
+    End,
+}
+
+/// Children drawn in the overlay layer of the window, above all other
+/// elements and out of the clip of their parent, for tooltips, dropdowns
+/// and menus.
+///
+/// The region laid out for the portal by its parent is the anchor, the
+/// children are placed beside it, flipped to the other side if there is
+/// no room, and kept in the window. Portals opened later are above, and
+/// receive the pointer before elements below them. Pressing outside of
+/// a portal and its anchor emits [`Dismissed`], see [`Self::on_dismiss`].
+pub struct Portal<T> {
+    children: T,
+    options: PortalOptions,
+    dismiss: Option<EventDispatcher>,
+}
+
+#[derive(Clone, Copy)]
+struct PortalOptions {
+    placement: Placement,
+    width: Option<Pixel>,
+    height: Option<Pixel>,
+    gap: Pixel,
+}
+
+impl<T> Portal<T> {
+    /// Portal below the anchor, as wide and as high as the anchor.
+    pub fn new(children: T) -> Self {
+        Portal {
+            children,
+            options: PortalOptions {
+                placement: Placement::Below,
+                width: None,
+                height: None,
+                gap: Pixel(0.0),
+            },
+            dismiss: None,
+        }
+    }
+
+    pub fn placement(mut self, placement: Placement) -> Self {
+        self.options.placement = placement;
+        self
+    }
+
+    pub fn width(mut self, width: Pixel) -> Self {
+        self.options.width = Some(width);
+        self
+    }
+
+    pub fn height(mut self, height: Pixel) -> Self {
+        self.options.height = Some(height);
+        self
+    }
+
+    /// Space between the anchor and the portal.
+    pub fn gap(mut self, gap: Pixel) -> Self {
+        self.options.gap = gap;
+        self
+    }
+
+    /// Emit [`Dismissed`] to `ed` when pressed outside of the portal and
+    /// its anchor, usually the dispatcher of the element owning it, which
+    /// should remove the portal then.
+    pub fn on_dismiss(mut self, ed: EventDispatcher) -> Self {
+        self.dismiss = Some(ed);
+        self
+    }
+}
+
+#[doc(hidden)]
+pub struct PortalUpdater<'a, T> {
+    portal: Portal<T>,
+    content: EMUpdateContent<'a>,
+}
+
+pub struct PortalModel<M> {
+    shared: Rc<RefCell<PortalShared<M>>>,
+    registered: bool,
+}
+
+struct PortalShared<M> {
+    children: M,
+    options: PortalOptions,
+    dismiss: Option<EventDispatcher>,
+    global_content: Arc<GlobalContent>,
+    direction: Direction,
+    anchor: Option<Region>,
+    region: Option<Region>,
+}
+
+impl<'a, T> MapVisit<EMUpdateContent<'a>> for Portal<T> {
+    type Output = PortalUpdater<'a, T>;
+    fn map(self, visitor: &EMUpdateContent<'a>) -> Self::Output {
+        PortalUpdater {
+            portal: self,
+            content: *visitor,
+        }
+    }
+}
+
+/// Children of portals are drawn in the overlay layer.
+fn overlay_content<'a>(content: &EMUpdateContent<'a>) -> EMUpdateContent<'a> {
+    EMUpdateContent {
+        dep_layer_id: OVERLAY_LAYER_ID,
+        ..*content
+    }
+}
+
+impl<T> UpdateWith<PortalUpdater<'_, T>> for PortalModel<T::Model>
+where
+    T: ChildrenNodes,
+{
+    fn create_with(updater: PortalUpdater<T>) -> Self {
+        let PortalUpdater { portal, content } = updater;
+        let shared = PortalShared {
+            children: portal.children.create_model(&overlay_content(&content)),
+            options: portal.options,
+            dismiss: portal.dismiss,
+            global_content: content.global_content.clone(),
+            direction: content.direction,
+            anchor: None,
+            region: None,
+        };
+
+        PortalModel {
+            shared: Rc::new(RefCell::new(shared)),
+            registered: false,
+        }
+    }
+
+    fn update_with(&mut self, updater: PortalUpdater<T>, _: bool) -> bool {
+        let PortalUpdater { portal, content } = updater;
+        let mut shared = self.shared.borrow_mut();
+
+        portal
+            .children
+            .update_model(&mut shared.children, &overlay_content(&content), &mut false);
+        shared.options = portal.options;
+        shared.dismiss = portal.dismiss;
+        shared.direction = content.direction;
+
+        // structures cannot be compared
+        false
+    }
+}
+
+impl<T> SpecificUpdate for PortalUpdater<'_, T>
+where
+    T: ChildrenNodes,
+{
+    type UpdateTo = PortalModel<T::Model>;
+}
+
+impl<M> RenderMultiple for PortalModel<M>
+where
+    M: RenderMultiple,
+{
+    fn render(
+        &mut self,
+        _: &mut LayerRebuilder,
+        reg: &mut IndepLayerRegister,
+        _: Duration,
+    ) -> Result<()> {
+        // drawn in the overlay layer rather than the layer of the parent
+        if !self.registered {
+            let shared: Rc<dyn OverlayObject> = self.shared.clone();
+            reg.reg_overlay(Rc::downgrade(&shared));
+            self.registered = true;
+        }
+        Ok(())
+    }
+
+    fn layout(&mut self, iter: &mut dyn Iterator<Item = Region>) -> Result<()> {
+        match iter.next() {
+            Some(anchor) => self.shared.borrow_mut().layout(anchor),
+            None => Err(anyhow!("regions in the iterator is not enough")),
+        }
+    }
+
+    fn emit_event(&mut self, _: &NewPointerEvent) -> bool {
+        // received from the window before the root element
+        false
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl<M> Drop for PortalModel<M> {
+    fn drop(&mut self) {
+        if self.registered {
+            self.shared
+                .borrow()
+                .global_content
+                .request_redraw(OVERLAY_LAYER_ID);
+        }
+    }
+}
+
+impl<M> PortalShared<M>
+where
+    M: RenderMultiple,
+{
+    fn layout(&mut self, anchor: Region) -> Result<()> {
+        let region = self.place(anchor);
+        if self.region != Some(region) {
+            self.global_content.request_redraw(OVERLAY_LAYER_ID);
+        }
+
+        self.anchor = Some(anchor);
+        self.region = Some(region);
+        self.children.layout(&mut std::iter::repeat(region))
+    }
+
+    /// Region beside `anchor`, flipped if overflowing the window on the
+    /// placed side, then moved into the window.
+    fn place(&self, (start, end): Region) -> Region {
+        let size = self.global_content.window().inner_size();
+        let window = (
+            Pixel::from_physical(size.width as _).0,
+            Pixel::from_physical(size.height as _).0,
+        );
+
+        let PortalOptions {
+            placement,
+            width,
+            height,
+            gap,
+        } = self.options;
+        let width = width.unwrap_or(end.0 - start.0).0;
+        let height = height.unwrap_or(end.1 - start.1).0;
+        let gap = gap.0;
+
+        let rtl = self.direction.is_rtl();
+        let (before_x, after_x) = (start.0 .0 - gap - width, end.0 .0 + gap);
+        let (before_y, after_y) = (start.1 .0 - gap - height, end.1 .0 + gap);
+
+        let fits_above = before_y >= 0.0;
+        let fits_below = after_y + height <= window.1;
+        let fits_left = before_x >= 0.0;
+        let fits_right = after_x + width <= window.0;
+
+        let (x, y) = match placement {
+            Placement::Below | Placement::Above => {
+                let below = match placement {
+                    Placement::Below => fits_below || !fits_above,
+                    _ => !fits_above && fits_below,
+                };
+                let x = if rtl { end.0 .0 - width } else { start.0 .0 };
+                (x, if below { after_y } else { before_y })
+            }
+            Placement::Start | Placement::End => {
+                // the start side is at the left in LTR
+                let left = if (placement == Placement::Start) != rtl {
+                    fits_left || !fits_right
+                } else {
+                    !fits_right && fits_left
+                };
+                (if left { before_x } else { after_x }, start.1 .0)
+            }
+        };
+
+        let x = x.min(window.0 - width).max(0.0);
+        let y = y.min(window.1 - height).max(0.0);
+        (
+            Point(Pixel(x), Pixel(y)),
+            Point(Pixel(x + width), Pixel(y + height)),
+        )
+    }
+}
+
+impl<M> OverlayObject for RefCell<PortalShared<M>>
+where
+    M: RenderMultiple,
+{
+    fn render(
+        &self,
+        lr: &mut LayerRebuilder,
+        reg: &mut IndepLayerRegister,
+        interval: Duration,
+    ) -> Result<()> {
+        self.borrow_mut().children.render(lr, reg, interval)
+    }
+
+    fn emit_event(&self, npe: &NewPointerEvent) -> bool {
+        let mut this = self.borrow_mut();
+        let Some(region) = this.region else {
+            return false;
+        };
+
+        let contains = |(start, end): Region, p: Point| p.abs_ge(start) && p.abs_le(end);
+        let position = npe.hit_position();
+        let over = position.map_or(false, |p| contains(region, p));
+        let entered = this.children.emit_event(npe);
+
+        if let (PointerStateChange::Press, Some(position)) = (npe.pointer_state_change, position) {
+            let on_anchor = this
+                .anchor
+                .map_or(false, |anchor| contains(anchor, position));
+            if !over && !entered && !on_anchor {
+                if let Some(ed) = &this.dismiss {
+                    ed.emit_sys(Dismissed);
+                }
+            }
+        }
+
+        over || entered
+    }
+}
@@ -0,0 +1,167 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::update_with::SpecificUpdate;
+use crate::Result;
+
+use super::{repeating::MapIter, MapVisit, UpdateWith, Visit, VisitLen, VisitMut};
+
+const DEFAULT_POOL_SIZE: usize = 16;
+
+/// Keyed structure like [`Repeat`](super::repeating::Repeat), but models
+/// of keys no longer given are kept in a pool, and updated with the items
+/// of new keys instead of creating new models.
+///
+/// Recycled models keep their states which are not decided by the
+/// updater, so it fits items whose states are fully decided by props.
+pub struct Recycle<I> {
+    iter: I,
+    pool_size: usize,
+}
+
+impl<I: Iterator> Recycle<I> {
+    pub fn new(iter: I) -> Self {
+        Recycle {
+            iter,
+            pool_size: DEFAULT_POOL_SIZE,
+        }
+    }
+
+    /// Set the max count of models kept for recycling, `16` by default.
+    pub fn pool_size(mut self, pool_size: usize) -> Self {
+        self.pool_size = pool_size;
+        self
+    }
+}
+
+pub struct RecycleModel<K, T> {
+    map: HashMap<K, T>,
+    order: Vec<K>,
+    pool: Vec<T>,
+}
+
+impl<K, T> RecycleModel<K, T> {
+    /// Keys in order.
+    pub fn keys(&self) -> &[K] {
+        &self.order
+    }
+}
+
+// map
+
+impl<I, K, T, V> MapVisit<V> for Recycle<I>
+where
+    I: Iterator<Item = (K, T)>,
+    T: MapVisit<V>,
+    V: Clone,
+{
+    type Output = Recycle<MapIter<I, V>>;
+    fn map(self, visitor: &V) -> Self::Output {
+        Recycle {
+            iter: MapIter {
+                iter: self.iter,
+                map_visit: visitor.clone(),
+            },
+            pool_size: self.pool_size,
+        }
+    }
+}
+
+// visit
+
+impl<K, T> VisitLen for RecycleModel<K, T> {
+    fn len(&self) -> usize {
+        self.order.len()
+    }
+}
+
+impl<K, T, V> Visit<V> for RecycleModel<K, T>
+where
+    K: Hash + Eq,
+    T: Visit<V>,
+{
+    fn visit(&self, visitor: &mut V) -> Result<()> {
+        for k in self.order.iter() {
+            self.map[k].visit(visitor)?;
+        }
+        Ok(())
+    }
+}
+
+impl<K, T, V> VisitMut<V> for RecycleModel<K, T>
+where
+    K: Hash + Eq,
+    T: VisitMut<V>,
+{
+    fn visit_mut(&mut self, visitor: &mut V) -> Result<()> {
+        for k in self.order.iter() {
+            self.map.get_mut(k).unwrap().visit_mut(visitor)?;
+        }
+        Ok(())
+    }
+}
+
+// update
+
+impl<K, I, T, U> UpdateWith<Recycle<I>> for RecycleModel<K, T>
+where
+    K: Hash + Eq + Clone + 'static,
+    I: Iterator<Item = (K, U)>,
+    T: UpdateWith<U>,
+{
+    fn create_with(update: Recycle<I>) -> Self {
+        let mut output = Self {
+            map: HashMap::new(),
+            order: Vec::new(),
+            pool: Vec::new(),
+        };
+
+        for (k, v) in update.iter {
+            output.order.push(k.clone());
+            output.map.insert(k, T::create_with(v));
+        }
+
+        output
+    }
+
+    fn update_with(&mut self, update: Recycle<I>, mut equality_matters: bool) -> bool {
+        let mut old_map = std::mem::take(&mut self.map);
+        let old_order = std::mem::take(&mut self.order);
+        let mut new_items = Vec::new();
+
+        for (k, v) in update.iter {
+            self.order.push(k.clone());
+            match old_map.remove(&k) {
+                Some(mut model) => {
+                    equality_matters &= model.update_with(v, equality_matters);
+                    self.map.insert(k, model);
+                }
+                None => new_items.push((k, v)),
+            }
+        }
+
+        // models of removed keys are updated with the new items, after
+        // all of them are known
+        self.pool.extend(old_map.into_values());
+        for (k, v) in new_items {
+            let model = match self.pool.pop() {
+                Some(mut model) => {
+                    model.update_with(v, false);
+                    model
+                }
+                None => T::create_with(v),
+            };
+            self.map.insert(k, model);
+        }
+        self.pool.truncate(update.pool_size);
+
+        equality_matters && self.order == old_order
+    }
+}
+
+impl<I, K, V> SpecificUpdate for Recycle<I>
+where
+    I: Iterator<Item = (K, V)>,
+    V: SpecificUpdate,
+{
+    type UpdateTo = RecycleModel<K, V::UpdateTo>;
+}
@@ -26,8 +26,8 @@ pub struct RepeatModel<K, T> {
 // map
 
 pub struct MapIter<I, V> {
-    iter: I,
-    map_visit: V,
+    pub(super) iter: I,
+    pub(super) map_visit: V,
 }
 
 impl<I, K, T, V> Iterator for MapIter<I, V>
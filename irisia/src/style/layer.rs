@@ -0,0 +1,97 @@
+use irisia_backend::skia_safe::{BlendMode, M44, V3};
+
+use crate::{self as irisia, primitive::Pixel, Style};
+
+/// Opacity of the element's independent layer.
+#[derive(Debug, Style, Clone, Copy, PartialEq)]
+#[style(from)]
+pub struct StyleOpacity(pub f32);
+
+/// Transform of the element's independent layer, applied around
+/// the center of its drawing region.
+#[derive(Debug, Style, Clone, PartialEq)]
+#[style(from, from = "", impl_default)]
+pub struct StyleTransform(#[style(default = "M44::new_identity()")] pub M44);
+
+impl StyleTransform {
+    pub fn translate_x(&mut self, x: Pixel) {
+        self.0.pre_translate(x.to_physical(), 0.0, None);
+    }
+
+    pub fn translate_y(&mut self, y: Pixel) {
+        self.0.pre_translate(0.0, y.to_physical(), None);
+    }
+
+    pub fn translate_z(&mut self, z: Pixel) {
+        self.0.pre_translate(0.0, 0.0, z.to_physical());
+    }
+
+    pub fn scale(&mut self, scale: f32) {
+        self.0.pre_scale(scale, scale);
+    }
+
+    pub fn scale_x(&mut self, scale: f32) {
+        self.0.pre_scale(scale, 1.0);
+    }
+
+    pub fn scale_y(&mut self, scale: f32) {
+        self.0.pre_scale(1.0, scale);
+    }
+
+    /// Rotate clockwise in degrees.
+    pub fn rotate(&mut self, degrees: f32) {
+        self.rotate_axis((0.0, 0.0, 1.0), degrees);
+    }
+
+    pub fn rotate_x(&mut self, degrees: f32) {
+        self.rotate_axis((1.0, 0.0, 0.0), degrees);
+    }
+
+    pub fn rotate_y(&mut self, degrees: f32) {
+        self.rotate_axis((0.0, 1.0, 0.0), degrees);
+    }
+
+    fn rotate_axis(&mut self, (x, y, z): (f32, f32, f32), degrees: f32) {
+        let rotation = M44::rotate(V3::new(x, y, z), degrees.to_radians());
+        self.0.pre_concat(&rotation);
+    }
+}
+
+/// Blend mode used to composite the element's independent layer.
+#[derive(Debug, Style, Clone, Copy, PartialEq)]
+#[style(from, from = "", impl_default)]
+pub struct StyleBlendMode(#[style(default = "BlendMode::SrcOver")] pub BlendMode);
+
+impl StyleBlendMode {
+    pub fn normal(&mut self) {
+        self.0 = BlendMode::SrcOver;
+    }
+
+    pub fn multiply(&mut self) {
+        self.0 = BlendMode::Multiply;
+    }
+
+    pub fn screen(&mut self) {
+        self.0 = BlendMode::Screen;
+    }
+
+    pub fn overlay(&mut self) {
+        self.0 = BlendMode::Overlay;
+    }
+
+    pub fn darken(&mut self) {
+        self.0 = BlendMode::Darken;
+    }
+
+    pub fn lighten(&mut self) {
+        self.0 = BlendMode::Lighten;
+    }
+
+    pub fn difference(&mut self) {
+        self.0 = BlendMode::Difference;
+    }
+
+    pub fn plus(&mut self) {
+        self.0 = BlendMode::Plus;
+    }
+}
@@ -27,16 +27,21 @@
 pub mod add_style;
 pub mod branch;
 pub mod chain;
+pub mod layer;
 pub mod reader;
 
 use std::any::Any;
 
-use crate::{self as irisia, primitive::Pixel};
+use crate::{
+    self as irisia,
+    primitive::{Direction, Pixel},
+};
 pub use add_style::*;
 pub use branch::*;
 pub use chain::*;
 use irisia_backend::skia_safe::Color;
 use irisia_macros::Style;
+pub use layer::*;
 
 pub use reader::StyleReader;
 
@@ -46,6 +51,33 @@ pub trait Style: Clone + 'static {}
 #[style(from)]
 pub struct StyleColor(pub Color);
 
+/// Direction of text and layout, inherited by children elements. `inherit`
+/// takes the direction of the parent, and elements without a parent
+/// are left to right. Read the direction decided for an element with
+/// [`ElementHandle::direction`](crate::element::ElementHandle::direction).
+#[derive(Debug, Style, Clone, Copy, PartialEq)]
+#[style(from = "", impl_default)]
+pub struct StyleDirection(#[style(default = "None")] pub Option<Direction>);
+
+impl StyleDirection {
+    pub fn ltr(&mut self) {
+        self.0 = Some(Direction::Ltr);
+    }
+
+    pub fn rtl(&mut self) {
+        self.0 = Some(Direction::Rtl);
+    }
+
+    pub fn inherit(&mut self) {
+        self.0 = None;
+    }
+
+    /// Direction of an element with this style, which inherits `parent`.
+    pub fn resolve(&self, parent: Direction) -> Direction {
+        self.0.unwrap_or(parent)
+    }
+}
+
 #[derive(Debug, Style, Clone, Copy, PartialEq)]
 pub enum XAxisBound {
     #[style(option)]